@@ -0,0 +1,205 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::util::mod_file::{read_mod_json, write_mod_json};
+use crate::{done, fatal, info, warn};
+
+/// Prefix of strings that still need translating
+const TODO_PREFIX: &str = "TODO: ";
+
+#[derive(Subcommand, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum I18n {
+	/// Create a folder of language strings and add it to the mod's resources
+	Init {
+		/// Languages to translate the mod into, like `es` or `pt-BR`
+		#[clap(num_args(0..))]
+		languages: Vec<String>,
+
+		/// Language the mod's strings are written in
+		#[clap(long, default_value = "en")]
+		base: String,
+
+		/// Folder of the language files, relative to the project
+		#[clap(long, default_value = "lang")]
+		dir: PathBuf,
+	},
+
+	/// Add strings missing from translations as TODO entries and report
+	/// strings the base language doesn't have anymore
+	Sync {
+		/// Language the mod's strings are written in
+		#[clap(long, default_value = "en")]
+		base: String,
+
+		/// Folder of the language files, relative to the project
+		#[clap(long, default_value = "lang")]
+		dir: PathBuf,
+
+		/// Remove strings the base language doesn't have instead of only
+		/// reporting them
+		#[clap(long)]
+		prune: bool,
+	},
+}
+
+fn check_language(code: &str) {
+	if !Regex::new(r"^[a-z]{2,3}([-_][A-Za-z0-9]+)*$").unwrap().is_match(code) {
+		fatal!("'{}' is not a valid language code, use one like `es` or `pt-BR`", code);
+	}
+}
+
+fn read_strings(path: &Path) -> Map<String, Value> {
+	let text = fs::read_to_string(path)
+		.unwrap_or_else(|e| fatal!("Unable to read {}: {}", path.display(), e));
+	match serde_json::from_str(&text) {
+		Ok(Value::Object(strings)) => strings,
+		Ok(_) => fatal!("{} should be an object of strings", path.display()),
+		Err(e) => fatal!("Unable to parse {}: {}", path.display(), e),
+	}
+}
+
+/// Write with tabs like mod.json, since translators edit these by hand
+fn write_strings(path: &Path, strings: &Map<String, Value>) {
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(Vec::new(), formatter);
+	strings.serialize(&mut ser).unwrap();
+	fs::write(path, ser.into_inner())
+		.unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+}
+
+fn init(project: &Path, languages: Vec<String>, base: String, dir: PathBuf) {
+	check_language(&base);
+	languages.iter().for_each(|l| check_language(l));
+
+	let lang_dir = project.join(&dir);
+	fs::create_dir_all(&lang_dir).expect("Unable to create language folder");
+
+	let base_path = lang_dir.join(format!("{}.json", base));
+	if base_path.exists() {
+		info!("{} already exists, keeping it", base_path.display());
+	} else {
+		let mut strings = Map::new();
+		strings.insert("greeting".into(), json!("Hello!"));
+		write_strings(&base_path, &strings);
+		info!("Created {}", base_path.display());
+	}
+	for language in &languages {
+		let path = lang_dir.join(format!("{}.json", language));
+		if !path.exists() {
+			write_strings(&path, &Map::new());
+			info!("Created {}", path.display());
+		}
+	}
+
+	// The mod reads the strings at runtime, so they have to be packaged
+	let pattern = format!("{}/*.json", dir.to_string_lossy().replace('\\', "/"));
+	let mut mod_json = read_mod_json(project).unwrap_or_else(|e| fatal!("{}", e));
+	let files = mod_json
+		.as_object_mut()
+		.unwrap_or_else(|| fatal!("mod.json is not an object"))
+		.entry("resources")
+		.or_insert_with(|| json!({}))
+		.as_object_mut()
+		.unwrap_or_else(|| fatal!("[mod.json].resources is not an object"))
+		.entry("files")
+		.or_insert_with(|| json!([]));
+	match files.as_array_mut() {
+		Some(files) if !files.iter().any(|f| f.as_str() == Some(&pattern)) => {
+			files.push(json!(pattern));
+			write_mod_json(project, &mod_json).unwrap_or_else(|e| fatal!("{}", e));
+			info!("Added {} to the mod's resources", pattern);
+		}
+		Some(_) => {}
+		None => warn!("[mod.json].resources.files is not a list, add {} to it by hand", pattern),
+	}
+
+	sync(project, base, dir, false);
+}
+
+fn sync(project: &Path, base: String, dir: PathBuf, prune: bool) {
+	let lang_dir = project.join(&dir);
+	let base_path = lang_dir.join(format!("{}.json", base));
+	if !base_path.exists() {
+		fatal!("{} not found, create it with `geode project i18n init`", base_path.display());
+	}
+	let base_strings = read_strings(&base_path);
+
+	let mut translations = fs::read_dir(&lang_dir)
+		.expect("Unable to read language folder")
+		.map(|e| e.unwrap().path())
+		.filter(|p| p.extension().is_some_and(|e| e == "json") && *p != base_path)
+		.collect::<Vec<_>>();
+	translations.sort();
+
+	if translations.is_empty() {
+		info!("No translations yet, add some with `geode project i18n init <language>`");
+	}
+
+	let mut todo_total = 0;
+	for path in translations {
+		let language = path.file_stem().unwrap().to_string_lossy().to_string();
+		let strings = read_strings(&path);
+
+		// Follow the base language's order, with anything else at the end
+		let mut synced = Map::new();
+		let mut added = 0;
+		for (key, text) in &base_strings {
+			match strings.get(key) {
+				Some(existing) => {
+					synced.insert(key.clone(), existing.clone());
+				}
+				None => {
+					let text = text.as_str().unwrap_or_default();
+					synced.insert(key.clone(), json!(format!("{}{}", TODO_PREFIX, text)));
+					added += 1;
+				}
+			}
+		}
+		let mut orphans = 0;
+		for (key, text) in &strings {
+			if base_strings.contains_key(key) {
+				continue;
+			}
+			orphans += 1;
+			if prune {
+				info!("[{}] Removed '{}'", language, key);
+			} else {
+				warn!("[{}] '{}' isn't in {}.json anymore", language, key, base);
+				synced.insert(key.clone(), text.clone());
+			}
+		}
+
+		if synced != strings || synced.keys().ne(strings.keys()) {
+			write_strings(&path, &synced);
+		}
+
+		let todo = synced
+			.values()
+			.filter(|v| v.as_str().is_some_and(|s| s.starts_with(TODO_PREFIX)))
+			.count();
+		todo_total += todo;
+		info!(
+			"[{}] {} added, {} orphaned, {}/{} left to translate",
+			language, added, orphans, todo, base_strings.len()
+		);
+	}
+
+	if todo_total > 0 {
+		done!("Translations synced, search for \"{}\" to find what's left", TODO_PREFIX.trim());
+	} else {
+		done!("Translations synced");
+	}
+}
+
+pub fn subcommand(project: &Path, cmd: I18n) {
+	match cmd {
+		I18n::Init { languages, base, dir } => init(project, languages, base, dir),
+		I18n::Sync { base, dir, prune } => sync(project, base, dir, prune),
+	}
+}
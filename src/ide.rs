@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::sdk;
+use crate::util::logging::ask_confirm;
+use crate::util::mod_file::parse_mod_info;
+use crate::{done, fatal, info, warn};
+
+/// Editors that `geode project ide` can set up
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Ide {
+	/// Visual Studio Code with the C/C++ and CMake Tools extensions
+	Vscode,
+	Clion,
+}
+
+/// Name of the configuration for each platform in c_cpp_properties.json,
+/// matching the ones VSCode picks by default on each OS whatever the
+/// architecture
+fn vscode_platforms(win32: bool) -> [(&'static str, &'static str, &'static str); 2] {
+	[
+		("Win32", "GEODE_IS_WINDOWS", if win32 { "windows-msvc-x86" } else { "windows-msvc-x64" }),
+		("Mac", "GEODE_IS_MACOS", "macos-clang-x64"),
+	]
+}
+
+/// An existing config file, an empty one if there's none, or None if it
+/// can't be parsed
+fn read_json(path: &Path, empty: Value) -> Option<Value> {
+	let Ok(text) = fs::read_to_string(path) else {
+		return Some(empty);
+	};
+	match serde_json::from_str(&text) {
+		Ok(json) => Some(json),
+		Err(e) => {
+			// VSCode allows comments in its config files, which serde doesn't
+			warn!("Unable to parse {} ({}), leaving it alone", path.display(), e);
+			None
+		}
+	}
+}
+
+fn write_json(path: &Path, json: &Value) {
+	fs::create_dir_all(path.parent().unwrap()).expect("Unable to create IDE config directory");
+	fs::write(path, serde_json::to_string_pretty(json).unwrap())
+		.unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+	info!("Wrote {}", path.display());
+}
+
+fn write_vscode(project: &Path, build_dir: &str, mod_id: &str, win32: bool) {
+	let vscode = project.join(".vscode");
+
+	// Keep whatever else the user has in their settings
+	let settings_path = vscode.join("settings.json");
+	if let Some(mut settings) = read_json(&settings_path, json!({})) {
+		let object = settings.as_object_mut().unwrap();
+		object.insert("cmake.buildDirectory".into(), json!(format!("${{workspaceFolder}}/{}", build_dir)));
+		object.insert("cmake.configureSettings".into(), json!({
+			"CMAKE_EXPORT_COMPILE_COMMANDS": "ON",
+			"CMAKE_OSX_ARCHITECTURES": "x86_64",
+		}));
+		object.insert("cmake.platform".into(), json!(if win32 { "Win32" } else { "x64" }));
+		object.insert("C_Cpp.default.configurationProvider".into(), json!("ms-vscode.cmake-tools"));
+		write_json(&settings_path, &settings);
+	}
+
+	let properties_path = vscode.join("c_cpp_properties.json");
+	let Some(mut properties) = read_json(&properties_path, json!({ "version": 4 })) else {
+		return;
+	};
+	let Some(configurations) = properties
+		.as_object_mut()
+		.unwrap()
+		.entry("configurations")
+		.or_insert_with(|| json!([]))
+		.as_array_mut()
+	else {
+		warn!("{} has no list of configurations, leaving it alone", properties_path.display());
+		return;
+	};
+	for (name, define, mode) in vscode_platforms(win32) {
+		let configuration = json!({
+			"name": name,
+			"includePath": [
+				"${workspaceFolder}/**",
+				"${env:GEODE_SDK}/loader/include",
+				"${env:GEODE_SDK}/loader/include/Geode/cocos/include",
+				"${env:GEODE_SDK}/loader/include/Geode/cocos/extensions",
+				"${env:GEODE_SDK}/loader/include/Geode/fmod",
+				format!("${{workspaceFolder}}/{}/bindings/bindings", build_dir),
+				format!("${{workspaceFolder}}/{}/geode-deps", build_dir),
+			],
+			"defines": [define, format!("GEODE_MOD_ID=\"{}\"", mod_id)],
+			"compileCommands": format!("${{workspaceFolder}}/{}/compile_commands.json", build_dir),
+			"cppStandard": "c++20",
+			"intelliSenseMode": mode,
+		});
+		// Replace the configurations from a previous run instead of duplicating them
+		match configurations.iter_mut().find(|c| c.get("name").and_then(|n| n.as_str()) == Some(name)) {
+			Some(existing) => *existing = configuration,
+			None => configurations.push(configuration),
+		}
+	}
+	write_json(&properties_path, &properties);
+}
+
+/// Whether the config was written
+fn write_clion(project: &Path, build_dir: &str, win32: bool) -> bool {
+	let path = project.join(".idea").join("cmake.xml");
+	if path.exists() {
+		warn!("{} already exists", path.display());
+		if !ask_confirm("Do you want to replace its CMake profiles?", false) {
+			info!("Aborting");
+			return false;
+		}
+	}
+
+	// GD on macOS only runs on Intel
+	let options = if cfg!(windows) {
+		if win32 { "-A Win32" } else { "-A x64" }
+	} else {
+		"-DCMAKE_OSX_ARCHITECTURES=x86_64"
+	};
+	let profiles = [("Debug", "Debug", ""), ("Release", "RelWithDebInfo", "-release")]
+		.iter()
+		.map(|(profile, config, suffix)| format!(
+			"      <configuration PROFILE_NAME=\"{profile}\" ENABLED=\"true\" \
+			CONFIG_NAME=\"{config}\" GENERATION_DIR=\"{build_dir}{suffix}\" \
+			GENERATION_OPTIONS=\"{options} -DCMAKE_EXPORT_COMPILE_COMMANDS=ON\" />\n"
+		))
+		.collect::<String>();
+	let xml = format!(
+		"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+		<project version=\"4\">\n  \
+		<component name=\"CMakeSharedSettings\">\n    \
+		<configurations>\n{profiles}    \
+		</configurations>\n  \
+		</component>\n\
+		</project>\n"
+	);
+	fs::create_dir_all(path.parent().unwrap()).expect("Unable to create IDE config directory");
+	fs::write(&path, xml).expect("Unable to write CLion config");
+	info!("Wrote {}", path.display());
+
+	if cfg!(windows) {
+		info!(
+			"Make sure your CLion toolchain is Visual Studio with the {} architecture",
+			if win32 { "x86" } else { "amd64" }
+		);
+	}
+	true
+}
+
+pub fn write_ide_config(project: &Path, ide: Ide, build_dir: &str) {
+	let mod_info = parse_mod_info(project);
+	if std::env::var("GEODE_SDK").is_err() {
+		warn!("GEODE_SDK isn't set, so your editor won't be able to find the SDK's headers");
+	}
+	let win32 = sdk::is_win32(&sdk::build_sdk_version(&mod_info.geode));
+	match ide {
+		Ide::Vscode => write_vscode(project, build_dir, &mod_info.id, win32),
+		Ide::Clion => if !write_clion(project, build_dir, win32) {
+			return;
+		},
+	}
+	done!("Editor configuration written for {}", mod_info.id);
+}
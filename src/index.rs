@@ -1,11 +1,11 @@
 use clap::Subcommand;
 use semver::VersionReq;
 use zip::ZipArchive;
-use crate::config::Config;
+use crate::config::{Config, Download};
 use crate::file::copy_dir_recursive;
 use crate::util::logging::ask_value;
 use crate::util::mod_file::{parse_mod_info, try_parse_mod_info};
-use crate::{done, info, warn, fatal};
+use crate::{done, fatal, info, warn};
 use sha3::{Digest, Sha3_256};
 use serde::{Serialize, Deserialize};
 use serde_json::json;
@@ -31,14 +31,14 @@ pub enum Index {
 #[allow(unused)]
 #[derive(Deserialize)]
 pub struct EntryMod {
-	download: String,
-	hash: String,
+	pub download: String,
+	pub hash: String,
 }
 
 #[allow(unused)]
 #[derive(Deserialize)]
 pub struct Entry {
-	r#mod: EntryMod,
+	pub r#mod: EntryMod,
 	platforms: HashSet<String>,
 	tags: Vec<String>,
 	featured: bool,
@@ -53,28 +53,53 @@ pub fn update_index(config: &Config) {
 	let current_sha = fs::read_to_string(&checksum).unwrap_or(String::new());
 
 	let client = reqwest::blocking::Client::new();
+	let sources = config.download_sources(Download::Index);
 
 	let response = client.get("https://api.github.com/repos/geode-sdk/mods/commits/main")
 		.header("Accept", "application/vnd.github.sha")
 		.header("If-None-Match", format!("\"{}\"", current_sha))
 		.header("User-Agent", "GeodeCli")
-		.send()
-		.expect("Unable to fetch index version");
-
-	if response.status() == 304 {
-		done!("Index is up-to-date");
-		return;
-	}
-	assert!(response.status() == 200, "Version check received status code {}", response.status());
-	let latest_sha = response.text().expect("Unable to decode index version");
-
-	let mut zip_data = io::Cursor::new(Vec::new());
+		.send();
+
+	// Mirrors can't say which commit they have, so without GitHub the index
+	// is downloaded again
+	let latest_sha = match response {
+		Ok(response) if response.status() == 304 => {
+			done!("Index is up-to-date");
+			return;
+		}
+		Ok(response) if response.status() == 200 => {
+			Some(response.text().expect("Unable to decode index version"))
+		}
+		Ok(response) if sources.len() == 1 => {
+			fatal!("Version check received status code {}", response.status())
+		}
+		Err(e) if sources.len() == 1 => fatal!("Unable to fetch index version: {}", e),
+		_ => {
+			warn!("Unable to check the index version on GitHub, trying the mirrors");
+			None
+		}
+	};
 
-	client.get("https://github.com/geode-sdk/mods/zipball/main")
-		.send().expect("Unable to download index")
-		.copy_to(&mut zip_data).expect("Unable to write to index");
+	let (source, mut zip_archive) = sources.iter().find_map(|url| {
+		let mut zip_data = io::Cursor::new(Vec::new());
+		let downloaded = client.get(url)
+			.send()
+			.and_then(|res| res.error_for_status())
+			.and_then(|mut res| res.copy_to(&mut zip_data));
+		if let Err(e) = downloaded {
+			warn!("Unable to download index from {}: {}", url, e);
+			return None;
+		}
+		ZipArchive::new(zip_data)
+			.map_err(|e| warn!("Index from {} isn't a valid ZIP: {}", url, e))
+			.ok()
+			.map(|zip| (url, zip))
+	}).unwrap_or_else(|| fatal!("Unable to download index"));
 
-	let mut zip_archive = ZipArchive::new(zip_data).expect("Unable to decode index zip");
+	// A mirror may not have caught up with GitHub yet, so its copy can't be
+	// recorded as that commit
+	let latest_sha = latest_sha.filter(|_| Some(source) == sources.last());
 
 
 	let before_items = if target_index_dir.join("mods").exists() {
@@ -131,7 +156,11 @@ pub fn update_index(config: &Config) {
 		}
 	}
 
-	fs::write(checksum, latest_sha).expect("Unable to save version");
+	match latest_sha {
+		Some(sha) => fs::write(checksum, sha).expect("Unable to save version"),
+		// Don't claim to have a version of the index that may not be it
+		None => fs::remove_file(checksum).unwrap_or(()),
+	}
 	done!("Successfully updated index")
 }
 
@@ -153,38 +182,15 @@ pub fn get_entry(config: &Config, id: &String, version: &VersionReq) -> Option<E
 	None
 }
 
-pub fn install_mod(config: &Config, id: &String, version: &VersionReq) -> PathBuf {
-	let entry = get_entry(config, &id, &version)
-		.expect(&format!("Unable to find '{id}' version '{version}'"));
-	
-	let plat = if cfg!(windows) {
-		"windows"
-	} else if cfg!(macos) {
-		"macos"
-	} else {
-		panic!("This platform doesn't support installing mods");
-	};
-
-	if !entry.platforms.contains(plat) {
-		fatal!("Mod '{id}' is not available on '{plat}'");
-	}
-	
-	info!("Installing mod '{}' version '{}'", id, version);
-
-	let mut pkg_data = io::Cursor::new(Vec::new());
-
-	reqwest::blocking::get(entry.r#mod.download)
-		.expect("Unable to download mod")
-		.copy_to(&mut pkg_data)
-		.expect("Unable to download mod");
-	
-	let dest = config.get_current_profile().mods_dir().join(format!("{id}.geode"));
-	let mut file = std::fs::File::create(&dest)
-		.expect("Unable to create destination file for mod");
-	
-	std::io::copy(&mut pkg_data, &mut file).expect("Unable to install mod");
-
-	dest
+/// Name of the mod using an ID in the cached index, without updating it
+pub fn indexed_mod_name(config: &Config, id: &str) -> Option<String> {
+	config.get_profile(&config.current_profile)?;
+	index_mods_dir(config)
+		.read_dir()
+		.ok()?
+		.filter_map(|dir| try_parse_mod_info(&dir.ok()?.path()).ok())
+		.find(|mod_info| mod_info.id == id)
+		.map(|mod_info| mod_info.name)
 }
 
 fn create_index_json(path: &Path) {
@@ -1,139 +1,983 @@
-use crate::config::{geode_root};
-use crate::util::logging::ask_value;
+use crate::config::{geode_root, proxy_options, Config};
+use crate::util::logging::{ask_confirm, ask_value};
+use crate::util::mod_file::try_parse_mod_info;
+use clap::Subcommand;
+use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
-use git2::{Repository, ResetType, IndexAddOption, Signature};
-use crate::package::mod_json_from_archive;
-use crate::{info, done, fatal, warn};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{
+	BranchType, Cred, CredentialType, Diff, FetchOptions, IndexAddOption, Oid, PushOptions,
+	RemoteCallbacks, Repository, ResetType, Signature, Status, StatusOptions,
+};
+use crate::package::{
+	binary_platform, declared_platforms, diff_packages, ensure_valid_mod_json, mod_json_from_archive,
+};
+use crate::{info, done, fail, fatal, warn};
 use colored::Colorize;
 
-fn reset_and_commit(repo: &Repository, msg: &str) {
+#[derive(Subcommand, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum Indexer {
+	/// Initialize a local Indexer from your fork of geode-sdk/indexer
+	Init {
+		/// URL of your fork. If not provided, you will be asked for it
+		#[clap(long)]
+		url: Option<String>,
+
+		/// Delete an existing local Indexer and clone it again
+		#[clap(long)]
+		force: bool,
+
+		/// Don't check that the URL is a fork of geode-sdk/indexer
+		#[clap(long)]
+		no_verify: bool,
+
+		/// Only clone this many commits of history. Defaults to 1 if the
+		/// `indexer-shallow` option is set
+		#[clap(long)]
+		depth: Option<i32>,
+	},
+
+	/// List all mods in your local Indexer
+	List {
+		/// Output the list as JSON
+		#[clap(long)]
+		json: bool,
+
+		/// Don't fetch geode-sdk/indexer, use what was last fetched to tell
+		/// which mods are published
+		#[clap(long)]
+		offline: bool,
+	},
+
+	/// Remove mods from your local Indexer
+	Remove {
+		/// IDs of the mods to remove, either `id` to remove all major versions
+		/// or `id@major` as it appears in `geode indexer list`. Glob patterns
+		/// like `my.dev.*` are also accepted
+		#[clap(required = true, num_args(1..))]
+		ids: Vec<String>,
+
+		/// Commit on top of the existing history instead of squashing it
+		#[clap(long)]
+		no_squash: bool,
+	},
+
+	/// Remove entries that have been superseded by a newer major version of
+	/// the same mod
+	Prune {
+		/// Commit on top of the existing history instead of squashing it
+		#[clap(long)]
+		no_squash: bool,
+	},
+
+	/// Add or update .geode packages in your local Indexer
+	Export {
+		/// Paths to the .geode packages, glob patterns are also accepted
+		#[clap(required = true, num_args(1..))]
+		packages: Vec<PathBuf>,
+
+		/// Only print what would be done, without touching the Indexer
+		#[clap(long)]
+		dry_run: bool,
+
+		/// Overwrite the entry even if it already has the same version
+		#[clap(long)]
+		force: bool,
+
+		/// Fail instead of warning when a package is missing binaries
+		#[clap(long)]
+		strict: bool,
+
+		/// Also put each mod on its own branch named after the mod id, based
+		/// on geode-sdk/indexer, so it can get its own Pull Request
+		#[clap(long)]
+		branch: bool,
+
+		/// Commit on top of the existing history instead of squashing it
+		#[clap(long)]
+		no_squash: bool,
+	},
+
+	/// Compare a .geode package against the mod currently in your local Indexer
+	Diff {
+		/// ID of the mod to compare against, either `id` or `id@major`
+		id: String,
+
+		/// Path to the new .geode package
+		package: PathBuf,
+	},
+
+	/// Force-push your local Indexer to your fork
+	Push,
+
+	/// Show changes compared to your fork and upstream geode-sdk/indexer
+	Status,
+
+	/// Check a mod in your local Indexer against the index rules
+	Validate {
+		/// ID of the mod to check, either `id` or `id@major`
+		id: String,
+	},
+
+	/// Update your local Indexer to the latest geode-sdk/indexer, keeping your
+	/// own mods on top
+	Sync {
+		/// Commit your mods on top of geode-sdk/indexer's history instead of
+		/// squashing it
+		#[clap(long)]
+		no_squash: bool,
+	},
+
+	/// Discard all local changes and reset your local Indexer to your fork
+	Reset,
+
+	/// Publish .geode packages by uploading them to the Geode index
+	Publish {
+		/// Paths to the .geode packages, glob patterns are also accepted
+		#[clap(required = true, num_args(1..))]
+		packages: Vec<PathBuf>,
+
+		/// Publish through your Indexer fork instead, exporting and pushing the
+		/// packages
+		#[clap(long)]
+		git: bool,
+	},
+
+	/// Open your fork on Github in the browser
+	Open {
+		/// Open the page for opening a Pull Request to geode-sdk/indexer instead
+		#[clap(long)]
+		pr: bool,
+	},
+
+	/// Open or update a pull request from your fork to geode-sdk/indexer
+	Pr {
+		/// ID of the mod the pull request is for. If not provided, the only
+		/// mod in your Indexer is used
+		id: Option<String>,
+	},
+}
+
+const UPSTREAM_URL: &str = "https://github.com/geode-sdk/indexer";
+
+const INDEX_API_URL: &str = "https://api.geode-sdk.org";
+
+/// Points at the upstream commit the Indexer was last synced with, since
+/// squashing history means upstream never ends up as an ancestor of HEAD
+const SYNCED_REF: &str = "refs/geode/synced-upstream";
+
+/// Files extracted from the package into the entry on export
+const ENTRY_FILES: [&str; 3] = ["about.md", "changelog.md", "logo.png"];
+
+#[derive(Deserialize)]
+struct GithubPullRequest {
+	number: u64,
+	html_url: String,
+}
+
+#[derive(Deserialize)]
+struct GithubRepository {
+	full_name: String,
+	fork: bool,
+	parent: Option<Box<GithubRepository>>,
+	source: Option<Box<GithubRepository>>,
+}
+
+/// Credentials for talking to the fork. SSH remotes go through ssh-agent and
+/// then the default key files in ~/.ssh, HTTPS remotes use the Github token
+/// if one is set and the git credential helper otherwise
+fn remote_callbacks<'a>(config: &Config) -> RemoteCallbacks<'a> {
+	let mut callbacks = RemoteCallbacks::new();
+	let token = github_token(config);
+
+	// popped from the back, so the preferred key goes last
+	let mut ssh_keys = dirs::home_dir()
+		.map(|home| {
+			["id_rsa", "id_ecdsa", "id_ed25519"]
+				.iter()
+				.map(|key| home.join(".ssh").join(key))
+				.filter(|key| key.exists())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	let mut tried_agent = false;
+
+	// libgit2 keeps calling this for as long as we hand it credentials, so
+	// give up after a few tries instead of looping forever
+	let mut attempts = 0;
+	callbacks.credentials(move |url, username, allowed| {
+		let username = username.unwrap_or("git");
+
+		if allowed.contains(CredentialType::USERNAME) {
+			Cred::username(username)
+		}
+		else if allowed.contains(CredentialType::SSH_KEY) {
+			if !tried_agent {
+				tried_agent = true;
+				return Cred::ssh_key_from_agent(username);
+			}
+			match ssh_keys.pop() {
+				Some(key) => Cred::ssh_key(username, None, &key, None),
+				None => Err(git2::Error::from_str(
+					"No usable SSH key found - add your key to ssh-agent"
+				)),
+			}
+		}
+		else if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+			attempts += 1;
+			if attempts > 3 {
+				return Err(git2::Error::from_str("Authentication failed"));
+			}
+			match &token {
+				Some(token) => Cred::userpass_plaintext("x-access-token", token),
+				None => Cred::credential_helper(&git2::Config::open_default()?, url, Some(username)),
+			}
+		}
+		else {
+			Cred::default()
+		}
+	});
+	callbacks
+}
+
+/// Git config key set on the branches `export --branch` makes, so only those
+/// get pushed along with the current branch
+const MOD_BRANCH_KEY: &str = "geodeExport";
+
+fn push_to_fork(config: &Config, repo: &Repository) -> Result<(), git2::Error> {
+	// per-mod branches from `export --branch` go along with the current one,
+	// other local branches are left alone
+	let git_config = repo.config()?;
+	let mut refspecs = Vec::new();
+	for branch in repo.branches(Some(BranchType::Local))? {
+		let (branch, _) = branch?;
+		let (Some(name), Ok(Some(short))) = (branch.get().name(), branch.name()) else {
+			return Err(git2::Error::from_str("Branch is not a valid reference"));
+		};
+		let exported = git_config.get_bool(&format!("branch.{short}.{MOD_BRANCH_KEY}")).unwrap_or(false);
+		if branch.is_head() || exported {
+			// history gets rewritten unless squashing is turned off, so this has to be a force-push
+			refspecs.push(format!("+{0}:{0}", name));
+		}
+	}
+
+	let mut callbacks = remote_callbacks(config);
+	callbacks.push_update_reference(|name, status| match status {
+		Some(msg) => Err(git2::Error::from_str(&format!("Remote rejected {name}: {msg}"))),
+		None => Ok(()),
+	});
+
+	let mut opts = PushOptions::new();
+	opts.remote_callbacks(callbacks);
+	opts.proxy_options(proxy_options());
+
+	repo.find_remote("origin")?.push(&refspecs, Some(&mut opts))
+}
+
+/// The identity to commit as: the `git-name` / `git-email` config values, then
+/// the repository's (or global) git config, and GeodeBot if neither is set
+fn commit_signature(config: &Config, repo: &Repository) -> Signature<'static> {
+	let git_config = repo.config().ok();
+	let from_git = |key: &str| git_config.as_ref().and_then(|c| c.get_string(key).ok());
+
+	let name = config.git_name.clone().or_else(|| from_git("user.name"));
+	let email = config.git_email.clone().or_else(|| from_git("user.email"));
+
+	match (name, email) {
+		(Some(name), Some(email)) => Signature::now(&name, &email),
+		_ => Signature::now("GeodeBot", "hjfodgames@gmail.com"),
+	}
+	.expect("Unable to create commit signature")
+}
+
+/// Commit all changes in the Indexer. Unless squashing is turned off with
+/// `--no-squash` or the `indexer-keep-history` option, history is squashed
+/// down to the root commit and this one
+fn reset_and_commit(config: &Config, repo: &Repository, msg: &str, no_squash: bool) {
 	let head = repo.head().expect("Broken repository, can't get HEAD");
 	if !head.is_branch() {
 		fatal!("Broken repository, detached HEAD");
 	}
 
 	let mut commit = head.peel_to_commit().unwrap();
-	while commit.parent_count() > 0 {
-		commit = commit.parent(0).unwrap();
+	if !no_squash && !config.indexer_keep_history {
+		while commit.parent_count() > 0 {
+			commit = commit.parent(0).unwrap();
+		}
+
+		repo.reset(commit.as_object(), ResetType::Soft, None).expect("Unable to refresh repository");
 	}
 
-	repo.reset(commit.as_object(), ResetType::Soft, None).expect("Unable to refresh repository");
-	
 	let mut index = repo.index().expect("cannot get the Index file");
 	index.add_all(["."].iter(), IndexAddOption::DEFAULT, None).expect("Unable to add changes");
 	index.write().expect("Unable to write changes");
 
-	let sig = Signature::now("GeodeBot", "hjfodgames@gmail.com").unwrap();
+	let sig = commit_signature(config, repo);
 
 	let tree = repo.find_tree(index.write_tree().expect("Unable to get write tree")).unwrap();
 	repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &[&commit]).expect("Unable to commit");
 }
 
-pub fn indexer_path() -> PathBuf {
-	geode_root().join("indexer")
+/// Commit an entry from HEAD onto the branch named after its mod, creating
+/// the branch from geode-sdk/indexer if it doesn't exist yet
+fn update_mod_branch(config: &Config, repo: &Repository, mod_id: &str, entry: &str) {
+	let head_tree = repo.head().and_then(|h| h.peel_to_tree()).expect("Unable to get HEAD tree");
+	let entry_tree = head_tree
+		.get_path(Path::new(entry))
+		.expect("Exported entry is missing from HEAD");
+
+	let parent = match repo.find_branch(mod_id, BranchType::Local) {
+		Ok(branch) => branch.get().peel_to_commit().unwrap(),
+		Err(_) => match repo.find_reference("refs/remotes/upstream/main") {
+			Ok(upstream) => upstream.peel_to_commit().unwrap(),
+			Err(_) => {
+				warn!(
+					"geode-sdk/indexer has not been fetched yet, basing branch {} on \
+					your Indexer instead - run `geode indexer sync` first to fix this",
+					mod_id
+				);
+				repo.head().and_then(|h| h.peel_to_commit()).unwrap()
+			},
+		},
+	};
+
+	let mut builder = repo.treebuilder(Some(&parent.tree().unwrap()))
+		.expect("Unable to build branch tree");
+	builder.insert(entry, entry_tree.id(), entry_tree.filemode())
+		.expect("Unable to add entry to branch");
+	let tree = repo.find_tree(builder.write().expect("Unable to write branch tree")).unwrap();
+	if tree.id() == parent.tree_id() {
+		info!("Branch {} is already up to date", mod_id);
+		return;
+	}
+
+	let sig = commit_signature(config, repo);
+	let commit = repo.commit(None, &sig, &sig, &format!("Add/Update {}", mod_id), &tree, &[&parent])
+		.expect("Unable to commit to branch");
+	repo.branch(mod_id, &repo.find_commit(commit).unwrap(), true)
+		.expect("Unable to update branch");
+	repo.config()
+		.and_then(|mut c| c.set_bool(&format!("branch.{mod_id}.{MOD_BRANCH_KEY}"), true))
+		.expect("Unable to mark branch as exported");
+	info!("Updated branch {}", mod_id);
+}
+
+/// Fetch a branch from a remote into `refs/remotes/<remote>/<branch>`, adding
+/// the remote with the given URL if it doesn't exist yet
+fn fetch_branch(
+	config: &Config,
+	repo: &Repository,
+	remote: &str,
+	url: &str,
+	branch: &str,
+) -> Result<Oid, git2::Error> {
+	let mut remote_obj = repo.find_remote(remote).or_else(|_| repo.remote(remote, url))?;
+
+	let mut opts = FetchOptions::new();
+	opts.remote_callbacks(remote_callbacks(config));
+	opts.proxy_options(proxy_options());
+	// only limit history when asked to, a depth of 1 on a clone that's
+	// shallow for another reason would break merge bases against upstream
+	if config.indexer_shallow {
+		opts.depth(1);
+	}
+
+	let tracking = format!("refs/remotes/{remote}/{branch}");
+	remote_obj.fetch(&[&format!("+refs/heads/{branch}:{tracking}")], Some(&mut opts), None)?;
+	repo.refname_to_id(&tracking)
 }
 
-pub fn is_initialized() -> bool {
-	indexer_path().exists()
+/// Group the files touched by a diff by the entry (top-level directory) they
+/// belong to
+fn changed_entries(diff: &Diff) -> BTreeMap<String, char> {
+	let mut res = BTreeMap::new();
+	for delta in diff.deltas() {
+		let Some(path) = delta.new_file().path().or_else(|| delta.old_file().path()) else {
+			continue;
+		};
+		let Some(entry) = path.components().next() else { continue; };
+		let mark = match delta.status() {
+			git2::Delta::Added => '+',
+			git2::Delta::Deleted => '-',
+			_ => '~',
+		};
+		res.insert(entry.as_os_str().to_string_lossy().to_string(), mark);
+	}
+	res
+}
+
+/// The upstream commit the Indexer is based on: the one it was last synced
+/// with, or before the first sync where it branched off upstream. None if
+/// its history has nothing in common with upstream
+fn synced_base(repo: &Repository, head: Oid, upstream: Oid) -> Option<Oid> {
+	repo.refname_to_id(SYNCED_REF)
+		.or_else(|_| repo.merge_base(head, upstream))
+		.ok()
+}
+
+fn print_entries(entries: &BTreeMap<String, char>) {
+	for (entry, mark) in entries {
+		let mark = match mark {
+			'+' => "+".green(),
+			'-' => "-".red(),
+			_ => "~".yellow(),
+		};
+		println!("    {} {}", mark, entry);
+	}
 }
 
-pub fn initialize() {
-	if is_initialized() {
-		done!("Indexer is already initialized");
+/// Split a Github remote URL (HTTPS or SSH) into its owner and repository name
+fn github_repo_from_url(url: &str) -> Option<(String, String)> {
+	let path = url
+		.strip_prefix("https://github.com/")
+		.or_else(|| url.strip_prefix("http://github.com/"))
+		.or_else(|| url.strip_prefix("git@github.com:"))
+		.or_else(|| url.strip_prefix("ssh://git@github.com/"))?;
+	let path = path.trim_end_matches('/');
+	let path = path.strip_suffix(".git").unwrap_or(path);
+
+	let (owner, repo) = path.split_once('/')?;
+	Some((owner.to_string(), repo.to_string()))
+}
+
+/// GEODE_GITHUB_TOKEN takes precedence over the token stored in config
+pub fn github_token(config: &Config) -> Option<String> {
+	std::env::var("GEODE_GITHUB_TOKEN").ok().or_else(|| config.github_token.clone())
+}
+
+/// GEODE_INDEX_TOKEN takes precedence over the token stored in config
+fn index_token(config: &Config) -> Option<String> {
+	std::env::var("GEODE_INDEX_TOKEN").ok().or_else(|| config.index_token.clone())
+}
+
+/// Base URL of the index server, the `index-url` config value or the official
+/// Geode index
+pub fn index_url(config: &Config) -> String {
+	config.index_url
+		.as_deref()
+		.unwrap_or(INDEX_API_URL)
+		.trim_end_matches('/')
+		.to_string()
+}
+
+/// Where the local Indexer lives: `--path`, then the `indexer-path` config
+/// value, then the Geode directory
+pub fn indexer_path(config: &Config) -> PathBuf {
+	config.indexer_path_override
+		.clone()
+		.or_else(|| config.indexer_path.clone())
+		.unwrap_or_else(|| geode_root().join("indexer"))
+}
+
+pub fn is_initialized(config: &Config) -> bool {
+	indexer_path(config).exists()
+}
+
+/// Client for the Github API, authenticated if a token is available
+fn github_client(config: &Config) -> reqwest::blocking::Client {
+	let mut headers = HeaderMap::new();
+	headers.insert(USER_AGENT, HeaderValue::from_static("GeodeCli"));
+	headers.insert(ACCEPT, HeaderValue::from_static("application/vnd.github+json"));
+	if let Some(token) = github_token(config) {
+		headers.insert(
+			AUTHORIZATION,
+			HeaderValue::from_str(&format!("Bearer {token}")).expect("Invalid Github token"),
+		);
+	}
+
+	reqwest::blocking::Client::builder()
+		.default_headers(headers)
+		.build()
+		.unwrap()
+}
+
+/// Make sure a URL points at a fork of geode-sdk/indexer before cloning it.
+/// If the Github API can't tell us, only a warning is shown
+fn verify_fork(config: &Config, url: &str) {
+	let Some((owner, name)) = github_repo_from_url(url) else {
+		warn!("{} is not a Github repository, unable to check that it's a fork of geode-sdk/indexer", url);
 		return;
+	};
+
+	let repo = github_client(config)
+		.get(format!("https://api.github.com/repos/{owner}/{name}"))
+		.send()
+		.and_then(|r| r.error_for_status())
+		.and_then(|r| r.json::<GithubRepository>());
+	let repo = match repo {
+		Ok(repo) => repo,
+		Err(e) => {
+			warn!("Unable to check that {}/{} is a fork of geode-sdk/indexer: {}", owner, name, e);
+			return;
+		},
+	};
+
+	let is_upstream = |r: &GithubRepository| r.full_name.eq_ignore_ascii_case("geode-sdk/indexer");
+	if is_upstream(&repo) {
+		fatal!(
+			"{} is the official Indexer - fork it at \
+			https://github.com/geode-sdk/indexer/fork and use the URL of your fork",
+			url
+		);
+	}
+	let is_fork = repo.fork && [&repo.parent, &repo.source].into_iter().flatten().any(|r| is_upstream(r));
+	if !is_fork {
+		fatal!(
+			"{} is not a fork of geode-sdk/indexer - use --no-verify if you are sure it is the right repository",
+			repo.full_name
+		);
 	}
+}
 
-	info!(
-		"Before publishing mods on the Geode index, we need to make you a local \
-		Indexer, which handles everything related to publishing mods."
-	);
-	info!(
-		"The mod binaries will be hosted on your Indexer repository, which will \
-		automatically request them to be added to the official Mods Index."
-	);
-	info!(
-		"To get started, log in to Github using your account, and go to \
-		https://github.com/geode-sdk/indexer/fork to make a fork of the Indexer."
-	);
+pub fn initialize(
+	config: &Config,
+	url: Option<String>,
+	force: bool,
+	no_verify: bool,
+	depth: Option<i32>,
+) {
+	if is_initialized(config) {
+		if !force {
+			done!("Indexer is already initialized");
+			info!("Use --force if you want to clone it again");
+			return;
+		}
+		info!("Removing existing Indexer");
+		fs::remove_dir_all(indexer_path(config)).expect("Unable to remove existing Indexer");
+	}
+
+	let fork_url = url.unwrap_or_else(|| {
+		info!(
+			"Before publishing mods on the Geode index, we need to make you a local \
+			Indexer, which handles everything related to publishing mods."
+		);
+		info!(
+			"The mod binaries will be hosted on your Indexer repository, which will \
+			automatically request them to be added to the official Mods Index."
+		);
+		info!(
+			"To get started, log in to Github using your account, and go to \
+			https://github.com/geode-sdk/indexer/fork to make a fork of the Indexer."
+		);
 
-	let fork_url = ask_value("Enter the URL of your fork", None, true);
-	Repository::clone(&fork_url, indexer_path()).expect("Unable to clone your repository.");
+		ask_value("Enter the URL of your fork", None, true)
+	});
+
+	if !no_verify {
+		verify_fork(config, &fork_url);
+	}
+
+	let mut fetch = FetchOptions::new();
+	fetch.remote_callbacks(remote_callbacks(config));
+	fetch.proxy_options(proxy_options());
+	if let Some(depth) = depth.or(config.indexer_shallow.then_some(1)) {
+		fetch.depth(depth);
+	}
+
+	RepoBuilder::new()
+		.fetch_options(fetch)
+		.clone(&fork_url, &indexer_path(config))
+		.unwrap_or_else(|e| fatal!("Unable to clone your repository: {}", e.message()));
 
 	done!("Successfully initialized Indexer");
 }
 
-pub fn list_mods() {
-	if !is_initialized() {
+/// All entry directories (the ones with a mod.geode in them) in the Indexer
+fn entries(config: &Config) -> Vec<PathBuf> {
+	let mut res = fs::read_dir(indexer_path(config))
+		.expect("Unable to read local Indexer")
+		.map(|dir| dir.unwrap().path())
+		.filter(|path| path.is_dir() && path.join("mod.geode").exists())
+		.collect::<Vec<_>>();
+	res.sort();
+	res
+}
+
+/// Find the entry for a mod, either by its full `id@major` name or by just
+/// the id, in which case the latest major version is picked
+fn find_entry(config: &Config, id: &str) -> Option<PathBuf> {
+	entries(config)
+		.into_iter()
+		.filter_map(|path| {
+			let name = path.file_name()?.to_str()?.to_string();
+			if name == id {
+				return Some((u64::MAX, path));
+			}
+			let (entry_id, major) = name.split_once('@')?;
+			(entry_id == id).then(|| (major.parse().unwrap_or(0), path))
+		})
+		.max_by_key(|(major, _)| *major)
+		.map(|(_, path)| path)
+}
+
+/// Whether an entry is published, by comparing it to the same entry in
+/// geode-sdk/indexer
+fn publish_status(head: &git2::Tree, upstream: &git2::Tree, entry: &str) -> &'static str {
+	let local = head.get_path(Path::new(entry)).ok().map(|e| e.id());
+	match upstream.get_path(Path::new(entry)) {
+		Err(_) => "not yet submitted",
+		Ok(up) if Some(up.id()) == local => "published",
+		Ok(_) => "pending update",
+	}
+}
+
+pub fn list_mods(config: &Config, json: bool, offline: bool) {
+	if !is_initialized(config) {
 		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
 	}
 
-	println!("Published mods:");
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
 
-	for dir in fs::read_dir(indexer_path()).unwrap() {
-		let path = dir.unwrap().path();
+	if !offline {
+		if let Err(e) = fetch_branch(config, &repo, "upstream", UPSTREAM_URL, "main") {
+			if !json {
+				warn!("Unable to fetch geode-sdk/indexer: {}", e.message());
+			}
+		}
+	}
+	let upstream_tree = repo
+		.find_reference("refs/remotes/upstream/main")
+		.and_then(|r| r.peel_to_tree())
+		.ok();
+	let head_tree = repo.head().and_then(|h| h.peel_to_tree()).ok();
+	let trees = head_tree.as_ref().zip(upstream_tree.as_ref());
+
+	let mut list = Vec::new();
+
+	if !json {
+		println!("Published mods:");
+	}
+
+	for path in entries(config) {
+		let entry = path.file_name().unwrap().to_str().unwrap().to_string();
+		let mod_info = try_parse_mod_info(&path.join("mod.geode"));
+		let status = trees.map(|(head, upstream)| publish_status(head, upstream, &entry));
+
+		if json {
+			let mut value = match mod_info {
+				Ok(info) => json!({
+					"entry": entry,
+					"id": info.id,
+					"version": info.version.to_string(),
+					"name": info.name,
+					"developer": info.developer,
+				}),
+				Err(e) => json!({ "entry": entry, "error": e }),
+			};
+			if let Some(status) = status {
+				value["status"] = json!(status);
+			}
+			list.push(value);
+			continue;
+		}
 
-		if path.is_dir() && path.join("mod.geode").exists() {
-			println!("    - {}", path.file_name().unwrap().to_str().unwrap().bright_green());
+		let status = match status {
+			Some("published") => format!(" [{}]", "published".green()),
+			Some("pending update") => format!(" [{}]", "pending update".yellow()),
+			Some(other) => format!(" [{}]", other.red()),
+			None => String::new(),
+		};
+		match mod_info {
+			Ok(info) => println!(
+				"    - {} {} ({} by {}){}",
+				entry.bright_green(),
+				format!("v{}", info.version).bright_yellow(),
+				info.name,
+				info.developer,
+				status
+			),
+			Err(e) => println!("    - {} {}{}", entry.bright_green(), format!("({})", e).red(), status),
 		}
 	}
+
+	if json {
+		println!("{}", serde_json::to_string_pretty(&list).unwrap());
+	}
+}
+
+/// Find all entries matching a pattern. The pattern may be a glob, and is
+/// matched against both the full `id@major` name and just the mod id
+fn match_entries(config: &Config, pattern: &str) -> Vec<PathBuf> {
+	let glob = glob::Pattern::new(pattern)
+		.unwrap_or_else(|_| fatal!("Invalid pattern {}", pattern));
+	entries(config)
+		.into_iter()
+		.filter(|path| {
+			let name = path.file_name().unwrap().to_string_lossy();
+			let id = name.split_once('@').map(|(id, _)| id).unwrap_or(&name);
+			glob.matches(&name) || glob.matches(id)
+		})
+		.collect()
 }
 
-pub fn remove_mod(id: String) {
-	if !is_initialized() {
+pub fn remove_mods(config: &Config, ids: Vec<String>, no_squash: bool) {
+	if !is_initialized(config) {
 		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
 	}
-	let indexer_path = indexer_path();
+	let indexer_path = indexer_path(config);
 
-	let mod_path = indexer_path.join(&id);
-	if !mod_path.exists() {
-		fatal!("Cannot remove mod {}: does not exist", id);
+	let mut to_remove = Vec::new();
+	for id in &ids {
+		let matches = match_entries(config, id);
+		if matches.is_empty() {
+			fatal!("Cannot remove mod {}: does not exist", id);
+		}
+		for path in matches {
+			if !to_remove.contains(&path) {
+				to_remove.push(path);
+			}
+		}
 	}
 
-	fs::remove_dir_all(mod_path).expect("Unable to remove mod");
+	let names = to_remove
+		.iter()
+		.map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+		.collect::<Vec<_>>()
+		.join(", ");
+
+	for path in &to_remove {
+		fs::remove_dir_all(path).expect("Unable to remove mod");
+	}
 
 	let repo = Repository::open(&indexer_path).expect("Unable to open repository");
-	reset_and_commit(&repo, &format!("Remove {}", &id));
+	reset_and_commit(config, &repo, &format!("Remove {}", &names), no_squash);
 
-	done!("Succesfully removed {}\n", id);
-	info!("You will need to force-push to sync your changes.");
-	info!("Run `git -C {} push -f` to sync your changes", indexer_path.to_str().unwrap());
+	done!("Succesfully removed {}\n", names);
+	info!("Run `geode indexer push` to sync your changes");
 }
 
-pub fn add_mod(package: PathBuf) {
-	if !is_initialized() {
+pub fn prune(config: &Config, no_squash: bool) {
+	if !is_initialized(config) {
 		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
 	}
-	let indexer_path = indexer_path();
 
+	let mut latest: BTreeMap<String, u64> = BTreeMap::new();
+	let mut majors = Vec::new();
+	for path in entries(config) {
+		let name = path.file_name().unwrap().to_string_lossy().to_string();
+		let Some((id, major)) = name.split_once('@') else { continue; };
+		let Ok(major) = major.parse::<u64>() else { continue; };
+
+		let newest = latest.entry(id.to_string()).or_insert(major);
+		*newest = (*newest).max(major);
+		majors.push((id.to_string(), major, path));
+	}
+
+	let stale = majors
+		.into_iter()
+		.filter(|(id, major, _)| latest[id] > *major)
+		.collect::<Vec<_>>();
+	if stale.is_empty() {
+		done!("Nothing to prune");
+		return;
+	}
+
+	info!("These entries have a newer major version:");
+	for (id, major, _) in &stale {
+		println!("    - {}@{} (latest is {}@{})", id, major, id, latest[id]);
+	}
+	if !ask_confirm("Remove them?", false) {
+		fatal!("Aborting");
+	}
+
+	let ids = stale
+		.iter()
+		.map(|(id, major, _)| format!("{}@{}", id, major))
+		.collect();
+	remove_mods(config, ids, no_squash);
+}
+
+/// Set values in an entry's entry.json, keeping anything else that's already
+/// in there (like values added by the index maintainers)
+fn update_entry_json(mod_path: &Path, values: serde_json::Value) {
+	let path = mod_path.join("entry.json");
+
+	let mut entry_json = fs::read_to_string(&path)
+		.ok()
+		.and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+		.filter(|data| data.is_object())
+		.unwrap_or_else(|| json!({}));
+
+	if let Some(values) = values.as_object() {
+		for (key, value) in values {
+			entry_json[key] = value.clone();
+		}
+	}
+
+	// Format neatly
+	let buf = Vec::new();
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(buf, formatter);
+	entry_json.serialize(&mut ser).unwrap();
+
+	// Write formatted json
+	fs::write(path, String::from_utf8(ser.into_inner()).unwrap())
+		.expect("Unable to write entry.json");
+}
+
+/// Expand any glob patterns in a list of package paths
+fn expand_packages(packages: Vec<PathBuf>) -> Vec<PathBuf> {
+	packages
+		.into_iter()
+		.flat_map(|package| {
+			let pattern = package.to_string_lossy().to_string();
+			if !pattern.contains(['*', '?', '[']) {
+				return vec![package];
+			}
+			let matches = glob::glob(&pattern)
+				.unwrap_or_else(|_| fatal!("Invalid glob pattern {}", pattern))
+				.map(|g| g.unwrap())
+				.collect::<Vec<_>>();
+			if matches.is_empty() {
+				fatal!("No packages match {}", pattern);
+			}
+			matches
+		})
+		.collect()
+}
+
+/// Copy a package into its `id@major` entry, returning the mod id and the
+/// name of the entry
+fn export_package(
+	indexer_path: &Path,
+	package: &Path,
+	dry_run: bool,
+	force: bool,
+	strict: bool,
+) -> (String, String) {
 	if !package.exists() {
 		fatal!("Package path {} does not exist!", package.display());
 	}
 
-	let mut archive = zip::ZipArchive::new(fs::File::open(&package).unwrap()).expect("Unable to read package");
+	let mut archive = zip::ZipArchive::new(fs::File::open(package).unwrap()).expect("Unable to read package");
 	
 	let mod_json = mod_json_from_archive(&mut archive);
+	ensure_valid_mod_json(&mod_json);
 
-	let major_version = mod_json
+	let version = mod_json
 		.get("version")
 		.expect("[mod.json]: Missing key 'version'")
 		.as_str()
-		.expect("[mod.json].version: Expected string")
-		.split(".")
+		.expect("[mod.json].version: Expected string");
+
+	let major_version = version
+		.split('.')
 		.next()
 		.unwrap()
 		.chars()
 		.filter(|x| *x != 'v')
 		.collect::<String>();
 
-	let mod_id = mod_json_from_archive(&mut archive)
+	let mod_id = mod_json
 		.get("id")
 		.expect("[mod.json]: Missing key 'id'")
 		.as_str()
 		.expect("[mod.json].id: Expected string")
 		.to_string();
 
-	let mod_path = indexer_path.join(format!("{}@{}", &mod_id, &major_version));
+	let entry = format!("{}@{}", &mod_id, &major_version);
+	let mod_path = indexer_path.join(&entry);
+
+	// Don't silently replace a package with another build of the same version
+	let existing_version = fs::File::open(mod_path.join("mod.geode"))
+		.ok()
+		.and_then(|f| zip::ZipArchive::new(f).ok())
+		.and_then(|mut a| {
+			mod_json_from_archive(&mut a)
+				.get("version")
+				.and_then(|v| v.as_str())
+				.map(String::from)
+		});
+	if let Some(existing) = existing_version {
+		let strip = |v: &str| v.strip_prefix('v').unwrap_or(v).to_string();
+		if strip(&existing) == strip(version) {
+			if !force {
+				fatal!(
+					"{} already has version {} - bump the version in mod.json to \
+					publish an update, or use --force to overwrite it anyway",
+					entry, version
+				);
+			}
+			warn!("Overwriting {} version {}", entry, version);
+		}
+		else if let (Ok(old), Ok(new)) = (Version::parse(&strip(&existing)), Version::parse(&strip(version))) {
+			if new < old {
+				warn!("Downgrading {} from {} to {}", entry, existing, version);
+			}
+		}
+	}
+
+	let mut platforms = archive
+		.file_names()
+		.filter_map(binary_platform)
+		.collect::<Vec<_>>();
+	platforms.sort();
+	platforms.dedup();
+
+	// The index rejects these later, better to find out now
+	let mut problems = Vec::new();
+	if platforms.is_empty() {
+		problems.push(format!("{} contains no binaries for any platform", package.display()));
+	}
+	for platform in declared_platforms(&mod_json) {
+		if !platforms.contains(&platform.as_str()) {
+			problems.push(format!(
+				"mod.json declares platform {} but {} has no binary for it",
+				platform, package.display()
+			));
+		}
+	}
+	for problem in &problems {
+		if strict {
+			fail!("{}", problem);
+		} else {
+			warn!("{}", problem);
+		}
+	}
+	if strict && !problems.is_empty() {
+		fatal!("Not exporting {} because of missing binaries", entry);
+	}
+
+	let tags = mod_json
+		.get("tags")
+		.and_then(|t| t.as_array())
+		.map(|t| t.iter().filter_map(|x| x.as_str()).collect::<Vec<_>>())
+		.unwrap_or_default();
+
+	let hash = sha256::digest_file(package)
+		.unwrap_or_else(|e| fatal!("Unable to hash {}: {}", package.display(), e));
+	info!("SHA-256 of {}: {}", package.display(), hash);
+
+	let entry_values = json!({
+		"platforms": platforms,
+		"tags": tags,
+		"sha256": hash,
+	});
+
+	if dry_run {
+		if mod_path.exists() {
+			info!("Would update existing entry {}", mod_path.display());
+		} else {
+			info!("Would create entry {}", mod_path.display());
+		}
+		info!("Would copy {} to {}", package.display(), mod_path.join("mod.geode").display());
+		info!("Would write {} to {}", entry_values, mod_path.join("entry.json").display());
+		for file in ENTRY_FILES {
+			if archive.by_name(file).is_ok() {
+				info!("Would extract {} to {}", file, mod_path.join(file).display());
+			}
+		}
+		return (mod_id, entry);
+	}
+
 	if !mod_path.exists() {
 		fs::create_dir(&mod_path)
 			.expect("Unable to create directory in local indexer for mod");
@@ -142,38 +986,772 @@ pub fn add_mod(package: PathBuf) {
 	fs::copy(package, mod_path.join("mod.geode"))
 		.expect("Unable to copy .geode package to local Indexer");
 
+	update_entry_json(&mod_path, entry_values);
+
+	// The index shows these without downloading the package
+	for file in ENTRY_FILES {
+		let dest = mod_path.join(file);
+		match archive.by_name(file) {
+			Ok(mut data) => {
+				let mut buf = Vec::new();
+				data.read_to_end(&mut buf)
+					.unwrap_or_else(|e| fatal!("Unable to read {} from package: {}", file, e));
+				fs::write(&dest, buf)
+					.unwrap_or_else(|e| fatal!("Unable to extract {}: {}", file, e));
+			},
+			// Don't leave one from an older version lying around
+			Err(_) => {
+				if dest.exists() {
+					fs::remove_file(&dest).expect("Unable to remove old entry file");
+				}
+			},
+		}
+	}
+
+	(mod_id, entry)
+}
+
+pub fn add_mods(
+	config: &Config,
+	packages: Vec<PathBuf>,
+	dry_run: bool,
+	force: bool,
+	strict: bool,
+	branch: bool,
+	no_squash: bool,
+) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+	let indexer_path = indexer_path(config);
+
+	let packages = expand_packages(packages);
+	if packages.is_empty() {
+		fatal!("No packages to export");
+	}
+
+	let mut exported: Vec<(String, String)> = Vec::new();
+	for package in &packages {
+		let (mod_id, entry) = export_package(&indexer_path, package, dry_run, force, strict);
+		if exported.iter().any(|(_, e)| *e == entry) {
+			warn!("{} is exported more than once, only the last package will be kept", entry);
+			exported.retain(|(_, e)| *e != entry);
+		}
+		exported.push((mod_id, entry));
+	}
+
+	let commit_msg = format!(
+		"Add/Update {}",
+		exported.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>().join(", ")
+	);
+	let entries = exported.iter().map(|(_, e)| e.as_str()).collect::<Vec<_>>().join(", ");
+
+	if dry_run {
+		info!("Would commit with message '{}'", commit_msg);
+		if branch {
+			for (mod_id, _) in &exported {
+				info!("Would update branch {}", mod_id);
+			}
+		}
+		done!("Dry run finished, your Indexer was not changed");
+		return;
+	}
+
 	let repo = Repository::open(&indexer_path)
 			.expect("Unable to open local Indexer repository");
-	reset_and_commit(&repo, &format!("Add/Update {}", &mod_id));
+	reset_and_commit(config, &repo, &commit_msg, no_squash);
 
-	match repo.find_remote("origin").and_then(|mut o| o.push(&["main"], None)) {
+	if branch {
+		for (mod_id, entry) in &exported {
+			update_mod_branch(config, &repo, mod_id, entry);
+		}
+	}
+
+	match push_to_fork(config, &repo) {
 		Ok(_) => {
-			done!(
-				"Succesfully added {}@{} to your indexer!",
-				mod_id, major_version
-			);
+			done!("Succesfully added {} to your indexer!", entries);
 		},
-		Err(_) => {	
-			done!("Successfully added {}@{}\n", mod_id, major_version);
+		Err(e) => {
+			done!("Successfully added {}\n", entries);
 			warn!(
-				"Unable to automatically sync the changes to Github. \
-				You will need to push this commit yourself."
+				"Unable to automatically sync the changes to Github: {}. \
+				You will need to push this commit yourself.",
+				e.message()
 			);
-			info!("Run `git -C {} push -f` to push the commit", indexer_path.to_str().unwrap());
+			info!("Run `geode indexer push` to push the commit");
 		},
 	}
-	if let Some(url) = repo.find_remote("origin").unwrap().url() {
-		info!(
-			"To let us know you're ready to publish your mod, please open \
-			a Pull Request on your repository: \
-			{}/compare/geode-sdk:indexer:main...main",
-			url
+	info!(
+		"To let us know you're ready to publish your mod, run `geode indexer pr` \
+		to open a Pull Request from your fork"
+	);
+}
+
+pub fn push(config: &Config) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	info!("Pushing to your fork");
+	match push_to_fork(config, &repo) {
+		Ok(_) => done!("Successfully pushed your Indexer"),
+		Err(e) => fatal!("Unable to push your Indexer: {}", e.message()),
+	}
+}
+
+pub fn status(config: &Config) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	let head = repo.head().expect("Broken repository, can't get HEAD");
+	let branch = head.shorthand().expect("Broken repository, invalid HEAD").to_string();
+	let head_commit = head.peel_to_commit().expect("Broken repository, can't get HEAD commit");
+
+	info!("On branch {}", branch);
+
+	// Uncommitted changes
+	let mut opts = StatusOptions::new();
+	opts.include_untracked(true).recurse_untracked_dirs(true);
+	let mut uncommitted = BTreeMap::new();
+	for entry in repo.statuses(Some(&mut opts)).expect("Unable to get repository status").iter() {
+		let Some(path) = entry.path() else { continue; };
+		let name = path.split('/').next().unwrap_or(path).to_string();
+		let mark = if entry.status().intersects(Status::WT_NEW | Status::INDEX_NEW) {
+			'+'
+		} else if entry.status().intersects(Status::WT_DELETED | Status::INDEX_DELETED) {
+			'-'
+		} else {
+			'~'
+		};
+		uncommitted.insert(name, mark);
+	}
+
+	if uncommitted.is_empty() {
+		println!("No uncommitted changes");
+	} else {
+		println!("Uncommitted changes:");
+		print_entries(&uncommitted);
+	}
+
+	// Compare against the fork
+	let url = repo.find_remote("origin")
+		.ok()
+		.and_then(|r| r.url().map(String::from))
+		.unwrap_or_default();
+	match fetch_branch(config, &repo, "origin", &url, &branch) {
+		Ok(fork_id) => {
+			let fork_commit = repo.find_commit(fork_id).unwrap();
+			let diff = repo.diff_tree_to_tree(
+				Some(&fork_commit.tree().unwrap()), Some(&head_commit.tree().unwrap()), None
+			).expect("Unable to compare with your fork");
+			let changes = changed_entries(&diff);
+
+			if changes.is_empty() {
+				println!("Your fork is up to date with your local Indexer");
+			} else {
+				println!("Not pushed to your fork yet (use `geode indexer push`):");
+				print_entries(&changes);
+			}
+		},
+		Err(e) => warn!("Unable to fetch your fork: {}", e.message()),
+	}
+
+	// Compare against upstream
+	match fetch_branch(config, &repo, "upstream", UPSTREAM_URL, "main") {
+		Ok(upstream_id) => {
+			let base = synced_base(&repo, head_commit.id(), upstream_id).unwrap_or(head_commit.id());
+			let (_, behind) = repo.graph_ahead_behind(base, upstream_id)
+				.expect("Unable to compare with upstream");
+			if behind > 0 {
+				println!("geode-sdk/indexer has {} commits you don't have (use `geode indexer sync`)", behind);
+			}
+
+			let upstream_commit = repo.find_commit(upstream_id).unwrap();
+			let diff = repo.diff_tree_to_tree(
+				Some(&upstream_commit.tree().unwrap()), Some(&head_commit.tree().unwrap()), None
+			).expect("Unable to compare with upstream");
+			let changes = changed_entries(&diff);
+
+			if changes.is_empty() {
+				println!("Your mods are identical to geode-sdk/indexer");
+			} else {
+				println!("Differences from geode-sdk/indexer:");
+				print_entries(&changes);
+			}
+		},
+		Err(e) => warn!("Unable to fetch geode-sdk/indexer: {}", e.message()),
+	}
+}
+
+/// Ask what to do with an entry that was changed both locally and upstream.
+/// Returns whether to keep the local version
+fn resolve_conflict(repo: &Repository, upstream: &git2::Tree, head: &git2::Tree, entry: &str) -> bool {
+	warn!("{} was changed both by you and in geode-sdk/indexer", entry);
+	loop {
+		let choice = ask_value(
+			&format!("Keep [m]ine, take [t]heirs or show [d]iff for {}", entry),
+			Some("d"),
+			true,
 		);
+		match choice.to_lowercase().as_str() {
+			"m" | "mine" | "keep-mine" => return true,
+			"t" | "theirs" | "take-theirs" => return false,
+			"d" | "diff" | "show-diff" => {
+				let mut opts = git2::DiffOptions::new();
+				opts.pathspec(entry);
+				let diff = repo.diff_tree_to_tree(Some(upstream), Some(head), Some(&mut opts))
+					.expect("Unable to compare with upstream");
+				diff.print(git2::DiffFormat::Patch, |_, _, line| {
+					let content = String::from_utf8_lossy(line.content());
+					match line.origin() {
+						'+' => print!("{}", format!("+{}", content).green()),
+						'-' => print!("{}", format!("-{}", content).red()),
+						' ' => print!(" {}", content),
+						_ => print!("{}", content),
+					}
+					true
+				}).expect("Unable to show diff");
+			},
+			_ => fail!("Please enter m, t or d"),
+		}
 	}
-	else {
-		info!(
-			"To let us know you're ready to publish your mod, please open \
-			a Pull Request on your Indexer fork repository."
+}
+
+pub fn sync(config: &Config, no_squash: bool) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	let mut opts = StatusOptions::new();
+	opts.include_untracked(true);
+	if !repo.statuses(Some(&mut opts)).expect("Unable to get repository status").is_empty() {
+		fatal!("Your local Indexer has uncommitted changes, export or discard them before syncing");
+	}
+
+	info!("Fetching geode-sdk/indexer");
+	let upstream_id = fetch_branch(config, &repo, "upstream", UPSTREAM_URL, "main")
+		.unwrap_or_else(|e| fatal!("Unable to fetch geode-sdk/indexer: {}", e.message()));
+	let upstream_commit = repo.find_commit(upstream_id).unwrap();
+
+	let head_commit = repo.head()
+		.and_then(|h| h.peel_to_commit())
+		.expect("Broken repository, can't get HEAD commit");
+
+	let base = synced_base(&repo, head_commit.id(), upstream_id);
+	let (_, behind) = repo.graph_ahead_behind(base.unwrap_or(head_commit.id()), upstream_id)
+		.expect("Unable to compare with upstream");
+	if behind == 0 {
+		done!("Your Indexer is already up to date with geode-sdk/indexer");
+		return;
+	}
+
+	// Our changes are whatever differs from the upstream commit we last synced
+	// with, or branched off from. Without one, anything that differs from
+	// upstream is one of ours, and entries only upstream has are other
+	// people's mods
+	let base_tree = match base {
+		Some(base) => repo.find_commit(base).unwrap().tree().unwrap(),
+		None => upstream_commit.tree().unwrap(),
+	};
+	let diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&head_commit.tree().unwrap()), None)
+		.expect("Unable to compare with upstream");
+	let mut local = changed_entries(&diff);
+	let upstream_tree = upstream_commit.tree().unwrap();
+	let head_tree = head_commit.tree().unwrap();
+	if base.is_none() {
+		local.retain(|_, mark| *mark != '-');
+		// there's no telling who changed an entry both have, so ask
+		let both = local.iter()
+			.filter(|(_, mark)| **mark == '~')
+			.map(|(entry, _)| entry.clone())
+			.collect::<Vec<_>>();
+		for entry in both {
+			if !resolve_conflict(&repo, &upstream_tree, &head_tree, &entry) {
+				local.remove(&entry);
+			}
+		}
+	} else {
+		let upstream_diff = repo.diff_tree_to_tree(Some(&base_tree), Some(&upstream_tree), None)
+			.expect("Unable to compare with upstream");
+		for entry in changed_entries(&upstream_diff).keys() {
+			if !local.contains_key(entry) {
+				continue;
+			}
+			let id = |tree: &git2::Tree| tree.get_path(Path::new(entry)).ok().map(|e| e.id());
+			// nothing to resolve if both made the same change
+			if id(&head_tree) == id(&upstream_tree)
+				|| !resolve_conflict(&repo, &upstream_tree, &head_tree, entry)
+			{
+				local.remove(entry);
+			}
+		}
+	}
+
+	repo.reset(upstream_commit.as_object(), ResetType::Hard, None)
+		.expect("Unable to update to geode-sdk/indexer");
+
+	if !local.is_empty() {
+		let mut checkout = CheckoutBuilder::new();
+		checkout.force();
+		for (entry, mark) in &local {
+			info!("Reapplying {}", entry);
+			if *mark == '-' {
+				drop(fs::remove_dir_all(indexer_path(config).join(entry)));
+			} else {
+				checkout.path(entry);
+			}
+		}
+		// an empty pathspec would check out the whole old tree
+		if local.values().any(|mark| *mark != '-') {
+			repo.checkout_tree(head_commit.as_object(), Some(&mut checkout))
+				.expect("Unable to reapply your mods");
+		}
+
+		reset_and_commit(
+			config,
+			&repo,
+			&format!("Add/Update {}", local.keys().cloned().collect::<Vec<_>>().join(", ")),
+			no_squash,
 		);
+	}
+
+	repo.reference(SYNCED_REF, upstream_id, true, "Sync with geode-sdk/indexer")
+		.expect("Unable to save sync state");
+
+	done!("Synced with geode-sdk/indexer");
+	if !local.is_empty() {
+		info!("Run `geode indexer push` to sync your fork");
+	}
+}
+
+pub fn diff(config: &Config, id: String, package: PathBuf) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let Some(entry) = find_entry(config, &id) else {
+		fatal!("Mod {} is not in your Indexer", id);
 	};
+	if !package.exists() {
+		fatal!("Package path {} does not exist!", package.display());
+	}
+
+	info!(
+		"Comparing {} against {}",
+		package.display(),
+		entry.file_name().unwrap().to_string_lossy()
+	);
+
+	let old_path = entry.join("mod.geode");
+	diff_packages(&old_path, &package);
+
+	let version = |path: &Path| {
+		let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).expect("Unable to read package");
+		let json = mod_json_from_archive(&mut archive);
+		let version = json.get("version").and_then(|v| v.as_str()).unwrap_or("").to_string();
+		version.strip_prefix('v').map(String::from).unwrap_or(version)
+	};
+	let (old_version, new_version) = (version(&old_path), version(&package));
+	if old_version == new_version {
+		warn!("The version is the same, exporting it will need --force");
+	} else if let (Ok(old), Ok(new)) = (Version::parse(&old_version), Version::parse(&new_version)) {
+		if new < old {
+			warn!("The new version is older than the one in your Indexer");
+		}
+	}
+}
+
+/// Run the index rules on an entry, printing the result of each one. Returns
+/// whether all of them passed
+fn validate_entry(entry: &Path) -> bool {
+	let mut ok = true;
+	let mut check = |passed: bool, msg: String| {
+		if passed {
+			done!("{}", msg);
+		} else {
+			fail!("{}", msg);
+			ok = false;
+		}
+	};
+
+	let package = entry.join("mod.geode");
+	let mut archive = match fs::File::open(&package).map_err(|e| e.to_string())
+		.and_then(|f| zip::ZipArchive::new(f).map_err(|e| e.to_string()))
+	{
+		Ok(archive) => archive,
+		Err(e) => {
+			check(false, format!("mod.geode is not a valid package: {e}"));
+			return false;
+		}
+	};
+
+	let mod_info = try_parse_mod_info(&package);
+	check(
+		mod_info.is_ok(),
+		match &mod_info {
+			Ok(_) => "mod.json is valid".into(),
+			Err(e) => format!("mod.json is invalid: {e}"),
+		},
+	);
+
+	// parse_version is lenient about where the 'v' goes, the index is not
+	let version = mod_json_from_archive(&mut archive)
+		.get("version")
+		.and_then(|v| v.as_str())
+		.map(String::from)
+		.unwrap_or_default();
+	let valid = Version::parse(version.strip_prefix('v').unwrap_or(&version)).is_ok();
+	check(
+		valid,
+		format!("Version '{version}' {} in the format vX.Y.Z", if valid { "is" } else { "is not" }),
+	);
+
+	if let Ok(info) = &mod_info {
+		let expected = format!("{}@{}", info.id, info.version.major);
+		let valid = entry.file_name().is_some_and(|name| name == expected.as_str());
+		check(
+			valid,
+			if valid {
+				format!("Entry is named {expected}")
+			} else {
+				format!("Entry should be named {expected}")
+			},
+		);
+	}
+
+	let platforms = archive
+		.file_names()
+		.filter_map(binary_platform)
+		.collect::<Vec<_>>();
+	check(
+		!platforms.is_empty(),
+		if platforms.is_empty() {
+			"Package has no binaries for any platform".into()
+		} else {
+			format!("Package has binaries for {}", platforms.join(", "))
+		},
+	);
+
+	for file in ["logo.png", "about.md"] {
+		let found = archive.by_name(file).is_ok();
+		check(
+			found,
+			format!("Package {} {file}", if found { "contains" } else { "is missing" }),
+		);
+	}
+
+	let recorded = fs::read_to_string(entry.join("entry.json"))
+		.ok()
+		.and_then(|data| serde_json::from_str::<serde_json::Value>(&data).ok())
+		.and_then(|json| json.get("sha256").and_then(|h| h.as_str()).map(String::from));
+	if let Some(recorded) = recorded {
+		let valid = sha256::digest_file(&package).is_ok_and(|hash| hash == recorded);
+		check(
+			valid,
+			format!("SHA-256 in entry.json {} mod.geode", if valid { "matches" } else { "does not match" }),
+		);
+	}
+
+	ok
+}
+
+pub fn validate(config: &Config, id: String) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let Some(entry) = find_entry(config, &id) else {
+		fatal!("Mod {} is not in your Indexer", id);
+	};
+
+	info!("Validating {}", entry.file_name().unwrap().to_string_lossy());
+	if validate_entry(&entry) {
+		done!("All checks passed");
+	} else {
+		fatal!("Some checks failed - fix them before submitting your mod");
+	}
+}
+
+pub fn reset(config: &Config) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	if !ask_confirm(
+		"This will discard every change to your local Indexer that isn't \
+		pushed to your fork. Are you sure?",
+		false
+	) {
+		fatal!("Aborting");
+	}
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	let head = repo.head().expect("Broken repository, can't get HEAD");
+	let branch = head.shorthand().expect("Broken repository, invalid HEAD").to_string();
+	let url = repo.find_remote("origin")
+		.ok()
+		.and_then(|r| r.url().map(String::from))
+		.unwrap_or_default();
+
+	info!("Fetching your fork");
+	let fork_id = fetch_branch(config, &repo, "origin", &url, &branch)
+		.unwrap_or_else(|e| fatal!("Unable to fetch your fork: {}", e.message()));
+
+	repo.reset(&repo.find_object(fork_id, None).unwrap(), ResetType::Hard, None)
+		.expect("Unable to reset to your fork");
+
+	// Hard reset leaves untracked files alone
+	let mut opts = StatusOptions::new();
+	opts.include_untracked(true);
+	for entry in repo.statuses(Some(&mut opts)).expect("Unable to get repository status").iter() {
+		if !entry.status().contains(Status::WT_NEW) {
+			continue;
+		}
+		let Some(path) = entry.path() else { continue; };
+		let path = indexer_path(config).join(path);
+		if path.is_dir() {
+			fs::remove_dir_all(&path)
+		} else {
+			fs::remove_file(&path)
+		}.unwrap_or_else(|e| fatal!("Unable to remove {}: {}", path.display(), e));
+	}
+
+	done!("Your local Indexer now matches your fork");
+}
+
+/// Open a URL in the default browser
+fn open_in_browser(url: &str) -> std::io::Result<()> {
+	#[cfg(windows)]
+	let mut command = {
+		let mut command = std::process::Command::new("rundll32");
+		command.arg("url.dll,FileProtocolHandler");
+		command
+	};
+	#[cfg(target_os = "macos")]
+	let mut command = std::process::Command::new("open");
+	#[cfg(not(any(windows, target_os = "macos")))]
+	let mut command = std::process::Command::new("xdg-open");
+
+	command.arg(url).spawn().map(|_| ())
+}
+
+pub fn open(config: &Config, pr: bool) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	let remote = repo.find_remote("origin").expect("Unable to find your fork remote");
+	let Some((owner, name)) = remote.url().and_then(github_repo_from_url) else {
+		fatal!("Your Indexer remote does not seem to point to a Github repository");
+	};
+
+	let url = if pr {
+		let head = repo.head().expect("Broken repository, can't get HEAD");
+		let branch = head.shorthand().expect("Broken repository, invalid HEAD");
+		format!("{UPSTREAM_URL}/compare/main...{owner}:{name}:{branch}")
+	} else {
+		format!("https://github.com/{owner}/{name}")
+	};
+
+	info!("Opening {}", url);
+	if let Err(e) = open_in_browser(&url) {
+		fail!("Unable to open the browser: {}", e);
+	}
+}
+
+pub fn open_pr(config: &Config, id: Option<String>) {
+	if !is_initialized(config) {
+		fatal!("Indexer has not been set up - use `geode indexer init` to set it up");
+	}
+
+	if github_token(config).is_none() {
+		fatal!(
+			"No Github token found - set one using `geode config set github-token <token>` \
+			or the GEODE_GITHUB_TOKEN environment variable"
+		);
+	}
+
+	let entry = match id {
+		Some(id) => find_entry(config, &id).unwrap_or_else(|| fatal!("Mod {} is not in your Indexer", id)),
+		None => {
+			let mut entries = entries(config);
+			if entries.len() != 1 {
+				fatal!(
+					"Your Indexer has {} mods, please specify which one the \
+					Pull Request is for",
+					entries.len()
+				);
+			}
+			entries.remove(0)
+		}
+	};
+
+	let mod_info = try_parse_mod_info(&entry.join("mod.geode"))
+		.unwrap_or_else(|e| fatal!("Unable to read mod.json of {}: {}", entry.display(), e));
+
+	let repo = Repository::open(indexer_path(config))
+		.expect("Unable to open local Indexer repository");
+
+	let remote = repo.find_remote("origin").expect("Unable to find your fork remote");
+	let Some((owner, _)) = remote.url().and_then(github_repo_from_url) else {
+		fatal!("Your Indexer remote does not seem to point to a Github repository");
+	};
+
+	// mods exported with --branch get their own Pull Request
+	let branch = if repo.find_branch(&mod_info.id, BranchType::Local).is_ok() {
+		mod_info.id.clone()
+	} else {
+		let head = repo.head().expect("Broken repository, can't get HEAD");
+		head.shorthand().expect("Broken repository, invalid HEAD").to_string()
+	};
+
+	let title = format!("Add/Update {} {}", mod_info.name, mod_info.version);
+	let body = format!(
+		"Mod: **{}** (`{}`)\nVersion: {}\nDeveloper: {}\n\n{}",
+		mod_info.name, mod_info.id, mod_info.version, mod_info.developer, mod_info.description
+	);
+
+	let client = github_client(config);
+
+	let existing = client
+		.get("https://api.github.com/repos/geode-sdk/indexer/pulls")
+		.query(&[("head", format!("{owner}:{branch}")), ("state", "open".into())])
+		.send()
+		.and_then(|r| r.error_for_status())
+		.and_then(|r| r.json::<Vec<GithubPullRequest>>())
+		.unwrap_or_else(|e| fatal!("Unable to query existing Pull Requests: {}", e));
+
+	let response = if let Some(pr) = existing.first() {
+		info!("Updating existing Pull Request #{}", pr.number);
+		client
+			.patch(format!("https://api.github.com/repos/geode-sdk/indexer/pulls/{}", pr.number))
+			.json(&json!({ "title": title, "body": body }))
+			.send()
+	} else {
+		info!("Opening a new Pull Request");
+		client
+			.post("https://api.github.com/repos/geode-sdk/indexer/pulls")
+			.json(&json!({
+				"title": title,
+				"body": body,
+				"head": format!("{owner}:{branch}"),
+				"base": "main",
+			}))
+			.send()
+	};
+
+	let pr = response
+		.and_then(|r| r.error_for_status())
+		.and_then(|r| r.json::<GithubPullRequest>())
+		.unwrap_or_else(|e| fatal!("Unable to open Pull Request: {}", e));
+
+	done!("Pull Request ready: {}", pr.html_url);
+}
+
+pub fn publish(config: &Config, packages: Vec<PathBuf>, git: bool) {
+	if git {
+		if !is_initialized(config) {
+			initialize(config, None, false, false, None);
+		}
+		add_mods(config, packages, false, false, false, false, false);
+		return;
+	}
+
+	let Some(token) = index_token(config) else {
+		fatal!(
+			"No index token found - set one using `geode config set index-token <token>` \
+			or the GEODE_INDEX_TOKEN environment variable, or use --git to publish \
+			through your Indexer fork"
+		);
+	};
+
+	let packages = expand_packages(packages);
+	if packages.is_empty() {
+		fatal!("No packages to publish");
+	}
+
+	let mut headers = HeaderMap::new();
+	headers.insert(USER_AGENT, HeaderValue::from_static("GeodeCli"));
+	headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/octet-stream"));
+	headers.insert(
+		AUTHORIZATION,
+		HeaderValue::from_str(&format!("Bearer {token}")).expect("Invalid index token"),
+	);
+
+	let client = reqwest::blocking::Client::builder()
+		.default_headers(headers)
+		.build()
+		.unwrap();
+
+	let url = index_url(config);
+	for package in &packages {
+		if !package.exists() {
+			fatal!("Package path {} does not exist!", package.display());
+		}
+		let mod_info = try_parse_mod_info(package)
+			.unwrap_or_else(|e| fatal!("Unable to read mod.json of {}: {}", package.display(), e));
+
+		info!("Uploading {} {}", mod_info.id, mod_info.version);
+		let response = client
+			.post(format!("{}/v1/mods/{}/versions", url, mod_info.id))
+			.body(fs::read(package).expect("Unable to read package"))
+			.send()
+			.unwrap_or_else(|e| fatal!("Unable to reach the index at {}: {}", url, e));
+
+		let status = response.status();
+		if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+			fatal!("The index rejected your token, check `geode config get index-token`");
+		}
+		if !status.is_success() {
+			let text = response.text().unwrap_or_default();
+			fatal!(
+				"Unable to publish {}: {} {}",
+				mod_info.id,
+				status,
+				text.trim()
+			);
+		}
+
+		done!("Published {} {}", mod_info.id, mod_info.version);
+	}
+}
+
+pub fn subcommand(config: &mut Config, path: Option<PathBuf>, cmd: Indexer) {
+	config.indexer_path_override = path;
+
+	match cmd {
+		Indexer::Init { url, force, no_verify, depth } => {
+			initialize(config, url, force, no_verify, depth)
+		},
+		Indexer::List { json, offline } => list_mods(config, json, offline),
+		Indexer::Remove { ids, no_squash } => remove_mods(config, ids, no_squash),
+		Indexer::Prune { no_squash } => prune(config, no_squash),
+		Indexer::Export { packages, dry_run, force, strict, branch, no_squash } => {
+			add_mods(config, packages, dry_run, force, strict, branch, no_squash)
+		},
+		Indexer::Diff { id, package } => diff(config, id, package),
+		Indexer::Push => push(config),
+		Indexer::Status => status(config),
+		Indexer::Validate { id } => validate(config, id),
+		Indexer::Sync { no_squash } => sync(config, no_squash),
+		Indexer::Reset => reset(config),
+		Indexer::Publish { packages, git } => publish(config, packages, git),
+		Indexer::Open { pr } => open(config, pr),
+		Indexer::Pr { id } => open_pr(config, id),
+	}
 }
@@ -1,7 +1,7 @@
 use crate::config::Config;
 use crate::util::config::Profile;
 use crate::{done, fail, info};
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
 use colored::Colorize;
 use std::cell::RefCell;
 use std::io::BufRead;
@@ -38,7 +38,34 @@ pub enum Info {
 	Setup {},
 }
 
-const CONFIGURABLES: [&str; 3] = ["default-developer", "sdk-path", "sdk-nightly"];
+const CONFIGURABLES: [&str; 17] = [
+	"default-developer",
+	"sdk-path",
+	"sdk-nightly",
+	"github-token",
+	"git-name",
+	"git-email",
+	"indexer-path",
+	"indexer-keep-history",
+	"indexer-shallow",
+	"index-url",
+	"index-token",
+	"compress-pngs",
+	"package-compression",
+	"proxy",
+	"sdk-mirrors",
+	"binaries-mirrors",
+	"index-mirrors",
+];
+
+fn mirrors_mut<'a>(config: &'a mut Config, field: &str) -> Option<&'a mut Vec<String>> {
+	match field {
+		"sdk-mirrors" => Some(&mut config.sdk_mirrors),
+		"binaries-mirrors" => Some(&mut config.binaries_mirrors),
+		"index-mirrors" => Some(&mut config.index_mirrors),
+		_ => None,
+	}
+}
 
 fn get_bool(value: &str) -> Option<bool> {
 	let lower = value.to_ascii_lowercase();
@@ -62,6 +89,49 @@ pub fn subcommand(config: &mut Config, cmd: Info) {
 			} else if field == "sdk-nightly" {
 				config.sdk_nightly =
 					get_bool(&value).expect(&format!("'{}' cannot be parsed as a bool", value));
+			} else if field == "github-token" {
+				config.github_token = Some(value);
+			} else if field == "git-name" {
+				config.git_name = Some(value);
+			} else if field == "git-email" {
+				config.git_email = Some(value);
+			} else if field == "indexer-path" {
+				config.indexer_path = Some(PathBuf::from(value));
+			} else if field == "indexer-keep-history" {
+				config.indexer_keep_history =
+					get_bool(&value).unwrap_or_else(|| panic!("'{}' cannot be parsed as a bool", value));
+			} else if field == "indexer-shallow" {
+				config.indexer_shallow =
+					get_bool(&value).unwrap_or_else(|| panic!("'{}' cannot be parsed as a bool", value));
+			} else if field == "index-url" {
+				config.index_url = Some(value);
+			} else if field == "index-token" {
+				config.index_token = Some(value);
+			} else if field == "compress-pngs" {
+				config.compress_pngs =
+					get_bool(&value).unwrap_or_else(|| panic!("'{}' cannot be parsed as a bool", value));
+			} else if field == "package-compression" && value.is_empty() {
+				config.package_compression = None;
+			} else if field == "package-compression" {
+				if crate::package::Compression::from_str(&value, true).is_err() {
+					fail!("'{}' is not a compression level, use store, fast or best", value);
+					return;
+				}
+				config.package_compression = Some(value.to_ascii_lowercase());
+			} else if field == "proxy" && value.is_empty() {
+				config.proxy = None;
+			} else if field == "proxy" {
+				if !reqwest::Url::parse(&value).is_ok_and(|url| url.has_host()) {
+					fail!("'{}' is not a valid proxy URL", value);
+					return;
+				}
+				config.proxy = Some(value);
+			} else if let Some(mirrors) = mirrors_mut(config, &field) {
+				// Comma-separated, in the order they're tried
+				*mirrors = value.split(',')
+					.map(|m| m.trim().to_string())
+					.filter(|m| !m.is_empty())
+					.collect();
 			} else if field == "sdk-path" {
 				fail!("Set the SDK Path using `geode sdk set-path <PATH>`");
 				return;
@@ -75,6 +145,9 @@ pub fn subcommand(config: &mut Config, cmd: Info) {
 
 		Info::Get { field, raw } => {
 			let sdk_path;
+			let indexer_path;
+			let index_url;
+			let mirrors;
 
 			let out = if field == "default-developer" {
 				config.default_developer.as_deref().unwrap_or("")
@@ -87,6 +160,45 @@ pub fn subcommand(config: &mut Config, cmd: Info) {
 				} else {
 					"false"
 				}
+			} else if field == "github-token" {
+				config.github_token.as_deref().unwrap_or("")
+			} else if field == "git-name" {
+				config.git_name.as_deref().unwrap_or("")
+			} else if field == "git-email" {
+				config.git_email.as_deref().unwrap_or("")
+			} else if field == "indexer-path" {
+				indexer_path = crate::indexer::indexer_path(config);
+				indexer_path.to_str().unwrap_or("")
+			} else if field == "indexer-keep-history" {
+				if config.indexer_keep_history {
+					"true"
+				} else {
+					"false"
+				}
+			} else if field == "indexer-shallow" {
+				if config.indexer_shallow {
+					"true"
+				} else {
+					"false"
+				}
+			} else if field == "index-url" {
+				index_url = crate::indexer::index_url(config);
+				&index_url
+			} else if field == "index-token" {
+				config.index_token.as_deref().unwrap_or("")
+			} else if field == "compress-pngs" {
+				if config.compress_pngs {
+					"true"
+				} else {
+					"false"
+				}
+			} else if field == "package-compression" {
+				config.package_compression.as_deref().unwrap_or("")
+			} else if field == "proxy" {
+				config.proxy.as_deref().unwrap_or("")
+			} else if let Some(list) = mirrors_mut(config, &field) {
+				mirrors = list.join(",");
+				&mirrors
 			} else if raw {
 				std::process::exit(1);
 			} else {
@@ -12,6 +12,9 @@ mod index;
 mod file;
 mod indexer;
 mod project;
+mod ide;
+mod i18n;
+mod workspace;
 
 use util::*;
 
@@ -28,7 +31,60 @@ enum GeodeCommands {
 	/// Initialize a new Geode project
 	New {
 		/// The target directory to create the project in
-		path: Option<PathBuf>
+		path: Option<PathBuf>,
+
+		/// Template to use instead of the example mod: `minimal` (one source
+		/// file), `full` (settings, resources and events), `library` (an API
+		/// for other mods) or `ui` (a popup opened from the main menu), or a
+		/// git URL or path; see `geode template list`. A `geode-template.json`
+		/// in it lists globs of the paths to fill in under `substitute`,
+		/// where placeholders like `{{MOD_ID}}`, `{{MOD_NAME}}` and
+		/// `{{DEVELOPER}}` are replaced in both contents and file names
+		#[clap(long)]
+		template: Option<String>,
+
+		/// Don't download anything: the minimal template is used instead of
+		/// cloning the example mod, and only built-in or local templates
+		/// can be given
+		#[clap(long)]
+		offline: bool,
+
+		/// SPDX identifier of the mod's license, like MIT or GPL-3.0. Asked
+		/// for if not given
+		#[clap(long)]
+		license: Option<String>,
+
+		/// Don't create a git repository with the project in a first commit
+		#[clap(long)]
+		no_git: bool,
+
+		/// Run the commands the template lists under `post-generate` in its
+		/// geode-template.json. Only use with templates you trust
+		#[clap(long)]
+		allow_scripts: bool,
+	},
+
+	/// Configure and build a mod with CMake. In a workspace, members it
+	/// depends on are built first
+	Build {
+		/// ID or folder name of the workspace member to build. Defaults to
+		/// the mod in the current directory
+		#[clap(conflicts_with = "all")]
+		member: Option<String>,
+
+		/// Build every mod in the workspace
+		#[clap(long)]
+		all: bool,
+
+		/// CMake configuration to build
+		#[clap(long, default_value = "RelWithDebInfo")]
+		config: String,
+	},
+
+	/// Templates for new projects
+	Template {
+		#[clap(subcommand)]
+		commands: crate::template::Template,
 	},
 
 	/// Options for managing profiles (installations of Geode)
@@ -61,10 +117,27 @@ enum GeodeCommands {
 		commands: crate::package::Package,
 	},
 
+	/// Tools for repositories with several mods, listed in a
+	/// geode-workspace.json at the root
+	Workspace {
+		#[clap(subcommand)]
+		commands: crate::workspace::Workspace,
+	},
+
 	/// Tools for interacting with the Geode mod index
 	Index {
 		#[clap(subcommand)]
 		commands: crate::index::Index,
+	},
+
+	/// Tools for publishing mods through your Indexer fork
+	Indexer {
+		/// Use the Indexer at this path instead of the configured one
+		#[clap(long, global = true)]
+		path: Option<PathBuf>,
+
+		#[clap(subcommand)]
+		commands: crate::indexer::Indexer,
 	}
 }
 
@@ -93,13 +166,19 @@ fn main() {
 	let mut config = config::Config::new();
 
 	match args.command {
-		GeodeCommands::New { path } => template::build_template(&mut config, path),
+		GeodeCommands::New { path, template, offline, license, no_git, allow_scripts } => template::build_template(
+			&mut config, path, template, offline, license, !no_git, allow_scripts
+		),
+		GeodeCommands::Build { member, all, config: build_config } => workspace::build(member, all, &build_config),
+		GeodeCommands::Template { commands } => template::subcommand(commands),
 		GeodeCommands::Profile { commands } => profile::subcommand(&mut config, commands),
 		GeodeCommands::Config { commands } => info::subcommand(&mut config, commands),
 		GeodeCommands::Sdk { commands } => sdk::subcommand(&mut config, commands),
 		GeodeCommands::Package { commands } => package::subcommand(&mut config, commands),
 		GeodeCommands::Project { commands } => project::subcommand(&mut config, commands),
+		GeodeCommands::Workspace { commands } => workspace::subcommand(commands),
 		GeodeCommands::Index { commands } => index::subcommand(&mut config, commands),
+		GeodeCommands::Indexer { path, commands } => indexer::subcommand(&mut config, path, commands),
 	}
 
 	config.save();
@@ -1,45 +1,265 @@
 
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::{Read, Write, Seek};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use image::codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder};
+use image::ImageEncoder;
 use zip::write::FileOptions;
 use zip::ZipWriter;
+use rand_core::{OsRng, RngCore};
+use rayon::prelude::*;
+use regex::Regex;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use sha3::{Digest, Sha3_256};
 
 use crate::config::Config;
 use crate::util::bmfont;
 use crate::util::cache::CacheBundle;
-use crate::util::mod_file::{ModFileInfo, parse_mod_info};
+use crate::util::mod_file::{ModApi, ModFileInfo, ModResources, format_mod_json, is_valid_id, parse_mod_info, read_mod_json, validate_mod_json, write_mod_json};
 use crate::util::spritesheet;
-use crate::{cache, project};
+use crate::{cache, index, project, workspace};
 use crate::{done, fail, info, warn, fatal};
+use colored::Colorize;
 
 #[derive(Subcommand, Debug)]
 #[clap(rename_all = "kebab-case")]
 pub enum Package {
 	/// Install a .geode package to the current profile
 	Install {
-		/// Location of the .geode package to install
-		path: PathBuf,
+		/// Location of the .geode package to install, or the ID or folder
+		/// name of a workspace member to install the package built for it
+		#[clap(required_unless_present = "all")]
+		path: Option<PathBuf>,
+
+		/// Install the packages built for every mod in the workspace
+		#[clap(long, conflicts_with = "path")]
+		all: bool,
+	},
+
+	/// Uninstall a mod from the current profile
+	Uninstall {
+		/// ID of the mod to uninstall
+		id: String,
+
+		/// Also delete the mod's save data
+		#[clap(long)]
+		purge: bool,
 	},
 
 	/// Create a .geode package
 	New {
+		/// Location of mod's folder, or the ID or folder name of a workspace
+		/// member to package what's been built for it
+		#[clap(required_unless_present = "all")]
+		root_path: Option<PathBuf>,
+
+		/// Package every mod in the workspace
+		#[clap(long, conflicts_with = "root_path")]
+		all: bool,
+
+		/// Add binary file. If none are given, binaries in the mod's folder
+		/// are used, or in the build directory for workspace members
+		#[clap(short, long, num_args(1..), conflicts_with = "all")]
+		binary: Vec<PathBuf>,
+
+		/// Location of output file. Defaults to `<id>.geode` in the current
+		/// directory, or in the build directory for workspace members
+		#[clap(short, long, conflicts_with = "all")]
+		output: Option<PathBuf>,
+
+		/// Whether to install the generated package after creation
+		#[clap(short, long)]
+		install: bool,
+
+		/// Losslessly recompress PNGs before zipping. Can also be turned on
+		/// with the `compress-pngs` option
+		#[clap(long)]
+		compress: bool,
+
+		/// Strip debug info from the binaries, like `geode package strip`
+		#[clap(long)]
+		strip: bool,
+
+		/// Package a mod that other mods use, which may have no binaries if
+		/// it's header-only. Its headers come from `api.include` in mod.json
+		#[clap(long)]
+		api: bool,
+
+		/// How hard to compress the package. Defaults to the
+		/// `package-compression` option
+		#[clap(long, value_enum)]
+		compression: Option<Compression>,
+
+		/// Also write a C++ header with constants for every resource name
+		#[clap(long, conflicts_with = "all")]
+		header: Option<PathBuf>,
+
+		/// Obfuscate resources with the key in this file, so they can't be
+		/// pulled out of the package as is. If it doesn't exist, a new key is
+		/// generated and saved there. Needs a loader that supports it
+		#[clap(long)]
+		obfuscate: Option<PathBuf>,
+
+		/// Bundle a dependency's .geode package, for dependencies that aren't
+		/// on the index
+		#[clap(long, num_args(1..), conflicts_with = "all")]
+		bundle: Vec<PathBuf>,
+	},
+
+	/// Set up a mod in the current profile with its files linked to the
+	/// mod's folder, for testing without packaging after every change
+	Link {
 		/// Location of mod's folder
 		root_path: PathBuf,
 
-		/// Add binary file
+		/// Add binary file. If none are given, binaries in the mod's folder
+		/// are used
 		#[clap(short, long, num_args(1..))]
 		binary: Vec<PathBuf>,
+	},
+
+	/// Show information about a .geode package
+	Info {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// Print the raw mod.json
+		#[clap(long)]
+		json: bool,
+	},
+
+	/// Download a .geode package from a URL or the index and show what's in it
+	Fetch {
+		/// URL of the package, or the ID of a mod on the index
+		source: String,
+
+		/// Version of the mod to fetch from the index
+		#[clap(long, default_value = "*")]
+		version: String,
 
-		/// Location of output file
+		/// Expected SHA-256 or SHA3-256 hash of the package. Packages from
+		/// the index are checked against the index's hash
+		#[clap(long)]
+		hash: Option<String>,
+
+		/// Where to save the package. Defaults to its name in the current
+		/// directory
 		#[clap(short, long)]
-		output: PathBuf,
+		output: Option<PathBuf>,
+	},
 
-		/// Whether to install the generated package after creation
+	/// Check a mod's mod.json against the Geode mod.json schema
+	Validate {
+		/// Location of the mod's folder, its mod.json or a .geode package
+		path: PathBuf,
+	},
+
+	/// Show how big a package is, what takes up the most space, and whether
+	/// it fits in the index's upload limit
+	Size {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// How many of the largest files to show
+		#[clap(long, default_value_t = 10)]
+		top: usize,
+	},
+
+	/// Check that a package's binaries are for the platforms its mod.json
+	/// declares
+	Verify {
+		/// Location of the .geode package
+		path: PathBuf,
+	},
+
+	/// List the files in a .geode package by size
+	ListFiles {
+		/// Location of the .geode package
+		path: PathBuf,
+	},
+
+	/// Unpack a .geode package into a directory
+	Extract {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// Directory to extract to. Defaults to the package name without
+		/// the extension
+		dest: Option<PathBuf>,
+
+		/// Only extract these files, either paths like `mod.json` or
+		/// `resources`, or glob patterns like `*.png`
+		#[clap(long, num_args(1..))]
+		only: Vec<String>,
+	},
+
+	/// Remove debug info (PDBs, dSYMs and debug sections) from a package's
+	/// binaries
+	Strip {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// Save the removed symbols to `<id>-symbols.zip` next to the package
+		/// for symbolicating crashes later
+		#[clap(long)]
+		symbols: bool,
+	},
+
+	/// Rewrite a package made for an older format into the current one
+	Migrate {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// Where to write the migrated package. Defaults to replacing the
+		/// original
 		#[clap(short, long)]
-		install: bool,
+		output: Option<PathBuf>,
+	},
+
+	/// Change the ID of a mod, in a .geode package or a mod's folder
+	SetId {
+		/// Location of the .geode package or the mod's folder
+		path: PathBuf,
+
+		/// New ID of the mod
+		id: String,
+	},
+
+	/// Embed a checksum manifest in a package, and optionally sign it
+	Sign {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// File with your ed25519 signing key. If it doesn't exist, a new key
+		/// is generated and saved there
+		#[clap(long)]
+		key: Option<PathBuf>,
+	},
+
+	/// Check a package against its embedded checksums and signature
+	VerifySignature {
+		/// Location of the .geode package
+		path: PathBuf,
+
+		/// Public key (hex) the package should be signed with
+		#[clap(long)]
+		public_key: Option<String>,
+	},
+
+	/// Compare two packages
+	Diff {
+		/// The old package
+		old: PathBuf,
+
+		/// The new package
+		new: PathBuf,
 	},
 
 	/// Merge multiple packages
@@ -78,16 +298,293 @@ pub enum Package {
 		/// Less verbose output
 		#[clap(long)]
 		shut_up: bool,
+
+		/// Also write a C++ header with constants for every resource name
+		#[clap(long)]
+		header: Option<PathBuf>,
+
+		/// Fail on problems with textures instead of warning about them
+		#[clap(long)]
+		strict: bool,
 	},
 }
 
+/// Binary extensions and the platforms they are for. `.ios.dylib` has to come
+/// before `.dylib`
+pub const PLATFORM_BINARIES: [(&str, &str); 4] = [
+	(".ios.dylib", "ios"),
+	(".dylib", "macos"),
+	(".dll", "windows"),
+	(".so", "android"),
+];
+
+/// Get the platform a binary is for from its file name
+pub fn binary_platform(name: &str) -> Option<&'static str> {
+	PLATFORM_BINARIES
+		.iter()
+		.find(|(ext, _)| name.ends_with(ext))
+		.map(|(_, platform)| *platform)
+}
+
+/// Platforms mod.json says the mod is for, from the keys of `binary` when it
+/// has one per platform, or a `platforms` list
+pub fn declared_platforms(mod_json: &serde_json::Value) -> Vec<String> {
+	let mut res = Vec::new();
+	if let Some(binary) = mod_json.get("binary").and_then(|b| b.as_object()) {
+		res.extend(binary.keys().filter(|k| *k != "*").map(|k| k.to_lowercase()));
+	}
+	if let Some(platforms) = mod_json.get("platforms").and_then(|p| p.as_array()) {
+		res.extend(platforms.iter().filter_map(|p| p.as_str()).map(|p| p.to_lowercase()));
+	}
+	res.sort();
+	res.dedup();
+	res
+}
+
+/// Stop with every schema error if mod.json is invalid
+pub fn ensure_valid_mod_json(mod_json: &serde_json::Value) {
+	let errors = validate_mod_json(mod_json);
+	for error in &errors {
+		fail!("{}", error);
+	}
+	if !errors.is_empty() {
+		fatal!("mod.json is invalid, see `geode package validate`");
+	}
+}
+
+fn validate_package(path: &Path) {
+	let mod_json = read_mod_json(path).unwrap_or_else(|e| fatal!("{}", e));
+	let errors = validate_mod_json(&mod_json);
+	if errors.is_empty() {
+		done!("mod.json is valid");
+		return;
+	}
+	for error in &errors {
+		fail!("{}", error);
+	}
+	fatal!("Found {} problems in mod.json", errors.len());
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum BinaryFormat {
+	Pe,
+	MachO,
+	Elf,
+}
+
+/// Binary format and architectures each platform can load
+const PLATFORM_TARGETS: [(&str, BinaryFormat, &[&str]); 4] = [
+	("windows", BinaryFormat::Pe, &["x86"]),
+	("macos", BinaryFormat::MachO, &["x86_64", "arm64"]),
+	("ios", BinaryFormat::MachO, &["arm64"]),
+	("android", BinaryFormat::Elf, &["arm", "arm64"]),
+];
+
+/// Read the format and architectures of a binary from its header
+fn sniff_binary(data: &[u8]) -> Option<(BinaryFormat, Vec<&'static str>)> {
+	let u16_le = |at: usize| Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?));
+	let u32_le = |at: usize| Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?));
+	let u32_be = |at: usize| Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?));
+
+	let mach_arch = |cpu: u32| match cpu {
+		7 => "x86",
+		0x0100_0007 => "x86_64",
+		12 => "arm",
+		0x0100_000c => "arm64",
+		_ => "unknown",
+	};
+
+	if data.starts_with(b"MZ") {
+		let pe = u32_le(0x3c)? as usize;
+		if data.get(pe..pe + 4)? != b"PE\0\0" {
+			return None;
+		}
+		let arch = match u16_le(pe + 4)? {
+			0x14c => "x86",
+			0x8664 => "x86_64",
+			0x1c0 | 0x1c4 => "arm",
+			0xaa64 => "arm64",
+			_ => "unknown",
+		};
+		Some((BinaryFormat::Pe, vec![arch]))
+	} else if data.starts_with(b"\x7fELF") {
+		let arch = match u16_le(18)? {
+			3 => "x86",
+			0x3e => "x86_64",
+			0x28 => "arm",
+			0xb7 => "arm64",
+			_ => "unknown",
+		};
+		Some((BinaryFormat::Elf, vec![arch]))
+	} else {
+		match u32_be(0)? {
+			// Universal binary, with a list of architectures
+			0xcafe_babe => {
+				let count = u32_be(4)? as usize;
+				let archs = (0..count)
+					.map(|i| u32_be(8 + i * 20).map(mach_arch))
+					.collect::<Option<Vec<_>>>()?;
+				Some((BinaryFormat::MachO, archs))
+			},
+			0xcefa_edfe | 0xcffa_edfe => Some((BinaryFormat::MachO, vec![mach_arch(u32_le(4)?)])),
+			_ => None,
+		}
+	}
+}
+
+fn verify_binaries(path: &Path) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+	let declared = declared_platforms(&mod_json_from_archive(&mut archive));
+
+	let mut problems = 0;
+	let mut found = Vec::new();
+	for i in 0..archive.len() {
+		let mut file = archive.by_index(i).expect("Unable to read package");
+		let name = file.name().to_string();
+		let Some(platform) = binary_platform(&name) else {
+			continue;
+		};
+		found.push(platform);
+
+		let mut data = Vec::new();
+		file.read_to_end(&mut data).expect("Unable to read package");
+		let (_, format, archs) = PLATFORM_TARGETS.iter().find(|(p, _, _)| *p == platform).unwrap();
+
+		match sniff_binary(&data) {
+			None => {
+				fail!("{} is not a binary for {}", name, platform);
+				problems += 1;
+			},
+			Some((actual, _)) if actual != *format => {
+				fail!("{} is a {:?} binary, but {} needs {:?}", name, actual, platform, format);
+				problems += 1;
+			},
+			Some((_, actual)) if !actual.iter().any(|a| archs.contains(a)) => {
+				fail!(
+					"{} is built for {}, but {} needs {}",
+					name, actual.join(", "), platform, archs.join(" or ")
+				);
+				problems += 1;
+			},
+			Some((actual, arch)) => {
+				done!("{}: {:?} {} ({})", name, actual, arch.join(", "), platform);
+			},
+		}
+
+		if !declared.is_empty() && !declared.iter().any(|d| d == platform) {
+			warn!("{} is for {}, which mod.json doesn't declare", name, platform);
+		}
+	}
+
+	for platform in &declared {
+		if !found.contains(&platform.as_str()) {
+			fail!("mod.json declares {}, but there is no binary for it", platform);
+			problems += 1;
+		}
+	}
+
+	if problems > 0 {
+		fatal!("Found {} problems with the binaries in {}", problems, path.display());
+	}
+	if found.is_empty() {
+		warn!("{} has no binaries", path.display());
+	} else {
+		done!("All binaries match their platforms");
+	}
+}
+
+fn fetch_package(config: &Config, source: &str, version: &str, hash: Option<String>, output: Option<PathBuf>) {
+	let (url, hash, name) = if source.starts_with("http://") || source.starts_with("https://") {
+		let name = source
+			.rsplit('/')
+			.next()
+			.filter(|name| name.ends_with(".geode"))
+			.unwrap_or("download.geode")
+			.to_string();
+		(source.to_string(), hash, name)
+	} else {
+		let version_req = VersionReq::parse(&version.replace('v', ""))
+			.unwrap_or_else(|e| fatal!("Invalid version '{}': {}", version, e));
+		if !index::index_mods_dir(config).exists() {
+			fatal!("The index hasn't been downloaded yet, use `geode index update` to get it");
+		}
+		let Some(entry) = index::get_entry(config, &source.to_string(), &version_req) else {
+			fatal!("Unable to find '{}' version '{}' on the index", source, version);
+		};
+		(entry.r#mod.download, hash.or(Some(entry.r#mod.hash)), format!("{}.geode", source))
+	};
+	let output = output.unwrap_or_else(|| PathBuf::from(name));
+
+	info!("Downloading {}", url);
+	let data = reqwest::blocking::get(&url)
+		.and_then(|res| res.error_for_status())
+		.and_then(|res| res.bytes())
+		.unwrap_or_else(|e| fatal!("Unable to download {}: {}", url, e));
+
+	if let Some(hash) = hash {
+		let hash = hash.to_lowercase();
+		let sha3 = hex::encode(Sha3_256::digest(&data));
+		if hash != sha256::digest_bytes(&data) && hash != sha3 {
+			fatal!("Hash of the download doesn't match {}", hash);
+		}
+		done!("Hash matches");
+	}
+
+	fs::write(&output, &data).unwrap_or_else(|e| fatal!("Unable to save {}: {}", output.display(), e));
+	done!("Saved to {}", output.display());
+
+	package_info(&output, false);
+	validate_package(&output);
+}
+
+/// The mod.json in a .geode package, if it can be read
+fn package_mod_json(path: &Path) -> Option<serde_json::Value> {
+	let mut archive = zip::ZipArchive::new(fs::File::open(path).ok()?).ok()?;
+	let mut text = String::new();
+	archive.by_name("mod.json").ok()?.read_to_string(&mut text).ok()?;
+	serde_json::from_str(&text).ok()
+}
+
+/// ID of the mod in a .geode package, if it can be read
+fn package_mod_id(path: &Path) -> Option<String> {
+	package_mod_json(path)?.get("id")?.as_str().map(String::from)
+}
+
 pub fn install(config: &mut Config, pkg_path: &Path) {
+	if !pkg_path.exists() {
+		fatal!("Package path {} does not exist!", pkg_path.display());
+	}
+	let Some(mod_id) = package_mod_id(pkg_path) else {
+		fatal!("{} is not a valid .geode package", pkg_path.display());
+	};
+	let geode = package_mod_json(pkg_path)
+		.and_then(|json| Version::parse(json.get("geode")?.as_str()?.trim_start_matches('v')).ok());
+	if let Some(geode) = geode {
+		crate::sdk::check_profile_sdk(config, &mod_id, &geode);
+	}
+
 	let mod_path = config.get_current_profile().mods_dir();
 
 	if !mod_path.exists() {
 		fs::create_dir_all(&mod_path).expect("Could not setup mod installation");
 	}
-	fs::copy(pkg_path, mod_path.join(pkg_path.file_name().unwrap()))
+	let dest = mod_path.join(pkg_path.file_name().unwrap());
+
+	// Older versions may have been installed under another file name
+	for entry in fs::read_dir(&mod_path).expect("Unable to read mods directory") {
+		let path = entry.unwrap().path();
+		if path != dest
+			&& path.extension().is_some_and(|ext| ext == "geode")
+			&& package_mod_id(&path).as_deref() == Some(mod_id.as_str())
+		{
+			info!("Replacing {}", path.file_name().unwrap().to_string_lossy());
+			fs::remove_file(&path).expect("Unable to remove old version of mod");
+		}
+	}
+
+	fs::copy(pkg_path, &dest)
 		.expect("Could not install mod");
 
 	done!(
@@ -96,15 +593,126 @@ pub fn install(config: &mut Config, pkg_path: &Path) {
 	);
 }
 
-fn zip_folder(path: &Path, output: &Path) {
+pub fn uninstall(config: &mut Config, id: &str, purge: bool) {
+	let profile = config.get_current_profile();
+	let mod_path = profile.mods_dir();
+
+	let mut found = false;
+	if let Ok(dir) = fs::read_dir(&mod_path) {
+		for entry in dir {
+			let path = entry.unwrap().path();
+			if path.extension().is_some_and(|ext| ext == "geode")
+				&& package_mod_id(&path).as_deref() == Some(id)
+			{
+				fs::remove_file(&path).expect("Unable to remove mod");
+				info!("Removed {}", path.file_name().unwrap().to_string_lossy());
+				found = true;
+			}
+		}
+	}
+	if !found {
+		fatal!("{} is not installed in profile {}", id, profile.name);
+	}
+
+	let unzipped = profile.unzipped_dir().join(id);
+	if unzipped.exists() {
+		fs::remove_dir_all(&unzipped).expect("Unable to remove unzipped mod");
+	}
+
+	if purge {
+		match profile.save_dir().map(|dir| dir.join(id)) {
+			Some(save) if save.exists() => {
+				fs::remove_dir_all(&save).expect("Unable to remove save data");
+				info!("Deleted save data at {}", save.display());
+			},
+			Some(_) => info!("{} has no save data", id),
+			None => warn!("Unable to find the save data directory on this platform"),
+		}
+	}
+
+	done!("Uninstalled {}", id);
+}
+
+/// Re-encode every PNG in a directory with the best compression, keeping the
+/// original wherever that doesn't make it smaller
+fn optimize_pngs(dir: &Path) {
+	info!("Compressing PNGs");
+
+	let mut saved = 0;
+	for item in walkdir::WalkDir::new(dir) {
+		let item = item.unwrap();
+		let path = item.path();
+		if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("png")) {
+			continue;
+		}
+
+		let original = fs::metadata(path).unwrap().len();
+		let img = match image::open(path) {
+			Ok(img) => img,
+			Err(e) => {
+				warn!("Unable to read {}: {}", path.display(), e);
+				continue;
+			},
+		};
+
+		let mut buf = Vec::new();
+		PngEncoder::new_with_quality(&mut buf, CompressionType::Best, PngFilterType::Adaptive)
+			.write_image(img.as_bytes(), img.width(), img.height(), img.color())
+			.unwrap_or_else(|e| fatal!("Unable to compress {}: {}", path.display(), e));
+
+		if (buf.len() as u64) < original {
+			saved += original - buf.len() as u64;
+			fs::write(path, buf).unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+		}
+	}
+
+	info!("Saved {} KB", saved / 1024);
+}
+
+/// How hard to compress files in a package
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Compression {
+	/// No compression, fastest for debug builds
+	Store,
+	Fast,
+	/// Smallest packages, for releases
+	Best,
+}
+
+impl Compression {
+	/// The level given on the command line, or else the `package-compression`
+	/// option
+	fn resolve(flag: Option<Compression>, config: &Config) -> Option<Compression> {
+		flag.or_else(|| {
+			config.package_compression.as_ref().map(|value| {
+				Compression::from_str(value, true)
+					.unwrap_or_else(|_| fatal!("Invalid package-compression option '{}'", value))
+			})
+		})
+	}
+}
+
+fn zip_folder(path: &Path, output: &Path, compression: Option<Compression>) {
 	info!("Zipping");
 
-	// Setup zip
+	let (method, level) = match compression {
+		Some(Compression::Store) => (zip::CompressionMethod::Stored, None),
+		Some(Compression::Fast) => (zip::CompressionMethod::Deflated, Some(1)),
+		Some(Compression::Best) => (zip::CompressionMethod::Deflated, Some(9)),
+		None => (zip::CompressionMethod::Deflated, None),
+	};
+
+	// Setup zip. Timestamps and permissions are fixed and files are added in
+	// a stable order so the same inputs always give the same package
 	let mut zip_file = ZipWriter::new(fs::File::create(output).unwrap());
-	let zip_options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+	let zip_options = FileOptions::default()
+		.compression_method(method)
+		.compression_level(level)
+		.last_modified_time(zip::DateTime::default())
+		.unix_permissions(0o644);
 
 	// Iterate files in target path
-	for item in walkdir::WalkDir::new(path) {
+	for item in walkdir::WalkDir::new(path).sort_by_file_name() {
 		let item = item.unwrap();
 
 		// Only look at files
@@ -144,16 +752,65 @@ fn zip_folder(path: &Path, output: &Path) {
 pub fn get_working_dir(id: &String) -> PathBuf {
 	let working_dir = dirs::cache_dir().unwrap().join(format!("geode_pkg_{}", id));
 	fs::remove_dir_all(&working_dir).unwrap_or(());
-	fs::create_dir(&working_dir).unwrap_or(());
+	fs::create_dir_all(&working_dir).unwrap_or(());
 	working_dir
 }
 
+fn get_sprite_bundle(
+	sprite_path: &Path,
+	working_dir: &Path,
+	sprite_output_dir: &Path,
+	cache_bundle: &Mutex<Option<CacheBundle>>,
+	shut_up: bool,
+) -> PathBuf {
+	// Sprite base name
+	let base = sprite_path.file_stem().and_then(|x| x.to_str()).unwrap();
+
+	let outputs = ["-uhd.png", "-hd.png", ".png"]
+		.map(|suffix| sprite_output_dir.join(base.to_string() + suffix));
+	let cache_name = outputs[2]
+		.strip_prefix(working_dir)
+		.unwrap_or(&outputs[2])
+		.to_path_buf();
+
+	// Unchanged sprites can come straight from the last build
+	if let Some(bundle) = cache_bundle.lock().unwrap().as_mut() {
+		if bundle.cache.fetch_sprite(sprite_path) == Some(cache_name.as_path()) {
+			for out in &outputs {
+				let name = out.strip_prefix(working_dir).unwrap_or(out);
+				bundle.extract_cached_into(name.to_str().unwrap(), out);
+			}
+			if !shut_up {
+				done!("Fetched {} from cache", base.bright_yellow());
+			}
+			return cache_name;
+		}
+	}
+
+	let mut sprite = spritesheet::read_to_image(sprite_path);
+
+	// Collect all errors
+	(|| {
+		sprite.save(&outputs[0])?;
+
+		spritesheet::downscale(&mut sprite, 2);
+		sprite.save(&outputs[1])?;
+
+		spritesheet::downscale(&mut sprite, 2);
+		sprite.save(&outputs[2])
+	})()
+	.expect(&format!(
+		"Unable to copy sprite at {}",
+		sprite_path.display()
+	));
+	cache_name
+}
+
 fn create_resources(
 	#[allow(unused)]
 	config: &mut Config,
 	mod_info: &ModFileInfo,
-	#[allow(unused_mut)]
-	mut cache_bundle: &mut Option<CacheBundle>,
+	cache_bundle: &mut Option<CacheBundle>,
 	cache: &mut cache::ResourceCache,
 	working_dir: &Path,
 	output_dir: &PathBuf,
@@ -164,48 +821,74 @@ fn create_resources(
 	fs::create_dir_all(output_dir).expect("Could not create resource directory");
 	fs::create_dir_all(sprite_output_dir).expect("Could not create sprite resource directory");
 
-	// Create spritesheets
-	for sheet in mod_info.resources.spritesheets.values() {
-		let sheet_file = spritesheet::get_spritesheet_bundles(
-			sheet,
-			sprite_output_dir,
-			cache_bundle,
-			mod_info,
-			shut_up,
-		);
-		cache.add_sheet(sheet, sheet_file.cache_name(working_dir));
-	}
+	let resources = &mod_info.resources;
+	let total = resources.spritesheets.len() + resources.fonts.len() + resources.sprites.len();
 
-	// Create fonts
-	for font in mod_info.resources.fonts.values() {
-		let font_file = bmfont::get_font_bundles(font, sprite_output_dir, cache_bundle, mod_info, shut_up);
-		cache.add_font(font, font_file.cache_name(working_dir));
-	}
+	// Jobs only hold the cache while reading from it, everything else runs in parallel
+	let shared_cache = Mutex::new(cache_bundle.take());
+	let finished = AtomicUsize::new(0);
+	let report = |name: &str| {
+		let count = finished.fetch_add(1, Ordering::SeqCst) + 1;
+		if !shut_up {
+			info!("[{}/{}] Processed {}", count, total, name);
+		}
+	};
 
-	if !&mod_info.resources.sprites.is_empty() {
+	if !resources.sprites.is_empty() {
 		info!("Copying sprites");
 	}
-	// Resize sprites
-	for sprite_path in &mod_info.resources.sprites {
-		let mut sprite = spritesheet::read_to_image(sprite_path);
-
-		// Sprite base name
-		let base = sprite_path.file_stem().and_then(|x| x.to_str()).unwrap();
 
-		// Collect all errors
-		(|| {
-			sprite.save(sprite_output_dir.join(base.to_string() + "-uhd.png"))?;
+	let (sheets, (fonts, sprites)) = rayon::join(
+		// Create spritesheets
+		|| resources.spritesheets.par_iter().map(|(name, sheet)| {
+			let sheet_file = spritesheet::get_spritesheet_bundles(
+				sheet,
+				sprite_output_dir,
+				&shared_cache,
+				mod_info,
+				shut_up,
+			);
+			report(name);
+			(sheet, sheet_file.cache_name(working_dir))
+		}).collect::<Vec<_>>(),
+		|| rayon::join(
+			// Create fonts
+			|| resources.fonts.par_iter().map(|(name, font)| {
+				let font_file = bmfont::get_font_bundles(
+					font,
+					sprite_output_dir,
+					&shared_cache,
+					mod_info,
+					shut_up,
+				);
+				report(name);
+				(font, font_file.cache_name(working_dir))
+			}).collect::<Vec<_>>(),
+			// Resize sprites
+			|| resources.sprites.par_iter().map(|sprite_path| {
+				let sprite_file = get_sprite_bundle(
+					sprite_path,
+					working_dir,
+					sprite_output_dir,
+					&shared_cache,
+					shut_up,
+				);
+				report(&sprite_path.file_name().unwrap().to_string_lossy());
+				(sprite_path, sprite_file)
+			}).collect::<Vec<_>>(),
+		),
+	);
 
-			spritesheet::downscale(&mut sprite, 2);
-			sprite.save(sprite_output_dir.join(base.to_string() + "-hd.png"))?;
+	*cache_bundle = shared_cache.into_inner().unwrap();
 
-			spritesheet::downscale(&mut sprite, 2);
-			sprite.save(sprite_output_dir.join(base.to_string() + ".png"))
-		})()
-		.expect(&format!(
-			"Unable to copy sprite at {}",
-			sprite_path.display()
-		));
+	for (sheet, path) in sheets {
+		cache.add_sheet(sheet, path);
+	}
+	for (font, path) in fonts {
+		cache.add_font(font, path);
+	}
+	for (sprite, path) in sprites {
+		cache.add_sprite(sprite, path);
 	}
 
 	if !&mod_info.resources.files.is_empty() {
@@ -227,15 +910,181 @@ fn create_resources(
 	}
 }
 
+/// Words that can't be used as identifiers in C++, including the alternative
+/// operator spellings
+const CPP_KEYWORDS: &[&str] = &[
+	"alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor",
+	"bool", "break", "case", "catch", "char", "char8_t", "char16_t", "char32_t",
+	"class", "compl", "concept", "const", "consteval", "constexpr", "constinit",
+	"const_cast", "continue", "co_await", "co_return", "co_yield", "decltype",
+	"default", "delete", "do", "double", "dynamic_cast", "else", "enum",
+	"explicit", "export", "extern", "false", "float", "for", "friend", "goto",
+	"if", "inline", "int", "long", "mutable", "namespace", "new", "noexcept",
+	"not", "not_eq", "nullptr", "operator", "or", "or_eq", "private",
+	"protected", "public", "register", "reinterpret_cast", "requires", "return",
+	"short", "signed", "sizeof", "static", "static_assert", "static_cast",
+	"struct", "switch", "template", "this", "thread_local", "throw", "true",
+	"try", "typedef", "typeid", "typename", "union", "unsigned", "using",
+	"virtual", "void", "volatile", "wchar_t", "while", "xor", "xor_eq",
+];
+
+/// Turn a resource name into a C++ identifier
+pub fn cpp_identifier(name: &str) -> String {
+	let mut res = name
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+		.collect::<String>();
+	if res.starts_with(|c: char| c.is_ascii_digit()) {
+		res.insert(0, '_');
+	}
+	if CPP_KEYWORDS.contains(&res.as_str()) {
+		res.push('_');
+	}
+	res
+}
+
+/// Quote a string as a C++ string literal
+fn cpp_string(value: &str) -> String {
+	format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Write a header with the full name of every resource, so typos in them
+/// are caught when compiling
+fn write_resource_header(mod_info: &ModFileInfo, path: &Path) {
+	let stem = |path: &Path| path.file_stem().unwrap().to_string_lossy().to_string();
+	let file_name = |path: &Path| path.file_name().unwrap().to_string_lossy().to_string();
+
+	// Sprites and sheet frames are both `mod.id/name.png`
+	let mut sprites = mod_info.resources.sprites.iter().map(|p| stem(p)).collect::<Vec<_>>();
+	for sheet in mod_info.resources.spritesheets.values() {
+		sprites.extend(sheet.files.iter().map(|p| {
+			let name = stem(p);
+			name.strip_suffix("-uhd").or_else(|| name.strip_suffix("-hd")).unwrap_or(&name).to_string()
+		}));
+	}
+
+	let mut groups: BTreeMap<&str, BTreeMap<String, String>> = BTreeMap::new();
+	let mut add = |group: &'static str, ident: String, value: String| {
+		let constants = groups.entry(group).or_default();
+		match constants.get(&ident) {
+			Some(existing) if *existing != value => {
+				warn!("{} and {} would both be called {}, skipping the second", existing, value, ident);
+			},
+			_ => {
+				constants.insert(ident, value);
+			},
+		}
+	};
+	for sprite in sprites {
+		add("sprites", cpp_identifier(&sprite), format!("{}/{}.png", mod_info.id, sprite));
+	}
+	for font in mod_info.resources.fonts.keys() {
+		add("fonts", cpp_identifier(font), format!("{}/{}.fnt", mod_info.id, font));
+	}
+	for file in &mod_info.resources.files {
+		let name = file_name(file);
+		let group = if file_kind(&name) == "Sounds" { "sounds" } else { "files" };
+		add(group, cpp_identifier(&name), name);
+	}
+
+	let mut header = String::from("// Generated by `geode package`, don't edit this file\n#pragma once\n\nnamespace resources {\n");
+	for (group, constants) in &groups {
+		header += &format!("\tnamespace {} {{\n", group);
+		for (ident, value) in constants {
+			header += &format!("\t\tconstexpr auto {} = {};\n", ident, cpp_string(value));
+		}
+		header += "\t}\n";
+	}
+	header += "}\n";
+
+	// Don't touch the file if nothing changed, so it doesn't cause rebuilds
+	if fs::read_to_string(path).is_ok_and(|old| old == header) {
+		return;
+	}
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent).unwrap_or(());
+	}
+	fs::write(path, header).unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+	info!("Wrote resource header to {}", path.display());
+}
+
+/// Largest texture size GPUs can be counted on to load
+const MAX_TEXTURE_SIZE: u32 = 4096;
+
+/// Warn about textures that won't work well in game, returning how many
+/// problems were found
+fn check_textures(mod_info: &ModFileInfo, sprite_output_dir: &Path) -> usize {
+	let mut problems = 0;
+	let mut problem = |msg: String| {
+		warn!("{}", msg);
+		problems += 1;
+	};
+
+	for sprite in &mod_info.resources.sprites {
+		let name = sprite.file_name().unwrap().to_string_lossy();
+		let Ok((width, height)) = image::image_dimensions(sprite) else {
+			continue;
+		};
+		if width > MAX_TEXTURE_SIZE || height > MAX_TEXTURE_SIZE {
+			problem(format!(
+				"Sprite {} is {}x{}, larger than {}px which some GPUs can't load",
+				name, width, height, MAX_TEXTURE_SIZE
+			));
+		}
+		// UHD is scaled down by 4 for SD
+		if width % 4 != 0 || height % 4 != 0 {
+			problem(format!(
+				"Sprite {} is {}x{}, which isn't divisible by 4 and will be blurry in HD and SD",
+				name, width, height
+			));
+		}
+	}
+
+	let generated = mod_info.resources.spritesheets.keys()
+		.map(|name| ("Spritesheet", name))
+		.chain(mod_info.resources.fonts.keys().map(|name| ("Font", name)));
+	for (kind, name) in generated {
+		let Ok((width, height)) = image::image_dimensions(sprite_output_dir.join(name.to_string() + "-uhd.png")) else {
+			continue;
+		};
+		if width > MAX_TEXTURE_SIZE || height > MAX_TEXTURE_SIZE {
+			problem(format!(
+				"{} {} is {}x{} in UHD, larger than {}px which some GPUs can't load; split it up",
+				kind, name, width, height, MAX_TEXTURE_SIZE
+			));
+		}
+		if kind == "Font" && width.max(height) > width.min(height) * 2 {
+			problem(format!(
+				"Font {} is {}x{} in UHD, which is far from square and wastes texture space",
+				name, width, height
+			));
+		}
+	}
+
+	problems
+}
+
 fn create_package_resources_only(
 	config: &mut Config,
 	root_path: &Path,
 	output_dir: &PathBuf,
 	shut_up: bool,
+	header: Option<PathBuf>,
+	strict: bool,
 ) {
 	// Parse mod.json
+	ensure_valid_mod_json(&read_mod_json(root_path).unwrap_or_else(|e| fatal!("{}", e)));
 	let mod_info = parse_mod_info(root_path);
 
+	if let Some(header) = header {
+		write_resource_header(&mod_info, &header);
+	}
+
+	if mod_info.resources == ModResources::default() {
+		done!("mod.json does not define any resources, nothing to do");
+		return;
+	}
+
 	// Setup cache
 	let mut cache_bundle = cache::get_cache_bundle_from_dir(output_dir);
 	let mut new_cache = cache::ResourceCache::new();
@@ -253,43 +1102,371 @@ fn create_package_resources_only(
 
 	new_cache.save(output_dir);
 
+	let problems = check_textures(&mod_info, output_dir);
+	if strict && problems > 0 {
+		fatal!("Found {} problems with textures", problems);
+	}
+
 	done!("Resources created at {}", output_dir.to_str().unwrap());
 }
 
-fn create_package(
-	config: &mut Config,
-	root_path: &Path,
-	binaries: Vec<PathBuf>,
-	mut output: PathBuf,
-	do_install: bool,
-) {
-	// If it's a directory, add file path to it
-	if output.is_dir() {
-		output.push(root_path.file_name().unwrap());
-		output.set_extension("geode");
-		warn!(
-			"Specified output is a directory. Creating package at {}",
-			output.display()
+/// Binaries lying around in the mod's folder, for when none are given
+pub fn find_binaries(root_path: &Path) -> Vec<PathBuf> {
+	let mut res = fs::read_dir(root_path)
+		.map(|dir| {
+			dir.filter_map(|entry| Some(entry.ok()?.path()))
+				.filter(|path| path.is_file())
+				.filter(|path| {
+					path.file_name()
+						.and_then(|name| name.to_str())
+						.is_some_and(|name| binary_platform(name).is_some() || name.ends_with(".lib"))
+				})
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	res.sort();
+	res
+}
+
+/// Largest markdown file the index will show
+const MAX_MARKDOWN_SIZE: u64 = 256 * 1024;
+
+/// Make sure a markdown file from the mod's folder will display properly
+fn check_markdown(path: &Path, mod_info: &ModFileInfo) {
+	let name = path.file_name().unwrap().to_string_lossy();
+
+	let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+	if size > MAX_MARKDOWN_SIZE {
+		fatal!(
+			"{} is too large ({} KiB, the limit is {} KiB)",
+			name, size / 1024, MAX_MARKDOWN_SIZE / 1024
 		);
 	}
 
-	// Ensure at least one binary
-	if binaries.is_empty() {
-		fail!("No binaries added");
-		info!("Help: Add a binary with `--binary <bin_path>`");
-		return;
+	let data = fs::read(path).unwrap_or_else(|e| fatal!("Could not read {}: {}", name, e));
+	let text = String::from_utf8(data)
+		.unwrap_or_else(|e| fatal!("{} is not valid UTF-8: {}", name, e));
+
+	if name == "changelog.md" && !text.contains(&mod_info.version.to_string()) {
+		warn!(
+			"changelog.md doesn't mention the current version ({})",
+			mod_info.version
+		);
 	}
+}
 
-	// Test if possible to create file
-	if !output.exists() || output.is_dir() {
-		fs::write(&output, "").expect("Could not create package");
-		fs::remove_file(&output).unwrap();
+/// Binaries are named after the mod inside packages
+fn packaged_binary_name(binary: &Path, id: &str) -> String {
+	let binary_name = binary.file_name().unwrap().to_str().unwrap().to_string();
+	if let Some(ext) = [".ios.dylib", ".dylib", ".dll", ".lib", ".so"].iter().find(|x| binary_name.contains(**x)) {
+		id.to_string() + ext
+	} else {
+		binary_name
 	}
+}
 
-	// Parse mod.json
-	let mod_file_info = parse_mod_info(root_path);
+/// Symlink a file from the mod's folder, or copy it if links aren't available
+fn link_file(src: &Path, dest: &Path) {
+	let src = fs::canonicalize(src).unwrap_or_else(|e| fatal!("Unable to find {}: {}", src.display(), e));
+	fs::remove_file(dest).unwrap_or(());
 
-	// Setup working directory
+	#[cfg(windows)]
+	let res = std::os::windows::fs::symlink_file(&src, dest);
+	#[cfg(not(windows))]
+	let res = std::os::unix::fs::symlink(&src, dest);
+
+	if let Err(e) = res {
+		warn!("Unable to link {} ({}), copying it instead", src.display(), e);
+		fs::copy(&src, dest).unwrap_or_else(|e| fatal!("Unable to copy {}: {}", src.display(), e));
+	}
+}
+
+/// Set up the mod unpacked in the mods directory, with its files linked to
+/// the mod's folder so edits show up without packaging again
+fn link_package(config: &mut Config, root_path: &Path, mut binaries: Vec<PathBuf>) {
+	ensure_valid_mod_json(&read_mod_json(root_path).unwrap_or_else(|e| fatal!("{}", e)));
+	let mod_file_info = parse_mod_info(root_path);
+
+	if binaries.is_empty() {
+		binaries = find_binaries(root_path);
+	}
+	if binaries.is_empty() {
+		fail!("No binaries found");
+		info!("Help: Add a binary with `--binary <bin_path>`");
+		return;
+	}
+
+	crate::sdk::check_profile_sdk(config, &mod_file_info.id, &mod_file_info.geode);
+
+	let mods_dir = config.get_current_profile().mods_dir();
+	let dest = mods_dir.join(&mod_file_info.id);
+	fs::remove_dir_all(&dest).unwrap_or(());
+	fs::create_dir_all(&dest).expect("Unable to create mod directory");
+
+	// A packaged version of the mod would be loaded instead
+	for entry in fs::read_dir(&mods_dir).expect("Unable to read mods directory") {
+		let path = entry.unwrap().path();
+		if path.extension().is_some_and(|ext| ext == "geode")
+			&& package_mod_id(&path).as_deref() == Some(mod_file_info.id.as_str())
+		{
+			info!("Removing {}", path.file_name().unwrap().to_string_lossy());
+			fs::remove_file(&path).expect("Unable to remove packaged version of mod");
+		}
+	}
+
+	for file in &["mod.json", "logo.png", "about.md", "changelog.md", "support.md"] {
+		let path = root_path.join(file);
+		if path.exists() {
+			link_file(&path, &dest.join(file));
+		}
+	}
+
+	// Sheets, fonts and downscaled sprites have to be generated
+	let resource_dir = dest.join("resources");
+	let sprite_dir = resource_dir.join(&mod_file_info.id);
+	create_resources(
+		config,
+		&mod_file_info,
+		&mut None,
+		&mut cache::ResourceCache::new(),
+		&dest,
+		&resource_dir,
+		&sprite_dir,
+		true,
+	);
+
+	// Everything else can point straight at the source
+	for file in &mod_file_info.resources.files {
+		link_file(file, &resource_dir.join(file.file_name().unwrap()));
+	}
+	for sprite in &mod_file_info.resources.sprites {
+		let base = sprite.file_stem().and_then(|x| x.to_str()).unwrap();
+		link_file(sprite, &sprite_dir.join(base.to_string() + "-uhd.png"));
+	}
+
+	// Binaries are copied, as the game keeps them open
+	for binary in &binaries {
+		fs::copy(binary, dest.join(packaged_binary_name(binary, &mod_file_info.id)))
+			.unwrap_or_else(|e| fatal!("Unable to copy binary at '{}': {}", binary.display(), e));
+	}
+
+	done!("Linked {} into {}", mod_file_info.id, dest.display());
+	info!("Run this again to update binaries, spritesheets, fonts and low resolution sprites");
+}
+
+/// Headers of the mod's API relative to its folder, making sure they don't
+/// include anything that isn't part of the API
+fn collect_api_headers(root_path: &Path, api: &ModApi) -> Vec<PathBuf> {
+	let root = fs::canonicalize(root_path).expect("Unable to find mod folder");
+	let headers = api.include
+		.iter()
+		.filter(|header| header.is_file())
+		.map(|header| fs::canonicalize(header).unwrap())
+		.collect::<Vec<_>>();
+	if headers.is_empty() {
+		fatal!("[mod.json].api.include doesn't match any headers");
+	}
+
+	let relative = |path: &Path| path.strip_prefix(&root).unwrap_or(path).display().to_string();
+	let include = Regex::new(r#"^\s*#\s*include\s*"([^"]+)""#).unwrap();
+	let mut problems = 0;
+	for header in &headers {
+		if !header.starts_with(&root) {
+			fail!("{} is outside of the mod's folder", header.display());
+			problems += 1;
+			continue;
+		}
+
+		let text = fs::read_to_string(header)
+			.unwrap_or_else(|e| fatal!("Unable to read {}: {}", header.display(), e));
+		for captures in text.lines().filter_map(|line| include.captures(line)) {
+			// Includes that aren't next to the header come from include
+			// directories, which we can't know about
+			let Ok(target) = fs::canonicalize(header.parent().unwrap().join(&captures[1])) else {
+				continue;
+			};
+			if !headers.contains(&target) {
+				fail!(
+					"{} includes {}, which isn't part of the API",
+					relative(header), relative(&target)
+				);
+				problems += 1;
+			}
+		}
+	}
+	if problems > 0 {
+		fatal!("Found {} problems with the API headers", problems);
+	}
+
+	headers
+		.into_iter()
+		.map(|header| header.strip_prefix(&root).unwrap().to_path_buf())
+		.collect()
+}
+
+/// Keystream for obfuscating a resource: SHA3-256 of the key, the file's path
+/// in the package and a little-endian u64 block counter, repeated as needed
+fn resource_keystream(key: &[u8; 32], name: &str, len: usize) -> Vec<u8> {
+	let mut res = Vec::with_capacity(len + 32);
+	let mut counter: u64 = 0;
+	while res.len() < len {
+		let mut hasher = Sha3_256::new();
+		hasher.update(key);
+		hasher.update(name.as_bytes());
+		hasher.update(counter.to_le_bytes());
+		res.extend_from_slice(&hasher.finalize());
+		counter += 1;
+	}
+	res.truncate(len);
+	res
+}
+
+fn load_resource_key(path: &Path) -> [u8; 32] {
+	if !path.exists() {
+		let mut key = [0u8; 32];
+		OsRng.fill_bytes(&mut key);
+		fs::write(path, hex::encode(key))
+			.unwrap_or_else(|e| fatal!("Unable to save key to {}: {}", path.display(), e));
+		warn!("Generated a new resource key at {}", path.display());
+		return key;
+	}
+
+	hex::decode(fs::read_to_string(path).expect("Unable to read resource key").trim())
+		.ok()
+		.and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+		.unwrap_or_else(|| fatal!("{} is not a valid resource key", path.display()))
+}
+
+/// XOR every file under `resources/` with its keystream, and note the key in
+/// mod.json as `"obfuscation": { "algorithm": "sha3-xor", "key": "<hex>" }`
+/// for the loader. Undoing it is the same XOR, keyed on the file's path
+/// inside the package with forward slashes
+fn obfuscate_resources(working_dir: &Path, key_path: &Path) {
+	let key = load_resource_key(key_path);
+	info!("Obfuscating resources");
+
+	for item in walkdir::WalkDir::new(working_dir.join("resources")).sort_by_file_name() {
+		let item = item.unwrap();
+		if !item.file_type().is_file() {
+			continue;
+		}
+		let name = item.path().strip_prefix(working_dir).unwrap().to_str().unwrap().replace('\\', "/");
+		let mut data = fs::read(item.path()).unwrap();
+		let keystream = resource_keystream(&key, &name, data.len());
+		for (byte, k) in data.iter_mut().zip(keystream) {
+			*byte ^= k;
+		}
+		fs::write(item.path(), data).unwrap();
+	}
+
+	let mut mod_json = read_mod_json(working_dir).unwrap_or_else(|e| fatal!("{}", e));
+	mod_json["obfuscation"] = serde_json::json!({
+		"algorithm": "sha3-xor",
+		"key": hex::encode(key),
+	});
+	write_mod_json(working_dir, &mod_json).unwrap_or_else(|e| fatal!("{}", e));
+	warn!("Obfuscated resources can only be loaded by a loader that supports them");
+}
+
+/// How `geode package new` should build the package
+#[derive(Clone)]
+pub struct PackageOptions {
+	pub output: Option<PathBuf>,
+	pub install: bool,
+	pub compress: bool,
+	pub strip: bool,
+	pub api: bool,
+	pub compression: Option<Compression>,
+	pub header: Option<PathBuf>,
+	pub obfuscate: Option<PathBuf>,
+	pub bundle: Vec<PathBuf>,
+}
+
+/// Check that a package can be bundled as a dependency of this mod, returning
+/// its ID
+fn check_bundled_dependency(path: &Path, mod_info: &ModFileInfo) -> String {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).unwrap_or_else(|_| fatal!("{} is not a valid .geode package", path.display()));
+	let mod_json = mod_json_from_archive(&mut archive);
+
+	let Some(id) = mod_json.get("id").and_then(|id| id.as_str()) else {
+		fatal!("{} is not a valid .geode package", path.display());
+	};
+	let Some(dep) = mod_info.dependencies.iter().find(|dep| dep.id == id) else {
+		fatal!("{} is not a dependency of {}", id, mod_info.id);
+	};
+
+	let version = mod_json.get("version").and_then(|v| v.as_str()).unwrap_or_default();
+	match Version::parse(version.trim_start_matches('v')) {
+		Ok(v) if dep.version.matches(&v) => {},
+		Ok(_) => fatal!("{} is version {}, but {} needs {}", id, version, mod_info.id, dep.version),
+		Err(_) => fatal!("{} has an invalid version '{}'", id, version),
+	}
+
+	id.to_string()
+}
+
+pub fn create_package(
+	config: &mut Config,
+	root_path: &Path,
+	mut binaries: Vec<PathBuf>,
+	options: PackageOptions,
+) {
+	// Parse mod.json
+	ensure_valid_mod_json(&read_mod_json(root_path).unwrap_or_else(|e| fatal!("{}", e)));
+	let mod_file_info = parse_mod_info(root_path);
+	crate::sdk::check_sdk_version(&mod_file_info.id, &mod_file_info.geode);
+
+	// Check bundled dependencies before doing any work
+	let bundle = options.bundle
+		.iter()
+		.map(|path| (check_bundled_dependency(path, &mod_file_info), path))
+		.collect::<Vec<_>>();
+
+	let mut output = options.output.unwrap_or_else(|| PathBuf::from(format!("{}.geode", mod_file_info.id)));
+
+	// If it's a directory, add file path to it
+	if output.is_dir() {
+		output.push(root_path.file_name().unwrap());
+		output.set_extension("geode");
+		warn!(
+			"Specified output is a directory. Creating package at {}",
+			output.display()
+		);
+	}
+
+	if binaries.is_empty() {
+		binaries = find_binaries(root_path);
+		for binary in &binaries {
+			info!("Found binary {}", binary.display());
+		}
+	}
+
+	if options.api && mod_file_info.api.is_none() {
+		fatal!("API packages need an \"api\" key in mod.json with the headers to include");
+	}
+
+	// Ensure at least one binary, unless it's just headers
+	if binaries.is_empty() && options.api {
+		info!("No binaries found, packaging headers only");
+	} else if binaries.is_empty() {
+		fail!("No binaries added");
+		info!("Help: Add a binary with `--binary <bin_path>`, or use `--api` for header-only mods");
+		return;
+	}
+
+	// Test if possible to create file
+	if !output.exists() || output.is_dir() {
+		fs::write(&output, "").expect("Could not create package");
+		fs::remove_file(&output).unwrap();
+	}
+
+	if let Some(ref header) = options.header {
+		write_resource_header(&mod_file_info, header);
+	}
+
+	// Setup working directory
 	let working_dir = get_working_dir(&mod_file_info.id);
 
 	// Move mod.json
@@ -311,6 +1488,8 @@ fn create_package(
 		false,
 	);
 
+	check_textures(&mod_file_info, &working_dir.join("resources").join(&mod_file_info.id));
+
 	// Custom hardcoded resources
 	for file in &[
 		"logo.png",
@@ -320,6 +1499,9 @@ fn create_package(
 	] {
 		let path = root_path.join(file);
 		if path.exists() {
+			if file.ends_with(".md") {
+				check_markdown(&path, &mod_file_info);
+			}
 			std::fs::copy(path, working_dir.join(file))
 				.expect(&format!("Could not copy {file}"));
 		}
@@ -327,8 +1509,8 @@ fn create_package(
 
 	// Copy headers
 	if let Some(ref api) = mod_file_info.api {
-		for header in &api.include {
-			let out = working_dir.join(header.strip_prefix(&root_path).unwrap_or(header));
+		for header in collect_api_headers(root_path, api) {
+			let out = working_dir.join(&header);
 			out.parent().map(fs::create_dir_all);
 			fs::copy(root_path.join(&header), &out)
 				.expect(&format!("Unable to copy header {} to {}", header.to_string_lossy(), out.display()));
@@ -337,20 +1519,40 @@ fn create_package(
 
 	// Copy binaries
 	for binary in &binaries {
-		let mut binary_name = binary.file_name().unwrap().to_str().unwrap().to_string();
-		if let Some(ext) = [".ios.dylib", ".dylib", ".dll", ".lib", ".so"].iter().find(|x| binary_name.contains(**x)) {
-			binary_name = mod_file_info.id.to_string() + ext;
-		}
-
-		std::fs::copy(binary, working_dir.join(binary_name))
+		std::fs::copy(binary, working_dir.join(packaged_binary_name(binary, &mod_file_info.id)))
 			.expect(&format!("Unable to copy binary at '{}'", binary.display()));
 	}
 
-	new_cache.save(&working_dir);
+	// Dependencies that aren't on the index, for the loader to install too
+	if !bundle.is_empty() {
+		fs::create_dir_all(working_dir.join("deps")).unwrap();
+	}
+	for (id, path) in &bundle {
+		info!("Bundling dependency {}", id);
+		fs::copy(path, working_dir.join("deps").join(format!("{}.geode", id)))
+			.unwrap_or_else(|e| fatal!("Unable to copy {}: {}", path.display(), e));
+	}
 
-	zip_folder(&working_dir, &output);
+	if options.compress || config.compress_pngs {
+		optimize_pngs(&working_dir);
+	}
+
+	// Obfuscated files can't be reused as a cache
+	if let Some(ref key) = options.obfuscate {
+		obfuscate_resources(&working_dir, key);
+	} else {
+		new_cache.save(&working_dir);
+	}
 
-	if do_install {
+	zip_folder(&working_dir, &output, Compression::resolve(options.compression, config));
+
+	if options.strip {
+		strip_package(&output, false);
+	}
+
+	check_size_limit(&output, fs::metadata(&output).map(|m| m.len()).unwrap_or(0));
+
+	if options.install {
 		install(config, &output);
 	}
 }
@@ -366,30 +1568,936 @@ pub fn mod_json_from_archive<R: Seek + Read>(input: &mut zip::ZipArchive<R>) ->
 	serde_json::from_str::<serde_json::Value>(&text).expect("Unable to parse mod.json")
 }
 
+fn package_info(path: &Path, json: bool) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+	let mod_json = mod_json_from_archive(&mut archive);
+
+	if json {
+		println!("{}", serde_json::to_string_pretty(&mod_json).unwrap());
+		return;
+	}
+
+	let field = |key: &str| mod_json.get(key).and_then(|v| v.as_str()).unwrap_or("<none>").to_string();
+	println!("{} {}", field("name").bright_cyan(), field("version").bright_yellow());
+	println!("    ID: {}", field("id"));
+	println!("    Developer: {}", field("developer"));
+	println!("    Geode: {}", field("geode"));
+
+	// dependencies are a list of objects, or an object keyed by id
+	let mut dependencies = Vec::new();
+	match mod_json.get("dependencies") {
+		Some(serde_json::Value::Array(deps)) => {
+			for dep in deps {
+				let get = |key: &str| dep.get(key).and_then(|v| v.as_str()).unwrap_or("?").to_string();
+				dependencies.push((get("id"), get("version"), dep.get("required").and_then(|r| r.as_bool())));
+			}
+		},
+		Some(serde_json::Value::Object(deps)) => {
+			for (id, dep) in deps {
+				let version = dep.as_str()
+					.or_else(|| dep.get("version").and_then(|v| v.as_str()))
+					.unwrap_or("?")
+					.to_string();
+				dependencies.push((id.clone(), version, dep.get("required").and_then(|r| r.as_bool())));
+			}
+		},
+		_ => {},
+	}
+	if dependencies.is_empty() {
+		println!("    Dependencies: none");
+	} else {
+		println!("    Dependencies:");
+		for (id, version, required) in dependencies {
+			let optional = if required == Some(false) { " (optional)" } else { "" };
+			let bundled = if archive.by_name(&format!("deps/{}.geode", id)).is_ok() { " (bundled)" } else { "" };
+			println!("        - {} {}{}{}", id, version, optional, bundled);
+		}
+	}
+
+	let declared = declared_platforms(&mod_json);
+	if declared.is_empty() {
+		println!("    Declared platforms: none");
+	} else {
+		println!("    Declared platforms: {}", declared.join(", "));
+	}
+
+	let binaries = archive
+		.file_names()
+		.filter_map(|name| binary_platform(name).map(|platform| (name.to_string(), platform)))
+		.collect::<Vec<_>>();
+	if binaries.is_empty() {
+		println!("    Binaries: {}", "none".red());
+	} else {
+		println!("    Binaries:");
+		for (name, platform) in binaries {
+			println!("        - {} ({})", name, platform);
+		}
+	}
+}
+
+fn format_size(size: u64) -> String {
+	if size >= 1024 * 1024 {
+		format!("{:.1} MiB", size as f64 / (1024.0 * 1024.0))
+	} else if size >= 1024 {
+		format!("{:.1} KiB", size as f64 / 1024.0)
+	} else {
+		format!("{} B", size)
+	}
+}
+
+/// What kind of file a package member is, for grouping sizes
+fn file_kind(name: &str) -> &'static str {
+	let lower = name.to_lowercase();
+	if binary_platform(&lower).is_some() || lower.ends_with(".lib") {
+		"Binaries"
+	} else if [".png", ".plist", ".fnt"].iter().any(|ext| lower.ends_with(ext)) {
+		"Sprites"
+	} else if [".ogg", ".mp3", ".wav"].iter().any(|ext| lower.ends_with(ext)) {
+		"Sounds"
+	} else {
+		"Other"
+	}
+}
+
+fn list_files(path: &Path) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	let mut files = (0..archive.len())
+		.filter_map(|i| {
+			let file = archive.by_index(i).expect("Unable to read package");
+			file.is_file()
+				.then(|| (file.name().replace('\\', "/"), file.compressed_size(), file.size()))
+		})
+		.collect::<Vec<_>>();
+	files.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+
+	println!("{:>12} {:>12}  Name", "Compressed", "Size");
+	for (name, compressed, size) in &files {
+		println!("{:>12} {:>12}  {}", format_size(*compressed), format_size(*size), name);
+	}
+
+	// Summary by type
+	let mut kinds: BTreeMap<&str, (usize, u64, u64)> = BTreeMap::new();
+	for (name, compressed, size) in &files {
+		let entry = kinds.entry(file_kind(name)).or_default();
+		entry.0 += 1;
+		entry.1 += compressed;
+		entry.2 += size;
+	}
+
+	println!();
+	for (kind, (count, compressed, size)) in &kinds {
+		println!(
+			"{:>12} {:>12}  {} ({} file{})",
+			format_size(*compressed), format_size(*size), kind.bright_cyan(), count,
+			if *count == 1 { "" } else { "s" }
+		);
+	}
+	println!(
+		"{:>12} {:>12}  {}",
+		format_size(files.iter().map(|f| f.1).sum()),
+		format_size(files.iter().map(|f| f.2).sum()),
+		"Total".bright_yellow()
+	);
+}
+
+/// Largest package the index accepts
+const INDEX_SIZE_LIMIT: u64 = 100 * 1024 * 1024;
+
+/// Warn when a package won't fit in the index
+fn check_size_limit(path: &Path, size: u64) {
+	if size > INDEX_SIZE_LIMIT {
+		warn!(
+			"{} is {}, over the index's limit of {}",
+			path.display(), format_size(size), format_size(INDEX_SIZE_LIMIT)
+		);
+	} else if size > INDEX_SIZE_LIMIT / 10 * 9 {
+		warn!(
+			"{} is {}, close to the index's limit of {}",
+			path.display(), format_size(size), format_size(INDEX_SIZE_LIMIT)
+		);
+	}
+}
+
+fn package_size(path: &Path, top: usize) {
+	let size = fs::metadata(path)
+		.unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+		.len();
+	let mut archive = zip::ZipArchive::new(fs::File::open(path).unwrap()).expect("Unable to read package");
+
+	let mut files = (0..archive.len())
+		.filter_map(|i| {
+			let file = archive.by_index(i).expect("Unable to read package");
+			file.is_file().then(|| (file.name().replace('\\', "/"), file.compressed_size(), file.size()))
+		})
+		.collect::<Vec<_>>();
+	files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+	println!(
+		"{}: {} ({} uncompressed, {} files)",
+		path.display(),
+		format_size(size).bright_yellow(),
+		format_size(files.iter().map(|f| f.2).sum()),
+		files.len()
+	);
+
+	if !files.is_empty() {
+		println!("Largest files:");
+		for (name, compressed, _) in files.iter().take(top) {
+			println!(
+				"{:>12} {:>5.1}%  {}",
+				format_size(*compressed),
+				*compressed as f64 * 100.0 / size.max(1) as f64,
+				name
+			);
+		}
+	}
+
+	check_size_limit(path, size);
+	if size <= INDEX_SIZE_LIMIT {
+		done!("Fits in the index's limit of {}", format_size(INDEX_SIZE_LIMIT));
+	}
+}
+
+fn extract_package(path: &Path, dest: Option<PathBuf>, only: Vec<String>) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	let dest = dest.unwrap_or_else(|| path.with_extension(""));
+	let patterns = only
+		.iter()
+		.map(|p| glob::Pattern::new(p).unwrap_or_else(|e| fatal!("Invalid pattern {}: {}", p, e)))
+		.collect::<Vec<_>>();
+
+	let mut count = 0;
+	for i in 0..archive.len() {
+		let mut file = archive.by_index(i).expect("Unable to read package");
+		// packages zipped on Windows use backslashes
+		let name = file.name().replace('\\', "/");
+		if !only.is_empty() {
+			let wanted = only.iter().any(|o| {
+				let o = o.trim_end_matches('/');
+				name == o || name.starts_with(&format!("{o}/"))
+			}) || patterns.iter().any(|p| p.matches(&name));
+			if !wanted {
+				continue;
+			}
+		}
+
+		let relative = PathBuf::from(&name);
+		if !relative.components().all(|c| matches!(c, std::path::Component::Normal(_))) {
+			warn!("Skipping {}: path leaves the destination", name);
+			continue;
+		}
+		let out = dest.join(relative);
+		if file.is_dir() {
+			fs::create_dir_all(&out).expect("Unable to create directory");
+			continue;
+		}
+		if let Some(parent) = out.parent() {
+			fs::create_dir_all(parent).expect("Unable to create directory");
+		}
+
+		let mut data = Vec::new();
+		file.read_to_end(&mut data)
+			.unwrap_or_else(|e| fatal!("Unable to read {} from package: {}", name, e));
+		fs::write(&out, data).unwrap_or_else(|e| fatal!("Unable to write {}: {}", out.display(), e));
+		count += 1;
+	}
+
+	if count == 0 && !only.is_empty() {
+		fail!("No files in the package matched");
+		return;
+	}
+	done!("Extracted {} files to {}", count, dest.display());
+}
+
+const CHECKSUMS_FILE: &str = ".geode_checksums";
+const SIGNATURE_FILE: &str = ".geode_signature";
+
+/// `<sha256>  <name>` for every file in a package, except the checksums and
+/// signature themselves
+fn package_checksums<R: Seek + Read>(archive: &mut zip::ZipArchive<R>) -> BTreeMap<String, String> {
+	let mut res = BTreeMap::new();
+	for i in 0..archive.len() {
+		let mut file = archive.by_index(i).expect("Unable to read package");
+		let name = file.name().to_string();
+		if file.is_dir() || name == CHECKSUMS_FILE || name == SIGNATURE_FILE {
+			continue;
+		}
+		let mut data = Vec::new();
+		file.read_to_end(&mut data)
+			.unwrap_or_else(|e| fatal!("Unable to read {} from package: {}", name, e));
+		res.insert(name, sha256::digest_bytes(&data));
+	}
+	res
+}
+
+fn format_checksums(checksums: &BTreeMap<String, String>) -> String {
+	checksums.iter().map(|(name, hash)| format!("{hash}  {name}\n")).collect()
+}
+
+fn load_signing_key(path: &Path) -> SigningKey {
+	if !path.exists() {
+		let key = SigningKey::generate(&mut OsRng);
+		fs::write(path, hex::encode(key.to_bytes()))
+			.unwrap_or_else(|e| fatal!("Unable to save key to {}: {}", path.display(), e));
+		warn!("Generated a new signing key at {} - keep it safe and private", path.display());
+		return key;
+	}
+
+	let bytes = hex::decode(fs::read_to_string(path).expect("Unable to read signing key").trim())
+		.ok()
+		.and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+		.unwrap_or_else(|| fatal!("{} is not a valid signing key", path.display()));
+	SigningKey::from_bytes(&bytes)
+}
+
+/// Files that only hold debug info
+fn is_debug_file(name: &str) -> bool {
+	name.ends_with(".pdb") || name.ends_with(".debug") || name.contains(".dSYM/")
+}
+
+/// Run `strip` on a binary, returning the stripped file if it worked
+fn strip_binary(tool: &Path, name: &str, data: &[u8]) -> Option<Vec<u8>> {
+	let tmp = std::env::temp_dir().join(format!("geode_strip_{}", name.replace('/', "_")));
+	fs::write(&tmp, data).ok()?;
+	let output = std::process::Command::new(tool).arg("-S").arg(&tmp).output().ok();
+	let res = output
+		.filter(|output| output.status.success())
+		.and_then(|_| fs::read(&tmp).ok());
+	fs::remove_file(&tmp).unwrap_or(());
+	res
+}
+
+fn strip_package(path: &Path, symbols: bool) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	let tool = which::which("llvm-strip").or_else(|_| which::which("strip")).ok();
+	if tool.is_none() {
+		warn!("Neither llvm-strip nor strip was found, only removing debug files");
+	}
+
+	// Same options as zip_folder so the package stays reproducible
+	let options = FileOptions::default()
+		.compression_method(zip::CompressionMethod::Deflated)
+		.last_modified_time(zip::DateTime::default())
+		.unix_permissions(0o644);
+	let tmp = path.with_extension("geode.tmp");
+	let mut out = ZipWriter::new(fs::File::create(&tmp).expect("Unable to create package"));
+	let mut stash: Vec<(String, Vec<u8>)> = Vec::new();
+	let mut stripped = 0;
+	let mut was_signed = false;
+
+	for i in 0..archive.len() {
+		let mut file = archive.by_index(i).expect("Unable to read package");
+		let name = file.name().to_string();
+
+		if name == CHECKSUMS_FILE || name == SIGNATURE_FILE {
+			// These won't match anymore
+			was_signed = true;
+			continue;
+		}
+
+		if is_debug_file(&name) {
+			info!("Removing {}", name);
+			if symbols && !file.is_dir() {
+				let mut data = Vec::new();
+				file.read_to_end(&mut data).expect("Unable to read package");
+				stash.push((name, data));
+			}
+			continue;
+		}
+
+		if let Some(tool) = tool.as_ref().filter(|_| binary_platform(&name).is_some()) {
+			let mut data = Vec::new();
+			file.read_to_end(&mut data).expect("Unable to read package");
+
+			match strip_binary(tool, &name, &data) {
+				Some(res) if res.len() < data.len() => {
+					info!("Stripped {} ({} -> {} bytes)", name, data.len(), res.len());
+					out.start_file(&name, options).unwrap();
+					out.write_all(&res).unwrap();
+					stripped += 1;
+					if symbols {
+						// The unstripped binary is what symbolication needs
+						stash.push((name, data));
+					}
+				}
+				res => {
+					if res.is_none() {
+						warn!("Unable to strip {}, keeping it as is", name);
+					}
+					out.start_file(&name, options).unwrap();
+					out.write_all(&data).unwrap();
+				}
+			}
+			continue;
+		}
+
+		out.raw_copy_file(file).expect("Unable to copy file");
+	}
+	out.finish().expect("Unable to write package");
+	drop(archive);
+	fs::rename(&tmp, path).expect("Unable to replace package");
+
+	if symbols && !stash.is_empty() {
+		let id = package_mod_id(path).unwrap_or_else(|| fatal!("{} is not a valid .geode package", path.display()));
+		let symbols_path = path.with_file_name(format!("{}-symbols.zip", id));
+
+		let mut zip = ZipWriter::new(fs::File::create(&symbols_path).expect("Unable to create symbols archive"));
+		for (name, data) in &stash {
+			zip.start_file(name, options).unwrap();
+			zip.write_all(data).unwrap();
+		}
+		zip.finish().expect("Unable to write symbols archive");
+		done!("Saved symbols to {}", symbols_path.display());
+	}
+
+	if was_signed {
+		warn!("Removed the package's checksums, sign it again with `geode package sign`");
+	}
+	done!("Stripped {} binaries in {}", stripped, path.display());
+}
+
+fn migrate_package(path: &Path, output: Option<PathBuf>) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+	let mut mod_json = mod_json_from_archive(&mut archive);
+	let Some(id) = mod_json.get("id").and_then(|id| id.as_str()).map(String::from) else {
+		fatal!("[mod.json]: Missing key 'id'");
+	};
+
+	let mut changes = Vec::new();
+
+	// Packages zipped on Windows used backslashes, and binaries used to keep
+	// whatever name they were built with
+	let mut renames = BTreeMap::new();
+	for name in archive.file_names() {
+		let fixed = name.replace('\\', "/");
+		let renamed = if binary_platform(&fixed).is_some() && !fixed.contains('/') {
+			packaged_binary_name(Path::new(&fixed), &id)
+		} else {
+			fixed
+		};
+		if renamed != name {
+			changes.push(format!("Renamed {} to {}", name, renamed));
+			renames.insert(name.to_string(), renamed);
+		}
+	}
+
+	// mod.json has to point at the renamed binaries
+	let renamed_stem = |name: &str| renames
+		.iter()
+		.filter(|(_, new)| binary_platform(new).is_some())
+		.find(|(old, _)| old.as_str() == name || Path::new(old).file_stem().and_then(|s| s.to_str()) == Some(name))
+		.map(|(_, new)| if name.contains('.') { new.clone() } else { id.clone() });
+	match mod_json.get_mut("binary") {
+		Some(serde_json::Value::String(binary)) => {
+			if let Some(new) = renamed_stem(binary) {
+				changes.push(format!("Changed binary from {} to {}", binary, new));
+				*binary = new;
+			}
+		},
+		Some(serde_json::Value::Object(binaries)) => {
+			for (platform, binary) in binaries.iter_mut() {
+				if let Some(new) = binary.as_str().and_then(renamed_stem) {
+					changes.push(format!("Changed binary.{} to {}", platform, new));
+					*binary = serde_json::Value::String(new);
+				}
+			}
+		},
+		_ => {},
+	}
+
+	// Dependencies used to be an object keyed by ID
+	if let Some(serde_json::Value::Object(deps)) = mod_json.get("dependencies") {
+		let list = deps
+			.iter()
+			.map(|(id, dep)| {
+				let version = dep.as_str()
+					.or_else(|| dep.get("version").and_then(|v| v.as_str()))
+					.unwrap_or("*");
+				serde_json::json!({
+					"id": id,
+					"version": version,
+					"required": dep.get("required").and_then(|r| r.as_bool()).unwrap_or(true),
+				})
+			})
+			.collect::<Vec<_>>();
+		changes.push("Changed dependencies into a list".to_string());
+		mod_json["dependencies"] = serde_json::Value::Array(list);
+	}
+
+	if changes.is_empty() {
+		done!("{} is already up to date", path.display());
+		return;
+	}
+	for change in &changes {
+		info!("{}", change);
+	}
+
+	let output = output.unwrap_or_else(|| path.to_path_buf());
+	let tmp = output.with_extension("geode.tmp");
+	let mut out = ZipWriter::new(fs::File::create(&tmp).expect("Unable to create package"));
+	let mut was_signed = false;
+	for i in 0..archive.len() {
+		let file = archive.by_index(i).expect("Unable to read package");
+		let name = file.name().to_string();
+		if name == CHECKSUMS_FILE || name == SIGNATURE_FILE {
+			was_signed = true;
+			continue;
+		}
+		if name == "mod.json" {
+			continue;
+		}
+		match renames.get(&name) {
+			Some(new) => out.raw_copy_file_rename(file, new),
+			None => out.raw_copy_file(file),
+		}
+		.expect("Unable to copy file");
+	}
+
+	let options = FileOptions::default()
+		.compression_method(zip::CompressionMethod::Deflated)
+		.last_modified_time(zip::DateTime::default())
+		.unix_permissions(0o644);
+	out.start_file("mod.json", options).unwrap();
+	out.write_all(&format_mod_json(&mod_json).unwrap_or_else(|e| fatal!("{}", e))).unwrap();
+	out.finish().expect("Unable to write package");
+	drop(archive);
+	fs::rename(&tmp, &output).expect("Unable to replace package");
+
+	// Anything that couldn't be fixed automatically
+	for error in validate_mod_json(&mod_json) {
+		warn!("{}", error);
+	}
+	if was_signed {
+		warn!("Removed the package's checksums, sign it again with `geode package sign`");
+	}
+	done!("Migrated {} ({} changes)", output.display(), changes.len());
+}
+
+/// Point mod.json's binary entries named after the old ID at the new one
+pub fn rename_binary_entries(mod_json: &mut serde_json::Value, old: &str, new: &str) {
+	let rename = |binary: &mut serde_json::Value| {
+		if let Some(name) = binary.as_str() {
+			if let Some(rest) = name.strip_prefix(old).filter(|rest| rest.is_empty() || rest.starts_with('.')) {
+				*binary = serde_json::Value::String(new.to_string() + rest);
+			}
+		}
+	};
+	match mod_json.get_mut("binary") {
+		Some(serde_json::Value::Object(binaries)) => binaries.values_mut().for_each(rename),
+		Some(binary) => rename(binary),
+		None => {},
+	}
+}
+
+fn set_mod_id(path: &Path, new_id: &str) {
+	if !is_valid_id(new_id) {
+		fatal!("'{}' is not a valid ID, it should look like `developer.mod-name` in lowercase", new_id);
+	}
+
+	let mut mod_json = read_mod_json(path).unwrap_or_else(|e| fatal!("{}", e));
+	let Some(old_id) = mod_json.get("id").and_then(|id| id.as_str()).map(String::from) else {
+		fatal!("[mod.json]: Missing key 'id'");
+	};
+	if old_id == new_id {
+		done!("{} already has the ID {}", path.display(), new_id);
+		return;
+	}
+
+	mod_json["id"] = serde_json::Value::String(new_id.to_string());
+	rename_binary_entries(&mut mod_json, &old_id, new_id);
+
+	if path.is_dir() {
+		// Format neatly
+		let buf = Vec::new();
+		let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+		let mut ser = serde_json::Serializer::with_formatter(buf, formatter);
+		mod_json.serialize(&mut ser).unwrap();
+		fs::write(path.join("mod.json"), ser.into_inner()).expect("Unable to write mod.json");
+
+		// Sprites are referred to by ID in code, which we can't safely rewrite
+		let sources = walkdir::WalkDir::new(path)
+			.into_iter()
+			.filter_entry(|e| !e.file_name().to_str().is_some_and(|n| n == "build" || n.starts_with('.')))
+			.filter_map(|e| e.ok())
+			.filter(|e| {
+				e.path().extension().and_then(|x| x.to_str()).is_some_and(|x| ["cpp", "hpp", "h", "c"].contains(&x))
+					&& fs::read_to_string(e.path()).is_ok_and(|text| text.contains(&old_id))
+			})
+			.collect::<Vec<_>>();
+		for source in &sources {
+			warn!("{} still mentions {}", source.path().display(), old_id);
+		}
+
+		done!("Changed the ID of {} from {} to {}", path.display(), old_id, new_id);
+		info!("Rebuild the mod so its binaries are named after the new ID");
+		return;
+	}
+
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	// Binaries and the resource folder are named after the ID
+	let old_prefix = format!("resources/{}/", old_id);
+	let rename = |name: &str| -> Option<String> {
+		let name = name.replace('\\', "/");
+		if let Some(rest) = name.strip_prefix(&old_prefix) {
+			Some(format!("resources/{}/{}", new_id, rest))
+		} else if binary_platform(&name).is_some() || name.ends_with(".lib") {
+			name.strip_prefix(&old_id)
+				.filter(|rest| rest.starts_with('.'))
+				.map(|rest| new_id.to_string() + rest)
+		} else {
+			None
+		}
+	};
+
+	let options = FileOptions::default()
+		.compression_method(zip::CompressionMethod::Deflated)
+		.last_modified_time(zip::DateTime::default())
+		.unix_permissions(0o644);
+
+	let tmp = path.with_extension("geode.tmp");
+	let mut out = ZipWriter::new(fs::File::create(&tmp).expect("Unable to create package"));
+	let mut was_signed = false;
+	for i in 0..archive.len() {
+		let mut file = archive.by_index(i).expect("Unable to read package");
+		let name = file.name().to_string();
+		// The cache refers to the old resource folder, and the checksums to
+		// the old files
+		if name == ".geode_cache" || name == "mod.json" {
+			continue;
+		}
+		if name == CHECKSUMS_FILE || name == SIGNATURE_FILE {
+			was_signed = true;
+			continue;
+		}
+		let Some(new) = rename(&name) else {
+			out.raw_copy_file(file).expect("Unable to copy file");
+			continue;
+		};
+		info!("Renamed {} to {}", name, new);
+
+		// Spritesheet frames are prefixed with the ID
+		if new.ends_with(".plist") {
+			let mut text = String::new();
+			file.read_to_string(&mut text).expect("Unable to read package");
+			out.start_file(&new, options).unwrap();
+			out.write_all(text.replace(&format!(">{}/", old_id), &format!(">{}/", new_id)).as_bytes()).unwrap();
+		} else {
+			out.raw_copy_file_rename(file, new).expect("Unable to copy file");
+		}
+	}
+
+	out.start_file("mod.json", options).unwrap();
+	out.write_all(&format_mod_json(&mod_json).unwrap_or_else(|e| fatal!("{}", e))).unwrap();
+	out.finish().expect("Unable to write package");
+	drop(archive);
+
+	// Packages are usually named after the mod too
+	let output = if path.file_stem().is_some_and(|stem| stem == old_id.as_str()) {
+		fs::remove_file(path).expect("Unable to remove old package");
+		path.with_file_name(format!("{}.geode", new_id))
+	} else {
+		path.to_path_buf()
+	};
+	fs::rename(&tmp, &output).expect("Unable to replace package");
+
+	if was_signed {
+		warn!("Removed the package's checksums, sign it again with `geode package sign`");
+	}
+	done!("Changed the ID of {} from {} to {}", output.display(), old_id, new_id);
+}
+
+fn sign_package(path: &Path, key: Option<PathBuf>) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	let manifest = format_checksums(&package_checksums(&mut archive));
+	let signature = key.map(|key| {
+		let key = load_signing_key(&key);
+		let public_key = hex::encode(key.verifying_key().to_bytes());
+		info!("Signing with public key {}", public_key);
+		serde_json::json!({
+			"algorithm": "ed25519",
+			"public-key": public_key,
+			"signature": hex::encode(key.sign(manifest.as_bytes()).to_bytes()),
+		})
+	});
+
+	// zip can't replace files in place, so copy everything else over
+	let tmp = path.with_extension("geode.tmp");
+	let mut out = ZipWriter::new(fs::File::create(&tmp).expect("Unable to create package"));
+	for i in 0..archive.len() {
+		let file = archive.by_index(i).expect("Unable to read package");
+		if file.name() == CHECKSUMS_FILE || file.name() == SIGNATURE_FILE {
+			continue;
+		}
+		out.raw_copy_file(file).expect("Unable to copy file");
+	}
+
+	let options = FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+	out.start_file(CHECKSUMS_FILE, options).unwrap();
+	out.write_all(manifest.as_bytes()).unwrap();
+	if let Some(signature) = &signature {
+		out.start_file(SIGNATURE_FILE, options).unwrap();
+		out.write_all(serde_json::to_string_pretty(signature).unwrap().as_bytes()).unwrap();
+	}
+	out.finish().expect("Unable to write package");
+	drop(archive);
+
+	fs::rename(&tmp, path).expect("Unable to replace package");
+	if signature.is_some() {
+		done!("Signed {}", path.display());
+	} else {
+		done!("Added checksums to {}", path.display());
+	}
+}
+
+fn verify_package_signature(path: &Path, public_key: Option<String>) {
+	let mut archive = zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+
+	let mut manifest = String::new();
+	if archive.by_name(CHECKSUMS_FILE).and_then(|mut f| Ok(f.read_to_string(&mut manifest)?)).is_err() {
+		fatal!("{} has no checksums, sign it with `geode package sign`", path.display());
+	}
+
+	// Checksums
+	let expected = manifest
+		.lines()
+		.filter_map(|line| line.split_once("  "))
+		.map(|(hash, name)| (name.to_string(), hash.to_string()))
+		.collect::<BTreeMap<_, _>>();
+	let actual = package_checksums(&mut archive);
+	let mut ok = true;
+	for (name, hash) in &actual {
+		match expected.get(name) {
+			None => { fail!("{} was added after signing", name); ok = false; },
+			Some(expected) if expected != hash => { fail!("{} was modified", name); ok = false; },
+			_ => {},
+		}
+	}
+	for name in expected.keys() {
+		if !actual.contains_key(name) {
+			fail!("{} was removed", name);
+			ok = false;
+		}
+	}
+	if !ok {
+		fatal!("{} does not match its checksums", path.display());
+	}
+	done!("All {} files match their checksums", actual.len());
+
+	// Signature
+	let mut signature = String::new();
+	if archive.by_name(SIGNATURE_FILE).and_then(|mut f| Ok(f.read_to_string(&mut signature)?)).is_err() {
+		if public_key.is_some() {
+			fatal!("{} is not signed", path.display());
+		}
+		info!("Package is not signed");
+		return;
+	}
+
+	let signature = serde_json::from_str::<serde_json::Value>(&signature)
+		.unwrap_or_else(|e| fatal!("Unable to parse signature: {}", e));
+	let field = |key: &str| {
+		signature.get(key)
+			.and_then(|v| v.as_str())
+			.and_then(|v| hex::decode(v).ok())
+			.unwrap_or_else(|| fatal!("Signature is missing '{}'", key))
+	};
+
+	let signer = field("public-key");
+	if let Some(public_key) = public_key {
+		if hex::decode(public_key.trim()).ok().as_deref() != Some(signer.as_slice()) {
+			fatal!("Package is signed with a different key: {}", hex::encode(&signer));
+		}
+	}
+
+	let verifying_key = <[u8; 32]>::try_from(signer.clone())
+		.ok()
+		.and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+		.unwrap_or_else(|| fatal!("Signature has an invalid public key"));
+	let sig = <[u8; 64]>::try_from(field("signature"))
+		.map(|bytes| Signature::from_bytes(&bytes))
+		.unwrap_or_else(|_| fatal!("Signature is invalid"));
+
+	if verifying_key.verify(manifest.as_bytes(), &sig).is_err() {
+		fatal!("Signature does not match, the package has been tampered with");
+	}
+	done!("Signature is valid, signed by {}", hex::encode(&signer));
+}
+
+/// Sizes and hashes of every file in a package, keyed by name
+fn archive_files(archive: &mut zip::ZipArchive<fs::File>) -> BTreeMap<String, (u64, String)> {
+	(0..archive.len())
+		.map(|i| {
+			let mut file = archive.by_index(i).expect("Unable to read package");
+			let mut data = Vec::new();
+			file.read_to_end(&mut data).expect("Unable to read package");
+			(file.name().to_string(), (data.len() as u64, sha256::digest_bytes(&data)))
+		})
+		.collect()
+}
+
+fn signed_size(old: u64, new: u64) -> String {
+	if new >= old {
+		format!("+{}", new - old)
+	} else {
+		format!("-{}", old - new)
+	}
+}
+
+/// Print the differences between two packages: mod.json, files and sizes
+pub fn diff_packages(old_path: &Path, new_path: &Path) {
+	let open = |path: &Path| zip::ZipArchive::new(
+		fs::File::open(path).unwrap_or_else(|e| fatal!("Unable to open {}: {}", path.display(), e))
+	).expect("Unable to read package");
+	let mut old_archive = open(old_path);
+	let mut new_archive = open(new_path);
+
+	let old_json = mod_json_from_archive(&mut old_archive);
+	let new_json = mod_json_from_archive(&mut new_archive);
+
+	// Version
+	let version = |json: &serde_json::Value| {
+		json.get("version").and_then(|v| v.as_str()).unwrap_or("<none>").to_string()
+	};
+	let (old_version, new_version) = (version(&old_json), version(&new_json));
+	if old_version == new_version {
+		println!("Version: {} (unchanged)", old_version);
+	} else {
+		println!("Version: {} -> {}", old_version, new_version.bright_green());
+	}
+
+	// Size
+	let old_size = fs::metadata(old_path).unwrap().len();
+	let new_size = fs::metadata(new_path).unwrap().len();
+	println!(
+		"Size: {} -> {} bytes ({})",
+		old_size, new_size, signed_size(old_size, new_size)
+	);
+
+	// Files
+	let old_files = archive_files(&mut old_archive);
+	let new_files = archive_files(&mut new_archive);
+	let mut changes = BTreeMap::new();
+	for (name, info) in &new_files {
+		match old_files.get(name) {
+			None => { changes.insert(name.clone(), '+'); },
+			Some(old) if old.1 != info.1 => { changes.insert(name.clone(), '~'); },
+			_ => {},
+		}
+	}
+	for name in old_files.keys() {
+		if !new_files.contains_key(name) {
+			changes.insert(name.clone(), '-');
+		}
+	}
+	if changes.is_empty() {
+		println!("Files: unchanged");
+	} else {
+		println!("Files:");
+		for (name, mark) in &changes {
+			let mark = match mark {
+				'+' => "+".green(),
+				'-' => "-".red(),
+				_ => "~".yellow(),
+			};
+			let sizes = match (old_files.get(name), new_files.get(name)) {
+				(Some(old), Some(new)) => format!("{} -> {} bytes, {}", old.0, new.0, signed_size(old.0, new.0)),
+				(None, Some(new)) => format!("{} bytes", new.0),
+				(Some(old), None) => format!("{} bytes", old.0),
+				(None, None) => unreachable!(),
+			};
+			println!("    {} {} ({})", mark, name, sizes);
+		}
+	}
+
+	// mod.json
+	let empty = serde_json::Map::new();
+	let old_obj = old_json.as_object().unwrap_or(&empty);
+	let new_obj = new_json.as_object().unwrap_or(&empty);
+	let mut keys = old_obj.keys().chain(new_obj.keys()).collect::<Vec<_>>();
+	keys.sort();
+	keys.dedup();
+
+	let json_changes = keys
+		.into_iter()
+		.filter(|key| old_obj.get(*key) != new_obj.get(*key))
+		.collect::<Vec<_>>();
+	if json_changes.is_empty() {
+		println!("mod.json: unchanged");
+	} else {
+		println!("mod.json:");
+		for key in json_changes {
+			match (old_obj.get(key), new_obj.get(key)) {
+				(Some(old), Some(new)) => println!("    {} {}: {} -> {}", "~".yellow(), key, old, new),
+				(None, Some(new)) => println!("    {} {}: {}", "+".green(), key, new),
+				(Some(old), None) => println!("    {} {}: {}", "-".red(), key, old),
+				(None, None) => unreachable!(),
+			}
+		}
+	}
+}
+
 fn merge_packages(inputs: Vec<PathBuf>) {
 	let mut archives: Vec<_> = inputs.iter().map(|x| {
 		zip::ZipArchive::new(fs::File::options().read(true).write(true).open(x).unwrap()).expect("Unable to unzip")
 	}).collect();
 
 	// Sanity check
-	let mut mod_ids: Vec<_> = archives.iter_mut().map(|x|
-		mod_json_from_archive(x)
-			.get("id")
-			.expect("[mod.json]: Missing key 'id'")
-			.as_str()
-			.expect("[mod.json].id: Expected string")
-			.to_string()
-	).collect();
-
-	let mod_id = mod_ids.remove(0);
-
-	// They have to be the same mod
-	mod_ids.iter().for_each(|x| {
-		if *x != mod_id {
-			fatal!("Cannot merge packages with different mod id: {} and {}", x, mod_id);
+	let mut mod_jsons: Vec<_> = archives.iter_mut().map(mod_json_from_archive).collect();
+	let mod_id_of = |json: &serde_json::Value| json
+		.get("id")
+		.expect("[mod.json]: Missing key 'id'")
+		.as_str()
+		.expect("[mod.json].id: Expected string")
+		.to_string();
+
+	let first_json = mod_jsons.remove(0);
+	let mod_id = mod_id_of(&first_json);
+
+	// They have to be the same mod, built from the same mod.json
+	for (json, input) in mod_jsons.iter().zip(&inputs[1..]) {
+		let id = mod_id_of(json);
+		if id != mod_id {
+			fatal!("Cannot merge packages with different mod id: {} and {}", id, mod_id);
 		}
-	});
+		if *json != first_json {
+			let (Some(a), Some(b)) = (first_json.as_object(), json.as_object()) else {
+				fatal!("mod.json of {} is not an object", input.display());
+			};
+			let mut keys = a.keys().chain(b.keys()).filter(|k| a.get(*k) != b.get(*k)).collect::<Vec<_>>();
+			keys.sort();
+			keys.dedup();
+			fail!(
+				"mod.json of {} differs from {} in: {}",
+				input.display(),
+				inputs[0].display(),
+				keys.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", ")
+			);
+			fatal!("Packages must be built from the same mod.json to be merged");
+		}
+	}
 
+	let mut existing: Vec<String> = archives[0].file_names().map(|x| x.to_string()).collect();
 	let mut out_archive = ZipWriter::new_append(archives.remove(0).into_inner()).expect("Unable to create zip writer");
 
 	for archive in &mut archives {
@@ -399,8 +2507,13 @@ fn merge_packages(inputs: Vec<PathBuf>) {
 		let files: Vec<_> = archive.file_names().map(|x| x.to_string()).collect();
 
 		for file in files {
-			if potential_names.iter().filter(|x| file.ends_with(*x)).next().is_some() {
+			if potential_names.iter().any(|x| file.ends_with(*x)) {
+				if existing.contains(&file) {
+					warn!("Skipping {}, the package already has it", file);
+					continue;
+				}
 				println!("{}", file);
+				existing.push(file.clone());
 
 				out_archive.raw_copy_file(
 					archive.by_name(&file).expect("Unable to fetch file")
@@ -415,14 +2528,75 @@ fn merge_packages(inputs: Vec<PathBuf>) {
 
 pub fn subcommand(config: &mut Config, cmd: Package) {
 	match cmd {
-		Package::Install { path } => install(config, &path),
+		Package::Install { path, all } => match path {
+			Some(path) if !workspace::is_member(&path) => install(config, &path),
+			member => workspace::install(config, member.map(|p| p.to_string_lossy().to_string()), all),
+		},
+
+		Package::Uninstall { id, purge } => uninstall(config, &id, purge),
 
 		Package::New {
 			root_path,
+			all,
 			binary: binaries,
 			output,
 			install,
-		} => create_package(config, &root_path, binaries, output, install),
+			compress,
+			strip,
+			api,
+			compression,
+			header,
+			obfuscate,
+			bundle,
+		} => {
+			let options = PackageOptions {
+				output,
+				install,
+				compress,
+				strip,
+				api,
+				compression,
+				header,
+				obfuscate,
+				bundle,
+			};
+			match root_path {
+				Some(root_path) if !workspace::is_member(&root_path) => {
+					create_package(config, &root_path, binaries, options)
+				}
+				member => workspace::package(
+					config, member.map(|p| p.to_string_lossy().to_string()), all, binaries, options
+				),
+			}
+		}
+
+		Package::Link { root_path, binary } => link_package(config, &root_path, binary),
+
+		Package::Info { path, json } => package_info(&path, json),
+
+		Package::Fetch { source, version, hash, output } => fetch_package(config, &source, &version, hash, output),
+
+		Package::Validate { path } => validate_package(&path),
+
+		Package::Size { path, top } => package_size(&path, top),
+
+		Package::Verify { path } => verify_binaries(&path),
+
+		Package::ListFiles { path } => list_files(&path),
+
+		Package::Extract { path, dest, only } => extract_package(&path, dest, only),
+
+		Package::Strip { path, symbols } => strip_package(&path, symbols),
+
+		Package::Migrate { path, output } => migrate_package(&path, output),
+
+		Package::SetId { path, id } => set_mod_id(&path, &id),
+
+		Package::Sign { path, key } => sign_package(&path, key),
+
+		Package::VerifySignature { path, public_key } => verify_package_signature(&path, public_key),
+
+		Package::Diff { old, new } => diff_packages(&old, &new),
 
 		Package::Merge { packages } => {
 			if packages.len() < 2 {
@@ -442,6 +2616,8 @@ pub fn subcommand(config: &mut Config, cmd: Package) {
 			root_path,
 			output,
 			shut_up,
-		} => create_package_resources_only(config, &root_path, &output, shut_up),
+			header,
+			strict,
+		} => create_package_resources_only(config, &root_path, &output, shut_up, header, strict),
 	}
 }
@@ -1,5 +1,5 @@
 use crate::config::{Config, Profile as CfgProfile};
-use crate::{done, fail};
+use crate::{done, fail, sdk};
 use clap::Subcommand;
 use colored::Colorize;
 use std::cell::RefCell;
@@ -32,6 +32,11 @@ pub enum Profile {
 		/// New profile name
 		#[clap(short, long)]
 		name: String,
+
+		/// Installed SDK version the profile's loader comes from, if it isn't
+		/// the main SDK's
+		#[clap(long)]
+		sdk: Option<String>,
 	},
 
 	/// Remove profile
@@ -48,6 +53,18 @@ pub enum Profile {
 		/// New name
 		new: String,
 	},
+
+	/// Set which installed SDK version a profile's loader comes from, like a
+	/// beta, so installing to it and checking the loader use that version
+	SetSdk {
+		/// SDK version, installed with `geode sdk install-version` or as the
+		/// main SDK. Leave out to go back to the main SDK
+		version: Option<String>,
+
+		/// Profile to set it for, the current one if not given
+		#[clap(long)]
+		profile: Option<String>,
+	},
 }
 
 fn is_valid_geode_dir(_dir: &Path) -> bool {
@@ -55,6 +72,20 @@ fn is_valid_geode_dir(_dir: &Path) -> bool {
 	true
 }
 
+/// Check an SDK version is installed, returning it in the form profiles
+/// store it
+fn installed_sdk_version(version: &str) -> Option<String> {
+	let version = sdk::parse_sdk_version(version);
+	if sdk::find_installed_sdk(&version).is_none() {
+		fail!(
+			"SDK v{} isn't installed, install it with `geode sdk install-version {}`",
+			version, version
+		);
+		return None;
+	}
+	Some(version.to_string())
+}
+
 pub fn subcommand(config: &mut Config, cmd: Profile) {
 	match cmd {
 		Profile::List => {
@@ -68,11 +99,16 @@ pub fn subcommand(config: &mut Config, cmd: Profile) {
 					""
 				};
 
+				let sdk = profile.borrow().sdk_version.as_ref()
+					.map(|v| format!(", sdk = v{}", v.bright_green()))
+					.unwrap_or_default();
+
 				println!(
-					"{}{} [ path = {} ]",
+					"{}{} [ path = {}{} ]",
 					indicator.bright_cyan(),
 					name.bright_cyan(),
-					path.to_string_lossy().bright_green()
+					path.to_string_lossy().bright_green(),
+					sdk
 				);
 			}
 		}
@@ -88,16 +124,21 @@ pub fn subcommand(config: &mut Config, cmd: Profile) {
 			}
 		}
 
-		Profile::Add { name, location } => {
+		Profile::Add { name, location, sdk } => {
 			if config.get_profile(&Some(name.to_owned())).is_some() {
 				fail!("A profile named '{}' already exists", name);
 			} else if !is_valid_geode_dir(&location) {
 				fail!("The specified path does not point to a valid Geode installation");
 			} else {
+				let sdk_version = match sdk.as_deref().map(installed_sdk_version) {
+					Some(None) => return,
+					Some(version) => version,
+					None => None,
+				};
 				done!("A new profile named '{}' has been created", &name);
-				config
-					.profiles
-					.push(RefCell::new(CfgProfile::new(name, location)));
+				let mut profile = CfgProfile::new(name, location);
+				profile.sdk_version = sdk_version;
+				config.profiles.push(RefCell::new(profile));
 			}
 		}
 
@@ -113,5 +154,27 @@ pub fn subcommand(config: &mut Config, cmd: Profile) {
 		Profile::Rename { old, new } => {
 			config.rename_profile(&old, new);
 		}
+
+		Profile::SetSdk { version, profile } => {
+			let name = profile.or_else(|| config.current_profile.clone());
+			let Some(profile) = config.get_profile(&name) else {
+				fail!("Profile '{}' does not exist", name.unwrap_or_default());
+				return;
+			};
+			let mut profile = profile.borrow_mut();
+			match version {
+				Some(version) => {
+					let Some(version) = installed_sdk_version(&version) else {
+						return;
+					};
+					done!("Profile '{}' now uses SDK v{}", profile.name, version);
+					profile.sdk_version = Some(version);
+				}
+				None => {
+					done!("Profile '{}' now uses the main SDK", profile.name);
+					profile.sdk_version = None;
+				}
+			}
+		}
 	}
 }
@@ -1,9 +1,12 @@
 
-use std::{fs, path::{PathBuf, Path}, collections::HashMap};
+use std::{fs, io::{Read, Seek}, path::{PathBuf, Path}, collections::HashMap};
 use clap::Subcommand;
 use semver::{Version, VersionReq};
-use crate::{util::{config::Config, mod_file::{parse_mod_info, ModFileInfo, Dependency, try_parse_mod_info}}, package::get_working_dir, done, warn, info, index::{update_index, index_mods_dir, install_mod}, fail, file::read_dir_recursive, fatal, template, indexer};
+use crate::{util::{config::{geode_root, Config}, logging::ask_value, mod_file::{is_valid_id, parse_mod_info, read_mod_json, write_mod_json, ModFileInfo, Dependency, try_parse_mod_info}}, package::{cpp_identifier, get_working_dir, rename_binary_entries}, done, warn, info, index::{get_entry, update_index, index_mods_dir}, fail, file::read_dir_recursive, fatal, template, indexer, ide, i18n, workspace};
 use edit_distance::edit_distance;
+use regex::Regex;
+use serde_json::json;
+use sha3::{Digest, Sha3_256};
 
 #[derive(Subcommand, Debug)]
 #[clap(rename_all = "kebab-case")]
@@ -11,13 +14,104 @@ pub enum Project {
 	/// Initialize a new Geode project (same as `geode new`)
     New {
 		/// The target directory to create the project in
-		path: Option<PathBuf>
+		path: Option<PathBuf>,
+
+		/// Template to use instead of the example mod: `minimal`, `full`,
+		/// `library`, `ui`, or a git URL or path
+		#[clap(long)]
+		template: Option<String>,
+
+		/// Don't download anything: the minimal template is used instead of
+		/// cloning the example mod, and only built-in or local templates
+		/// can be given
+		#[clap(long)]
+		offline: bool,
+
+		/// SPDX identifier of the mod's license, like MIT or GPL-3.0. Asked
+		/// for if not given
+		#[clap(long)]
+		license: Option<String>,
+
+		/// Don't create a git repository with the project in a first commit
+		#[clap(long)]
+		no_git: bool,
+
+		/// Run the commands the template lists under `post-generate` in its
+		/// geode-template.json. Only use with templates you trust
+		#[clap(long)]
+		allow_scripts: bool,
     },
 
+	/// Update this project's CMakeLists and other boilerplate to the latest
+	/// version of the template it was made from
+	Update {
+		/// Name, git URL or path of the template, if the project wasn't made
+		/// from the example mod
+		#[clap(long)]
+		template: Option<String>,
+
+		/// Don't download the template: the minimal built-in one is used
+		/// for projects made from the example mod, and only built-in or
+		/// local templates can be given
+		#[clap(long)]
+		offline: bool,
+	},
+
+	/// Write editor configuration for this project
+	Ide {
+		/// Which editor to set up
+		#[clap(value_enum)]
+		ide: ide::Ide,
+
+		/// The project's build directory, relative to the project
+		#[clap(long, default_value = "build")]
+		build_dir: String,
+	},
+
+	/// Change this project's ID and/or name in mod.json, CMake, resources and
+	/// sources. Asks for both if neither is given
+	Rename {
+		/// New ID of the mod
+		#[clap(long)]
+		id: Option<String>,
+
+		/// New name of the mod
+		#[clap(long)]
+		name: Option<String>,
+	},
+
+	/// Generate a C++ header with a typed getter for every setting in
+	/// mod.json
+	CodegenSettings {
+		/// Where to write the header
+		#[clap(short, long, default_value = "src/Settings.hpp")]
+		output: PathBuf,
+	},
+
+	/// Create a source file hooking a game class with `$modify` and add it to
+	/// CMakeLists.txt
+	AddHook {
+		/// Name of the class to hook, like MenuLayer
+		class: String,
+
+		/// Signatures of the functions to hook, like "bool init()" or
+		/// "void onPlay(CCObject* sender)"
+		#[clap(short, long, num_args(1..))]
+		method: Vec<String>,
+	},
+
+	/// Manage translations of this project's strings
+	I18n {
+		#[clap(subcommand)]
+		commands: i18n::I18n,
+	},
+
     /// Clear this project's cached resource files
     ClearCache,
 
-	/// Check & install the dependencies for this project 
+	/// Check & install the dependencies for this project. Packages from the
+	/// index are downloaded into a cache shared between projects, and what
+	/// each dependency resolved to is written to geode-deps/geode-deps.json
 	Check {
 		/// Where to install the dependencies; usually the project's build 
         /// directory. A directory called geode-deps will be created inside 
@@ -33,6 +127,38 @@ pub enum Project {
 		externals: Vec<String>,
 	},
 
+	/// Add a mod from the index as a dependency of this project and install
+	/// it for building
+	AddDependency {
+		/// ID of the mod to depend on
+		id: String,
+
+		/// Version requirement of the dependency. Defaults to at least the
+		/// newest version on the index
+		#[clap(long)]
+		version: Option<String>,
+
+		/// Mark the dependency as optional
+		#[clap(long)]
+		optional: bool,
+
+		/// Where to install the dependency, like in `geode project check`. If
+		/// not specified, "build" is assumed
+		#[clap(long)]
+		install_dir: Option<PathBuf>,
+	},
+
+	/// Remove a dependency from this project along with its installed files
+	RemoveDependency {
+		/// ID of the dependency to remove
+		id: String,
+
+		/// Where the dependency was installed, like in `geode project check`.
+		/// If not specified, "build" is assumed
+		#[clap(long)]
+		install_dir: Option<PathBuf>,
+	},
+
     /// Publish this project on the Geode mods index
     Publish {
         /// Path to the project's built .geode file. If you are using Geode 
@@ -189,16 +315,80 @@ fn find_dependency(
 	Ok(found)
 }
 
+/// Where packages of dependencies downloaded from the index are kept, shared
+/// between all projects
+pub fn dependency_cache_dir() -> PathBuf {
+	geode_root().join("dependency-cache")
+}
+
+/// Get a dependency's package from the index, reusing a previous download
+/// if its hash still matches
+fn fetch_dependency(config: &Config, id: &str, version: &Version) -> PathBuf {
+	let entry = get_entry(
+		config, &id.to_string(), &VersionReq::parse(&format!("={}", version)).unwrap()
+	).unwrap_or_else(|| fatal!("Unable to find '{}' version '{}' on the index", id, version));
+	let hash = entry.r#mod.hash.to_lowercase();
+
+	let path = dependency_cache_dir().join(format!("{}-{}.geode", id, version));
+	if fs::read(&path).is_ok_and(|data| hex::encode(Sha3_256::digest(data)) == hash) {
+		info!("Using cached package for '{}' version '{}'", id, version);
+		return path;
+	}
+
+	info!("Downloading '{}' version '{}' into the dependency cache", id, version);
+	let data = reqwest::blocking::get(&entry.r#mod.download)
+		.and_then(|res| res.error_for_status())
+		.and_then(|res| res.bytes())
+		.unwrap_or_else(|e| fatal!("Unable to download '{}': {}", id, e));
+	if hex::encode(Sha3_256::digest(&data)) != hash {
+		fatal!("Downloaded package for '{}' doesn't match the index's hash", id);
+	}
+
+	fs::create_dir_all(dependency_cache_dir()).expect("Unable to create dependency cache");
+	fs::write(&path, data).expect("Unable to save dependency to cache");
+	path
+}
+
+/// List what the dependencies resolved to in geode-deps.json, for the build
+/// and for CI to check against
+fn write_resolved_dependencies(dep_dir: &Path, resolved: Vec<serde_json::Value>) {
+	fs::create_dir_all(dep_dir).expect("Unable to create dependency directory");
+	fs::write(
+		dep_dir.join("geode-deps.json"),
+		serde_json::to_string_pretty(&json!({ "dependencies": resolved })).unwrap()
+	).expect("Unable to save resolved dependencies");
+}
+
+/// Put a dependency's package where CMake looks for it
+fn extract_dependency<R: Read + Seek>(geode: R, dep_dir: &Path, id: &str, required: bool) {
+	// unzip the whole .geode package because there's only like a few 
+	// extra files there aside from the lib, headers, and resources
+	zip::ZipArchive::new(geode)
+		.expect("Unable to unzip")
+		.extract(dep_dir.join(id))
+		.expect("Unable to extract geode package");
+
+	// add a note saying if the dependencey is required or not (for cmake to 
+	// know if to link or not)
+	fs::write(
+		dep_dir.join(id).join("geode-dep-options.json"),
+		format!(r#"{{ "required": {} }}"#, if required { "true" } else { "false" })
+	).expect("Unable to save dep options");
+}
+
 pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, externals: Vec<String>) {
 	let mod_info = parse_mod_info(&input);
 
+	let dep_dir = output.join("geode-deps");
+
 	// If no dependencies, skippy wippy
 	if mod_info.dependencies.is_empty() {
+		write_resolved_dependencies(&dep_dir, Vec::new());
 		return;
 	}
 
 	// Parse externals
-	let externals = externals
+	let mut externals = externals
 		.into_iter()
 		.map(|ext|
 			// If the external is provided as name:version get those, otherwise 
@@ -216,6 +406,11 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 			}
 		)
 		.collect::<HashMap<_, _>>();
+
+	// Other mods in the same workspace are built alongside this one
+	for (id, version) in workspace::sibling_versions(&input) {
+		externals.entry(id).or_insert(Some(version));
+	}
 	
 	let mut errors = false;
 
@@ -225,8 +420,8 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 		update_index(config);
 	}
 
-	let dep_dir = output.join("geode-deps");
 	fs::create_dir_all(&dep_dir).expect("Unable to create dependency directory");
+	let mut resolved = Vec::new();
 
 	// check all dependencies
 	for dep in mod_info.dependencies {
@@ -237,6 +432,12 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 				// is it valid?
 				if dep.version.matches(version) {
 					info!("Dependency '{}' found as external", dep.id);
+					resolved.push(json!({
+						"id": dep.id,
+						"version": version.to_string(),
+						"required": dep.required,
+						"external": true,
+					}));
 				}
 				// external dependency version must match regardless of whether 
 				// it's optional or not as most external dependencies are other 
@@ -260,6 +461,11 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 					"Dependency '{}' marked as external with no version specified",
 					dep.id
 				);
+				resolved.push(json!({
+					"id": dep.id,
+					"required": dep.required,
+					"external": true,
+				}));
 			}
 			continue;
 		}
@@ -350,12 +556,12 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 		}
 
 		let path_to_dep_geode;
-		let _geode_info;
+		let geode_info;
 		match (found_in_installed, found_in_index) {
 			(Found::Some(inst_path, inst_info), Found::Some(_, _)) => {
 				info!("Dependency '{}' found", dep.id);
 				path_to_dep_geode = inst_path;
-				_geode_info = inst_info;
+				geode_info = inst_info;
 			}
 
 			(Found::Some(inst_path, inst_info), _) => {
@@ -373,7 +579,7 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 					dep.id, dep.version
 				);
 				path_to_dep_geode = inst_path;
-				_geode_info = inst_info;
+				geode_info = inst_info;
 			}
 
 			(Found::Wrong(version), Found::Some(_, indx_info)) => {
@@ -390,27 +596,21 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 					continue;
 				}
 				info!(
-					"Dependency '{}' found on the index, installing \
+					"Dependency '{}' found on the index, using it \
 					(update '{}' => '{}')",
 					dep.id, version, indx_info.version
 				);
-				path_to_dep_geode = install_mod(
-					config, &indx_info.id,
-					&VersionReq::parse(&format!("=={}", indx_info.version.to_string())).unwrap()
-				);
-				_geode_info = indx_info;
+				path_to_dep_geode = fetch_dependency(config, &indx_info.id, &indx_info.version);
+				geode_info = indx_info;
 			}
 
 			(_, Found::Some(_, indx_info)) => {
 				info!(
-					"Dependency '{}' found on the index, installing (version '{}')",
+					"Dependency '{}' found on the index, using version '{}'",
 					dep.id, indx_info.version
 				);
-				path_to_dep_geode = install_mod(
-					config, &indx_info.id,
-					&VersionReq::parse(&format!("={}", indx_info.version.to_string())).unwrap()
-				);
-				_geode_info = indx_info;
+				path_to_dep_geode = fetch_dependency(config, &indx_info.id, &indx_info.version);
+				geode_info = indx_info;
 			}
 
 			_ => unreachable!()
@@ -431,30 +631,420 @@ pub fn check_dependencies(config: &Config, input: PathBuf, output: PathBuf, exte
 		// 	}
 		// }
 
-		// unzip the whole .geode package because there's only like a few 
-		// extra files there aside from the lib, headers, and resources
-		zip::ZipArchive::new(fs::File::open(path_to_dep_geode).unwrap())
-			.expect("Unable to unzip")
-			.extract(dep_dir.join(&dep.id))
-			.expect("Unable to extract geode package");
-		
-		// add a note saying if the dependencey is required or not (for cmake to 
-		// know if to link or not)
-		fs::write(
-			dep_dir.join(dep.id).join("geode-dep-options.json"),
-			format!(r#"{{ "required": {} }}"#, if dep.required { "true" } else { "false" })
-		).expect("Unable to save dep options");
+		extract_dependency(
+			fs::File::open(&path_to_dep_geode).unwrap(), &dep_dir, &dep.id, dep.required
+		);
+		resolved.push(json!({
+			"id": dep.id,
+			"version": geode_info.version.to_string(),
+			"required": dep.required,
+			"path": dep_dir.join(&dep.id),
+			"package": path_to_dep_geode,
+		}));
 	}
 
 	if errors {
 		fatal!("Some dependencies were unresolved");
 	}
     else {
+		write_resolved_dependencies(&dep_dir, resolved);
         done!("All dependencies resolved");
     }
 }
 
-pub fn publish_project(_config: &Config, dir: &Path, package_path: Option<PathBuf>) {
+fn add_dependency(
+	config: &Config,
+	dir: &Path,
+	id: String,
+	version: Option<String>,
+	optional: bool,
+	install_dir: PathBuf
+) {
+	let mut mod_json = read_mod_json(dir).unwrap_or_else(|e| fatal!("{}", e));
+	let deps = mod_json
+		.as_object_mut()
+		.unwrap_or_else(|| fatal!("mod.json is not an object"))
+		.entry("dependencies")
+		.or_insert_with(|| json!([]));
+	let Some(deps) = deps.as_array_mut() else {
+		fatal!(
+			"The dependencies in mod.json are in an old format, update them \
+			with `geode package migrate .`"
+		);
+	};
+	if deps.iter().any(|dep| dep.get("id").and_then(|i| i.as_str()) == Some(id.as_str())) {
+		fatal!("'{}' is already a dependency of this project", id);
+	}
+
+	let version_req = VersionReq::parse(&version.as_deref().unwrap_or("*").replace('v', ""))
+		.unwrap_or_else(|e| fatal!("Invalid version '{}': {}", version.as_deref().unwrap(), e));
+
+	info!("Updating Geode mods index");
+	update_index(config);
+
+	let dep = Dependency { id: id.clone(), version: version_req, required: !optional };
+	let info = match find_dependency(&dep, &index_mods_dir(config), false)
+		.expect("Unable to read index")
+	{
+		Found::Some(_, info) => info,
+		Found::Wrong(found) => fatal!(
+			"Only version '{}' of '{}' is on the index, which doesn't match '{}'",
+			found, id, dep.version
+		),
+		Found::NotAnApi => fatal!(
+			"'{}' is not marked as an API, so it can't be depended on", id
+		),
+		Found::Maybe(similar) => fatal!(
+			"Mod '{}' not found on the index - maybe you meant '{}'?", id, similar
+		),
+		Found::None => fatal!("Mod '{}' not found on the index", id),
+	};
+
+	let package = fetch_dependency(config, &id, &info.version);
+
+	let dep_dir = install_dir.join("geode-deps");
+	fs::create_dir_all(&dep_dir).expect("Unable to create dependency directory");
+	extract_dependency(fs::File::open(package).unwrap(), &dep_dir, &id, !optional);
+
+	deps.push(json!({
+		"id": id,
+		"version": version.unwrap_or_else(|| format!(">=v{}", info.version)),
+		"required": !optional,
+	}));
+	write_mod_json(dir, &mod_json).unwrap_or_else(|e| fatal!("{}", e));
+
+	done!("Added '{}' version '{}' as a dependency", id, info.version);
+}
+
+fn remove_dependency(dir: &Path, id: String, install_dir: PathBuf) {
+	let mut mod_json = read_mod_json(dir).unwrap_or_else(|e| fatal!("{}", e));
+	let Some(deps) = mod_json.get_mut("dependencies").and_then(|d| d.as_array_mut()) else {
+		fatal!("This project has no dependencies");
+	};
+	let count = deps.len();
+	deps.retain(|dep| dep.get("id").and_then(|i| i.as_str()) != Some(id.as_str()));
+	if deps.len() == count {
+		fatal!("'{}' is not a dependency of this project", id);
+	}
+	write_mod_json(dir, &mod_json).unwrap_or_else(|e| fatal!("{}", e));
+
+	let dep_dir = install_dir.join("geode-deps");
+	let dep_path = dep_dir.join(&id);
+	if dep_path.exists() {
+		fs::remove_dir_all(&dep_path).expect("Unable to remove installed dependency");
+		info!("Removed {}", dep_path.display());
+	}
+
+	// Keep what the build reads in line with what's installed
+	let resolved = fs::read_to_string(dep_dir.join("geode-deps.json"))
+		.ok()
+		.and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+		.and_then(|resolved| Some(resolved.get("dependencies")?.as_array()?.clone()));
+	if let Some(mut deps) = resolved {
+		deps.retain(|dep| dep.get("id").and_then(|i| i.as_str()) != Some(id.as_str()));
+		write_resolved_dependencies(&dep_dir, deps);
+	}
+
+	// Code still using the dependency's headers won't build anymore
+	let include = Regex::new(&format!(r#"#\s*(include|import)\s*[<"]{}/"#, regex::escape(&id))).unwrap();
+	let sources = walkdir::WalkDir::new(dir)
+		.into_iter()
+		.filter_entry(|e| {
+			e.path() != dir.join(&install_dir)
+				&& !e.file_name().to_string_lossy().starts_with('.')
+		})
+		.filter_map(|e| e.ok())
+		.filter(|e| e.path().extension().is_some_and(|ext| {
+			["c", "cc", "cpp", "cxx", "h", "hh", "hpp", "hxx", "m", "mm"]
+				.contains(&ext.to_string_lossy().as_ref())
+		}));
+	for source in sources {
+		let Ok(text) = fs::read_to_string(source.path()) else { continue };
+		for (i, line) in text.lines().enumerate() {
+			if include.is_match(line) {
+				warn!(
+					"{}:{} still includes headers from '{}'",
+					source.path().strip_prefix(dir).unwrap_or(source.path()).display(),
+					i + 1,
+					id
+				);
+			}
+		}
+	}
+
+	done!("Removed dependency '{}'", id);
+}
+
+fn rename_project(dir: &Path, id: Option<String>, name: Option<String>) {
+	let mut mod_json = read_mod_json(dir).unwrap_or_else(|e| fatal!("{}", e));
+	let Some(old_id) = mod_json.get("id").and_then(|i| i.as_str()).map(String::from) else {
+		fatal!("mod.json has no ID, add one before renaming the mod");
+	};
+	let old_name = mod_json.get("name").and_then(|i| i.as_str()).unwrap_or_default().to_string();
+
+	let (new_id, new_name) = if id.is_none() && name.is_none() {
+		let new_id = loop {
+			let id = ask_value("ID", Some(&old_id), true);
+			if is_valid_id(&id) {
+				break id;
+			}
+			fail!("'{}' is not a valid ID, it should look like `developer.mod-name` in lowercase", id);
+		};
+		(new_id, ask_value("Name", Some(&old_name), true))
+	} else {
+		(id.unwrap_or_else(|| old_id.clone()), name.unwrap_or_else(|| old_name.clone()))
+	};
+	if !is_valid_id(&new_id) {
+		fatal!("'{}' is not a valid ID, it should look like `developer.mod-name` in lowercase", new_id);
+	}
+	if new_id == old_id && new_name == old_name {
+		done!("Nothing to rename");
+		return;
+	}
+
+	mod_json["id"] = json!(new_id);
+	mod_json["name"] = json!(new_name);
+	rename_binary_entries(&mut mod_json, &old_id, &new_id);
+	write_mod_json(dir, &mod_json).unwrap_or_else(|e| fatal!("{}", e));
+	info!("Updated mod.json");
+
+	// The CMake project is named after the mod without spaces
+	let old_target: String = old_name.chars().filter(|c| !c.is_whitespace()).collect();
+	let new_target: String = new_name.chars().filter(|c| !c.is_whitespace()).collect();
+	let target_regex = Regex::new(&format!(r"\b{}\b", regex::escape(&old_target))).unwrap();
+
+	// The template base is renamed too so `geode project update` doesn't see
+	// the rename as a change to the project
+	let files = walkdir::WalkDir::new(dir)
+		.into_iter()
+		.filter_entry(|e| {
+			let name = e.file_name().to_string_lossy();
+			// CMake build directories can be called anything, but they all
+			// have a cache at the top
+			let is_build = e.file_type().is_dir()
+				&& (name == "build" || e.path().join("CMakeCache.txt").exists());
+			e.depth() == 0 || !(is_build || (name.starts_with('.') && name != ".geode"))
+		})
+		.filter_map(|e| e.ok())
+		.filter(|e| e.file_type().is_file() && e.file_name() != "mod.json");
+	let mut changed = 0;
+	for file in files {
+		let path = file.path();
+		let file_name = file.file_name().to_string_lossy().to_lowercase();
+		let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+		let is_cmake = file_name == "cmakelists.txt" || ext == "cmake";
+		let is_doc = ext == "md";
+		if !is_cmake && !is_doc && !["c", "cc", "cpp", "cxx", "h", "hh", "hpp", "hxx", "m", "mm"].contains(&ext.as_str()) {
+			continue;
+		}
+		let Ok(text) = fs::read_to_string(path) else { continue };
+
+		// Resources are referred to as "id/name.png", and IDs are distinct
+		// enough to replace anywhere
+		let mut new_text = text.replace(&old_id, &new_id);
+		if is_cmake && !old_target.is_empty() {
+			new_text = target_regex.replace_all(&new_text, new_target.as_str()).into_owned();
+		}
+		if is_doc && !old_name.is_empty() {
+			new_text = new_text.replace(&old_name, &new_name);
+		}
+		if new_text != text {
+			fs::write(path, new_text).unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+			info!("Updated {}", path.strip_prefix(dir).unwrap_or(path).display());
+			changed += 1;
+		}
+		// Names in code could be anything, so leave them to the user
+		if !is_cmake && !is_doc && old_name != new_name && !old_name.is_empty() && text.contains(&old_name) {
+			warn!(
+				"{} mentions '{}', you may want to rename it by hand",
+				path.strip_prefix(dir).unwrap_or(path).display(), old_name
+			);
+		}
+	}
+
+	done!("Renamed {} ({}) to {} ({}), {} other file(s) changed", old_name, old_id, new_name, new_id, changed);
+	if new_id != old_id {
+		info!("Rebuild the mod so its binaries and resources are named after the new ID");
+	}
+}
+
+/// C++ type of the value of each kind of setting that has one
+fn setting_type(kind: &str) -> Option<&'static str> {
+	Some(match kind {
+		"bool" => "bool",
+		"int" => "int64_t",
+		"float" => "double",
+		"string" => "std::string",
+		"file" | "path" => "std::filesystem::path",
+		"color" => "cocos2d::ccColor3B",
+		"rgba" => "cocos2d::ccColor4B",
+		_ => return None,
+	})
+}
+
+fn codegen_settings(dir: &Path, output: &Path) {
+	let mod_json = read_mod_json(dir).unwrap_or_else(|e| fatal!("{}", e));
+	let Some(settings) = mod_json.get("settings").and_then(|s| s.as_object()) else {
+		fatal!("mod.json has no settings");
+	};
+
+	let mut getters = Vec::new();
+	let mut idents = HashMap::new();
+	for (key, setting) in settings {
+		let kind = setting.get("type").and_then(|t| t.as_str()).unwrap_or_default();
+		// Titles are just for show
+		if kind == "title" {
+			continue;
+		}
+		let Some(cpp_type) = setting_type(kind) else {
+			warn!("Setting '{}' has type '{}' which has no known C++ type, skipping it", key, kind);
+			continue;
+		};
+		let ident = cpp_identifier(key);
+		if let Some(existing) = idents.get(&ident) {
+			warn!("{} and {} would both be called {}, skipping the second", existing, key, ident);
+			continue;
+		}
+
+		let mut getter = String::new();
+		for field in ["name", "description"] {
+			if let Some(text) = setting.get(field).and_then(|t| t.as_str()) {
+				for line in text.lines() {
+					getter += &format!("\t// {}\n", line);
+				}
+			}
+		}
+		getter += &format!(
+			"\tinline {cpp_type} {ident}() {{\n\
+			\t\treturn geode::Mod::get()->getSettingValue<{cpp_type}>(\"{key}\");\n\
+			\t}}\n"
+		);
+		getters.push(getter);
+		idents.insert(ident, key);
+	}
+
+	let header = format!(
+		"// Generated by `geode project codegen-settings`, don't edit this file\n\
+		#pragma once\n\n\
+		#include <Geode/loader/Mod.hpp>\n\n\
+		namespace settings {{\n{}}}\n",
+		getters.join("\n")
+	);
+
+	// Don't touch the file if nothing changed, so it doesn't cause rebuilds
+	let output = dir.join(output);
+	if fs::read_to_string(&output).is_ok_and(|old| old == header) {
+		done!("{} is up to date", output.display());
+		return;
+	}
+	if let Some(parent) = output.parent() {
+		fs::create_dir_all(parent).unwrap_or(());
+	}
+	fs::write(&output, header).unwrap_or_else(|e| fatal!("Unable to write {}: {}", output.display(), e));
+	done!("Wrote {} setting(s) to {}", idents.len(), output.display());
+}
+
+/// A hook calling the original function, from a signature like
+/// `void onPlay(CCObject* sender)`
+fn hook_function(class: &str, signature: &str) -> String {
+	let regex = Regex::new(r"^\s*(.*?)\b([A-Za-z_]\w*)\s*\((.*)\)\s*(const)?\s*;?\s*$").unwrap();
+	let Some(caps) = regex.captures(signature) else {
+		fatal!("'{}' doesn't look like a function signature, like \"bool init()\"", signature);
+	};
+	let ret = caps[1].trim();
+	let name = &caps[2];
+	let params = caps[3].trim();
+	let ident = Regex::new(r"([A-Za-z_]\w*)\s*(\[\s*\d*\s*\])?\s*(=.*)?$").unwrap();
+	let args = if params.is_empty() || params == "void" {
+		Vec::new()
+	} else {
+		params
+			.split(',')
+			.map(|param| ident.captures(param.trim())
+				// Just a type isn't enough, the hook has to pass it on
+				.filter(|c| c.get(1).unwrap().start() > 0)
+				.map(|c| c[1].to_string())
+				.unwrap_or_else(|| fatal!("Parameter '{}' of '{}' needs a name", param.trim(), signature)))
+			.collect()
+	};
+	let call = format!("{}::{}({})", class, name, args.join(", "));
+	let body = if ret.is_empty() || ret == "void" {
+		format!("\t\t{};\n", call)
+	} else if ret == "bool" && name.starts_with("init") {
+		format!("\t\tif (!{}) {{\n\t\t\treturn false;\n\t\t}}\n\n\t\treturn true;\n", call)
+	} else {
+		format!("\t\treturn {};\n", call)
+	};
+	format!(
+		"\t{} {}({}){} {{\n{}\t}}\n",
+		if ret.is_empty() { "void" } else { ret },
+		name,
+		params,
+		if caps.get(4).is_some() { " const" } else { "" },
+		body
+	)
+}
+
+fn add_hook(dir: &Path, class: String, methods: Vec<String>) {
+	if !Regex::new(r"^[A-Za-z_]\w*$").unwrap().is_match(&class) {
+		fatal!("'{}' is not a valid class name", class);
+	}
+	let relative = format!("src/hooks/{}.cpp", class);
+	let path = dir.join(&relative);
+	if path.exists() {
+		fatal!("{} already exists", relative);
+	}
+
+	let functions = if methods.is_empty() {
+		"\t// Write the functions you want to hook here, with the same signature\n\
+		\t// as in the original class\n".to_string()
+	} else {
+		methods.iter().map(|m| hook_function(&class, m)).collect::<Vec<_>>().join("\n")
+	};
+	let source = format!(
+		"#include <Geode/Geode.hpp>\n\
+		#include <Geode/modify/{class}.hpp>\n\n\
+		using namespace geode::prelude;\n\n\
+		class $modify(My{class}, {class}) {{\n\
+		{functions}\
+		}};\n"
+	);
+	fs::create_dir_all(path.parent().unwrap()).expect("Unable to create hooks directory");
+	fs::write(&path, source).unwrap_or_else(|e| fatal!("Unable to write {}: {}", relative, e));
+	info!("Created {}", relative);
+
+	// Add it to the mod's sources, unless they're globbed
+	let cmake_path = dir.join("CMakeLists.txt");
+	let Ok(cmake) = fs::read_to_string(&cmake_path) else {
+		warn!("Unable to read CMakeLists.txt, add {} to your sources by hand", relative);
+		return;
+	};
+	let add_library = Regex::new(r"add_library\s*\(\s*\$\{PROJECT_NAME\}\s+SHARED\b([^)]*)\)").unwrap();
+	match add_library.captures(&cmake) {
+		Some(caps) if caps[1].contains("${") && cmake.contains("GLOB") => {
+			info!("CMakeLists.txt finds source files by itself, so {} will be built", relative);
+		}
+		Some(caps) => {
+			let sources = caps.get(1).unwrap();
+			let insert = if sources.as_str().ends_with('\n') {
+				format!("\t{}\n", relative)
+			} else {
+				format!(" {}", relative)
+			};
+			let mut cmake = cmake.clone();
+			cmake.insert_str(sources.end(), &insert);
+			fs::write(&cmake_path, cmake).expect("Unable to write CMakeLists.txt");
+			info!("Added {} to CMakeLists.txt", relative);
+		}
+		None => {
+			warn!("Couldn't find the mod's sources in CMakeLists.txt, add {} to them by hand", relative);
+		}
+	}
+
+	done!("Hooked {}", class);
+}
+
+pub fn publish_project(config: &Config, dir: &Path, package_path: Option<PathBuf>) {
     let Some(pkg) = package_path.or(get_built_package(dir)) else {
         fatal!(
             "Unable to find the project's .geode package - please try manually \
@@ -466,26 +1056,48 @@ pub fn publish_project(_config: &Config, dir: &Path, package_path: Option<PathBu
     };
 
     // initialize indexer and add mod there
-    if !indexer::is_initialized() {
-        indexer::initialize();
+    if !indexer::is_initialized(config) {
+        indexer::initialize(config, None, false, false, None);
     }
-    indexer::add_mod(pkg);
+    indexer::add_mods(config, vec![pkg], false, false, false, false, false);
 }
 
-pub fn unpublish_project(id: Option<String>) {
-    if !indexer::is_initialized() {
+pub fn unpublish_project(config: &Config, id: Option<String>) {
+    if !indexer::is_initialized(config) {
         fatal!("You don't seem to have any mods published!");
     }
     else {
-        indexer::remove_mod(
-            id.unwrap_or_else(|| parse_mod_info(&std::env::current_dir().unwrap()).id)
+        indexer::remove_mods(
+            config,
+            vec![id.unwrap_or_else(|| parse_mod_info(&std::env::current_dir().unwrap()).id)],
+            false
         );
     }
 }
 
 pub fn subcommand(config: &mut Config, cmd: Project) {
 	match cmd {
-        Project::New { path } => template::build_template(config, path),
+        Project::New { path, template, offline, license, no_git, allow_scripts } => template::build_template(
+			config, path, template, offline, license, !no_git, allow_scripts
+		),
+		Project::Update { template, offline } => template::update_template(
+			&std::env::current_dir().unwrap(), template, offline
+		),
+		Project::Ide { ide, build_dir } => ide::write_ide_config(
+			&std::env::current_dir().unwrap(), ide, &build_dir
+		),
+		Project::Rename { id, name } => rename_project(
+			&std::env::current_dir().unwrap(), id, name
+		),
+		Project::CodegenSettings { output } => codegen_settings(
+			&std::env::current_dir().unwrap(), &output
+		),
+		Project::AddHook { class, method } => add_hook(
+			&std::env::current_dir().unwrap(), class, method
+		),
+		Project::I18n { commands } => i18n::subcommand(
+			&std::env::current_dir().unwrap(), commands
+		),
 		Project::ClearCache => clear_cache(
             &std::env::current_dir().unwrap()
         ),
@@ -495,10 +1107,23 @@ pub fn subcommand(config: &mut Config, cmd: Project) {
             install_dir.unwrap_or("build".into()),
             externals
         ),
+		Project::AddDependency { id, version, optional, install_dir } => add_dependency(
+			config,
+			&std::env::current_dir().unwrap(),
+			id,
+			version,
+			optional,
+			install_dir.unwrap_or("build".into())
+		),
+		Project::RemoveDependency { id, install_dir } => remove_dependency(
+			&std::env::current_dir().unwrap(),
+			id,
+			install_dir.unwrap_or("build".into())
+		),
         Project::Publish { package } => publish_project(
             config, &std::env::current_dir().unwrap(), package
         ),
-        Project::Unpublish { id } => unpublish_project(id),
-        Project::ListPublished => indexer::list_mods(),
+        Project::Unpublish { id } => unpublish_project(config, id),
+        Project::ListPublished => indexer::list_mods(config, false, false),
 	}
 }
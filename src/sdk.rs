@@ -1,14 +1,18 @@
 use clap::Subcommand;
 use colored::Colorize;
-use crate::config::Config;
+use crate::config::{geode_root, proxy_options, Config, Download, Profile};
 use crate::util::logging::ask_confirm;
+use crate::util::mod_file::try_parse_mod_info;
 use git2::build::RepoBuilder;
 use git2::{FetchOptions, RemoteCallbacks, Repository, SubmoduleUpdateOptions};
-use reqwest::header::{HeaderMap, HeaderValue, USER_AGENT};
+use reqwest::header::{HeaderMap, HeaderValue, ETAG, IF_RANGE, RANGE, USER_AGENT};
 use semver::{Version, Prerelease};
 use serde::Deserialize;
+use sha3::{Digest, Sha3_256};
 use std::fs;
+use std::io::{IsTerminal, Read, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 #[cfg(target_os = "macos")]
 use crate::launchctl;
@@ -23,6 +27,9 @@ use crate::{done, fail, fatal, info, warn};
 struct GithubReleaseAsset {
 	name: String,
 	browser_download_url: String,
+	/// Digest GitHub publishes for the asset, like `sha256:<hex>`
+	#[serde(default)]
+	digest: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -36,17 +43,136 @@ pub enum Branch {
 	Stable,
 }
 
+/// Download to `<file>.partial` first, so running the command again after
+/// the connection dropped continues where it stopped. The ETag the download
+/// started with is kept next to it, so a file that changed since is
+/// downloaded from the start instead of having its ends stitched together
 fn download_url(
 	url: String,
 	file_name: &PathBuf,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-	let res = reqwest::blocking::get(url)?;
-	let mut file = fs::File::create(file_name)?;
-	let mut content = std::io::Cursor::new(res.bytes()?);
-	std::io::copy(&mut content, &mut file)?;
+	download_url_with_progress(url, file_name, &|_, _| {})
+}
+
+/// Same as `download_url`, calling `progress` with how many bytes of how
+/// many are there as they come in
+fn download_url_with_progress(
+	url: String,
+	file_name: &PathBuf,
+	progress: &(dyn Fn(u64, Option<u64>) + Sync),
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+	let mut partial = file_name.clone().into_os_string();
+	partial.push(".partial");
+	let partial = PathBuf::from(partial);
+	let etag_file = partial.with_extension("partial.etag");
+
+	let have = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+	let etag = fs::read_to_string(&etag_file).ok();
+
+	let mut req = reqwest::blocking::Client::new().get(url);
+	let resuming = have > 0 && etag.is_some();
+	if let (true, Some(etag)) = (resuming, &etag) {
+		info!("Resuming download at {} bytes", have);
+		req = req.header(RANGE, format!("bytes={}-", have)).header(IF_RANGE, etag.trim());
+	}
+	let mut res = req.send()?;
+	// Everything was downloaded already, only the rename didn't happen
+	let complete = resuming && res.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE;
+
+	let mut file = if resuming && res.status() == reqwest::StatusCode::PARTIAL_CONTENT {
+		fs::OpenOptions::new().append(true).open(&partial)?
+	} else if complete {
+		fs::File::open(&partial)?
+	} else {
+		res = res.error_for_status()?;
+		if resuming {
+			info!("The file changed since, downloading it again");
+		}
+		match res.headers().get(ETAG).and_then(|e| e.to_str().ok()) {
+			Some(etag) => fs::write(&etag_file, etag)?,
+			None => fs::remove_file(&etag_file).unwrap_or(()),
+		}
+		fs::File::create(&partial)?
+	};
+	if !complete {
+		let mut done = if res.status() == reqwest::StatusCode::PARTIAL_CONTENT { have } else { 0 };
+		let total = res.content_length().map(|len| len + done);
+		let mut buf = vec![0; 64 * 1024];
+		loop {
+			let read = res.read(&mut buf)?;
+			if read == 0 {
+				break;
+			}
+			file.write_all(&buf[..read])?;
+			done += read as u64;
+			progress(done, total);
+		}
+	}
+	drop(file);
+
+	fs::rename(&partial, file_name)?;
+	fs::remove_file(&etag_file).unwrap_or(());
 	Ok(())
 }
 
+/// One line showing how far along each of the downloads running at the same
+/// time is. Only drawn when output goes to a terminal
+struct Progress {
+	state: Mutex<ProgressState>,
+}
+
+struct ProgressState {
+	/// Name, bytes downloaded and total bytes of each download
+	tasks: Vec<(String, u64, Option<u64>)>,
+	/// What was drawn last
+	line: String,
+}
+
+impl Progress {
+	fn new() -> Progress {
+		Progress { state: Mutex::new(ProgressState { tasks: Vec::new(), line: String::new() }) }
+	}
+
+	/// Start showing a download, returning its index for `update`
+	fn add(&self, name: &str) -> usize {
+		let mut state = self.state.lock().unwrap();
+		state.tasks.push((name.to_string(), 0, None));
+		state.tasks.len() - 1
+	}
+
+	fn update(&self, task: usize, done: u64, total: Option<u64>) {
+		let mut state = self.state.lock().unwrap();
+		let ProgressState { tasks, line: last } = &mut *state;
+		tasks[task].1 = done;
+		tasks[task].2 = total;
+		let line = tasks.iter()
+			.map(|(name, done, total)| match total {
+				Some(total) if *total > 0 => format!("{} {}%", name, done * 100 / total),
+				_ if *done == 0 => format!("{} 0%", name),
+				_ => format!("{} {:.1} MB", name, *done as f64 / 1_000_000.0),
+			})
+			.collect::<Vec<_>>()
+			.join(", ");
+
+		// Redrawing only when something visible changed keeps it from flickering
+		if *last != line && std::io::stdout().is_terminal() {
+			print!("\r{} Downloading {}\x1b[K", "| Info |".bright_cyan(), line);
+			std::io::stdout().flush().ok();
+			*last = line;
+		}
+	}
+
+	/// Move past the progress line so other output starts on its own. It's
+	/// drawn again on the next line if anything is still downloading
+	fn finish(&self) {
+		let mut state = self.state.lock().unwrap();
+		if !state.line.is_empty() {
+			println!();
+			state.line.clear();
+		}
+	}
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Sdk {
 	/// Install SDK
@@ -61,22 +187,97 @@ pub enum Sdk {
 
 		/// Path to install
 		path: Option<PathBuf>,
+
+		/// Install from an SDK archive (.zip) or directory someone else
+		/// downloaded instead of cloning it. Binaries in its `bin` folder
+		/// are installed along with it
+		#[clap(long)]
+		from: Option<PathBuf>,
+
+		/// Also install the prebuilt binaries for these platforms, like
+		/// `--binaries win,android64`. They're downloaded while the SDK is
+		/// cloned
+		#[clap(long, value_enum, value_delimiter = ',', conflicts_with = "from")]
+		binaries: Vec<BinaryPlatform>,
+
+		/// Install even if there's no SHA-256 sum to check against, either
+		/// a `<zip>.sha256` or SHA256SUMS file next to a `--from` archive or
+		/// one published with the release for `--binaries`. A `--from`
+		/// directory with binaries in it can't be checked and always needs it
+		#[clap(long)]
+		skip_checksum: bool,
 	},
 
-	/// Install prebuilt binaries for SDK
-	InstallBinaries,
+	/// Install the prebuilt loader binaries matching the SDK, or the version
+	/// the current project builds with if it's installed alongside
+	InstallBinaries {
+		/// Platforms to get binaries for, downloaded at the same time.
+		/// Defaults to the current one
+		#[clap(long, value_enum)]
+		platform: Vec<BinaryPlatform>,
 
-	/// Uninstall SDK
-	Uninstall,
+		/// Install even if the release publishes no SHA-256 sum for the
+		/// binaries to check them against
+		#[clap(long)]
+		skip_checksum: bool,
+
+		/// Install from a release ZIP someone else downloaded instead. Its
+		/// sum is read from a `<zip>.sha256` or SHA256SUMS file next to it
+		#[clap(long)]
+		from: Option<PathBuf>,
+
+		/// SHA256SUMS file to check the binaries against instead of the sums
+		/// GitHub publishes, for when only a mirror can be reached. Sums
+		/// from the mirror itself aren't trusted
+		#[clap(long, conflicts_with = "from")]
+		checksums: Option<PathBuf>,
+	},
+
+	/// Install what's needed to build mods for another platform, and CMake
+	/// toolchain files for it
+	InstallToolchain {
+		/// Platform to build for
+		#[clap(value_enum)]
+		toolchain: Toolchain,
+
+		/// Use the Android NDK at this path instead of downloading one
+		#[clap(long)]
+		ndk: Option<PathBuf>,
+	},
+
+	/// Uninstall the SDK with its binaries, or a version installed with
+	/// `install-version`. SDKs registered with `set-path` are left alone
+	Uninstall {
+		/// Version installed alongside the main SDK to remove instead
+		version: Option<String>,
+
+		/// Remove everything Geode CLI keeps in its own directory: the main
+		/// SDK if it was installed there, every version installed alongside
+		/// it, the installed toolchains and the cache of dependencies
+		/// downloaded for projects
+		#[clap(long, conflicts_with = "version")]
+		all: bool,
+	},
 
-	/// Update SDK
+	/// Update SDK to the latest of its branch, switching branches if one is
+	/// given. Stable is the latest release, nightly the latest commit
 	Update {
 		/// Set update branch
-		#[clap(value_enum)]
+		#[clap(long, value_enum)]
 		branch: Option<Branch>,
+
+		/// Same as `--branch`, from before it was an option
+		#[clap(value_enum, hide = true, conflicts_with = "branch")]
+		legacy_branch: Option<Branch>,
+
+		/// Pin the SDK to this commit, for CI or reproducing bugs. Updating
+		/// without it goes back to following the branch
+		#[clap(long, conflicts_with_all = ["branch", "legacy_branch"])]
+		commit: Option<String>,
 	},
 
-	/// Change SDK path.
+	/// Use an SDK checkout from anywhere, like one you're working on, instead
+	/// of the installed one
 	SetPath {
 		/// Move old SDK to new directory
 		#[clap(long)]
@@ -86,12 +287,72 @@ pub enum Sdk {
 		path: PathBuf
 	},
 
-	/// Get SDK version
+	/// Get SDK version, the latest release and whether the current profile's
+	/// loader matches the SDK
 	Version,
+
+	/// Check that everything needed to build mods is set up, and how to fix
+	/// what isn't
+	Doctor,
+
+	/// Regenerate the bindings headers with the SDK's codegen, for working
+	/// against a bindings checkout. Run from the mod's folder
+	Codegen {
+		/// Platforms to generate for, the current one if not given
+		#[clap(long, value_enum)]
+		platform: Vec<BinaryPlatform>,
+
+		/// Bindings checkout to generate from, defaults to
+		/// GEODE_BINDINGS_REPO_PATH
+		#[clap(long)]
+		bindings: Option<PathBuf>,
+
+		/// GD version to generate for, defaults to the one in mod.json
+		#[clap(long)]
+		gd: Option<String>,
+
+		/// Build directory the headers go in. With several platforms, each
+		/// gets its own `<build-dir>-<platform>`
+		#[clap(long, default_value = "build")]
+		build_dir: PathBuf,
+	},
+
+	/// Install another version of the SDK alongside the main one. Projects
+	/// whose mod.json targets that version are built with it
+	InstallVersion {
+		/// Version to install, like 2.0.0-beta.1
+		version: String,
+	},
+
+	/// List the installed SDK versions
+	List,
+
+	/// Remove a version of the SDK installed with `install-version`, same as
+	/// `uninstall <version>`
+	#[clap(hide = true)]
+	RemoveVersion {
+		/// Version to remove
+		version: String,
+	},
 }
 
-fn uninstall() -> bool {
+/// Whether an SDK was registered with `set-path`, so it's someone's own
+/// checkout that Geode CLI shouldn't delete
+fn is_linked_sdk(config: &Config, path: &Path) -> bool {
+	let same = |a: &Path, b: &Path| a == b || a.canonicalize().ok() == b.canonicalize().ok();
+	config.sdk_linked && config.sdk_path.as_ref().is_some_and(|p| same(p, path))
+}
+
+fn uninstall(config: &mut Config) -> bool {
 	let sdk_path = Config::sdk_path();
+	if is_linked_sdk(config, &sdk_path) {
+		fail!(
+			"The SDK at {} was registered with `geode sdk set-path`, remove it \
+			yourself if you want it gone",
+			sdk_path.display()
+		);
+		return false;
+	}
 
 	if !ask_confirm(
 		&format!("Are you sure you want to uninstall Geode SDK? (Installed at {sdk_path:?})"),
@@ -101,15 +362,88 @@ fn uninstall() -> bool {
 		return false;
 	}
 
-	if let Err(e) = std::fs::remove_dir_all(sdk_path) {
+	if let Err(e) = std::fs::remove_dir_all(&sdk_path) {
 		fail!("Unable to uninstall SDK: {}", e);
 		return false;
 	}
+	if config.sdk_path.as_ref() == Some(&sdk_path) {
+		config.sdk_path = None;
+	}
 
 	done!("Uninstalled Geode SDK");
 	true
 }
 
+/// Remove every SDK Geode CLI manages, along with the dependency cache
+fn uninstall_all(config: &mut Config) {
+	// Only what lives in Geode's own directory is removed, GEODE_SDK may point
+	// to someone's checkout even if it wasn't registered with `set-path`
+	let root = geode_root().canonicalize().unwrap_or_else(|_| geode_root());
+	let managed = |path: &Path| path.canonicalize().is_ok_and(|p| p.starts_with(&root));
+	let mut targets = Vec::new();
+	if let Ok(path) = Config::try_sdk_path() {
+		if is_linked_sdk(config, &path) {
+			info!("Leaving {} alone as it was registered with `geode sdk set-path`", path.display());
+		} else if !managed(&path) {
+			info!(
+				"Leaving {} alone as it's outside {}, remove it with `geode sdk uninstall`",
+				path.display(), root.display()
+			);
+		} else {
+			targets.push(path);
+		}
+	}
+	let mut versions = fs::read_dir(versioned_sdks_dir())
+		.map(|dir| dir.filter_map(|e| Some(e.ok()?.path())).collect::<Vec<_>>())
+		.unwrap_or_default();
+	versions.sort();
+	for path in versions {
+		if is_linked_sdk(config, &path) {
+			info!("Leaving {} alone as it was registered with `geode sdk set-path`", path.display());
+		} else {
+			targets.push(path);
+		}
+	}
+	let dependency_cache = crate::project::dependency_cache_dir();
+	if dependency_cache.exists() {
+		targets.push(dependency_cache);
+	}
+	if toolchains_dir().exists() {
+		targets.push(toolchains_dir());
+	}
+
+	if targets.is_empty() {
+		info!("There's nothing to uninstall");
+		return;
+	}
+	info!("This removes:");
+	for path in &targets {
+		println!("  {}", path.display());
+	}
+	if !ask_confirm("Are you sure you want to remove all of these?", false) {
+		fail!("Aborting");
+		return;
+	}
+
+	for path in &targets {
+		match fs::remove_dir_all(path) {
+			Ok(_) => {
+				if config.sdk_path.as_ref() == Some(path) {
+					config.sdk_path = None;
+				}
+				if config.android_ndk.as_ref().is_some_and(|ndk| ndk.starts_with(path)) {
+					config.android_ndk = None;
+				}
+			}
+			Err(e) => fail!("Unable to remove {}: {}", path.display(), e),
+		}
+	}
+	// The SDKs directory itself only holds versions
+	fs::remove_dir(versioned_sdks_dir()).unwrap_or(());
+
+	done!("Uninstalled all SDKs managed by Geode CLI");
+}
+
 fn update_submodules_recurse(repo: &Repository) -> Result<(), git2::Error> {
 	for mut subm in repo.submodules()? {
 		let name = subm
@@ -131,6 +465,7 @@ fn update_submodules_recurse(repo: &Repository) -> Result<(), git2::Error> {
 
 		let mut opts = FetchOptions::new();
 		opts.remote_callbacks(callbacks);
+		opts.proxy_options(proxy_options());
 
 		let mut sopts = SubmoduleUpdateOptions::new();
 		sopts.fetch(opts);
@@ -182,121 +517,347 @@ fn set_sdk_env(path: &Path) -> bool {
 		}
 	}
 
+	// There's no one place every shell reads variables from on Linux
 	#[cfg(target_os = "linux")] {
-		warn!("set_sdk_env is not implemented on linux");
-		env_success = false;
+		env_success = add_to_shell_profile(path);
 	}
 
+	// launchd only passes it to apps started after this, until the next
+	// restart, so terminals need it in the shell's profile as well
 	#[cfg(target_os = "macos")] {
-		env_success = launchctl::set_sdk_env(path.to_str().unwrap());
+		let launchd = launchctl::set_sdk_env(path.to_str().unwrap());
+		env_success = add_to_shell_profile(path) || launchd;
 	}
 
 	env_success
 }
 
-fn get_sdk_path() -> Option<PathBuf> {
-	if std::env::var("GEODE_SDK").is_ok() &&
-		Config::try_sdk_path().is_ok()
+/// The startup file of the user's shell, and the line that sets GEODE_SDK
+/// in it
+#[cfg(unix)]
+fn shell_profile(path: &str) -> Option<(PathBuf, String)> {
+	let home = dirs::home_dir()?;
+	let shell = std::env::var("SHELL").unwrap_or_default();
+	let export = format!("export GEODE_SDK=\"{path}\"");
+	Some(match shell.rsplit('/').next().unwrap_or_default() {
+		// Terminals on macOS start login shells, which don't read .bashrc
+		"bash" if cfg!(target_os = "macos") => (home.join(".bash_profile"), export),
+		"bash" => (home.join(".bashrc"), export),
+		"zsh" => (
+			std::env::var_os("ZDOTDIR").map(PathBuf::from).unwrap_or(home).join(".zshrc"),
+			export
+		),
+		"fish" => (
+			home.join(".config/fish/conf.d/geode.fish"),
+			format!("set -gx GEODE_SDK \"{path}\"")
+		),
+		_ => (home.join(".profile"), export),
+	})
+}
+
+/// Offer to set GEODE_SDK in the user's shell profile, replacing the line
+/// from an earlier install if there is one
+#[cfg(unix)]
+fn add_to_shell_profile(path: &Path) -> bool {
+	use std::io::IsTerminal;
+
+	let Some((profile, line)) = shell_profile(path.to_str().unwrap()) else {
+		return false;
+	};
+	// Scripts installing the SDK shouldn't edit anyone's profile
+	if !std::io::stdin().is_terminal()
+		|| !ask_confirm(&format!("Set GEODE_SDK in {}?", profile.display()), true)
 	{
-		Some(Config::sdk_path())
+		return false;
 	}
-	else {
-		None
+
+	let existing = fs::read_to_string(&profile).unwrap_or_default();
+	let is_ours = |l: &str| {
+		let l = l.trim_start();
+		l.starts_with("export GEODE_SDK=") || l.starts_with("set -gx GEODE_SDK ")
+	};
+	let contents = if existing.lines().any(is_ours) {
+		existing.lines()
+			.map(|l| if is_ours(l) { line.as_str() } else { l })
+			.collect::<Vec<_>>()
+			.join("\n") + "\n"
+	} else {
+		let separator = match existing.as_str() {
+			"" => "",
+			e if e.ends_with('\n') => "\n",
+			_ => "\n\n",
+		};
+		format!("{existing}{separator}# Added by Geode CLI\n{line}\n")
+	};
+
+	let written = profile.parent().map_or(Ok(()), fs::create_dir_all)
+		.and_then(|_| fs::write(&profile, contents));
+	match written {
+		Ok(_) => {
+			info!("Set GEODE_SDK in {}", profile.display());
+			true
+		}
+		Err(e) => {
+			warn!("Unable to write to {}: {}", profile.display(), e);
+			false
+		}
 	}
 }
 
-fn install(config: &mut Config, path: PathBuf, force: bool) {
-	let parent = path.parent().unwrap();
+/// Tell the user how to set GEODE_SDK themselves when it can't be done
+/// automatically
+fn print_sdk_env_instructions(path: &Path) {
+	let path = path.to_str().unwrap();
+	if cfg!(windows) {
+		info!("Set it with `setx GEODE_SDK \"{}\"`, then restart your terminal", path);
+		return;
+	}
 
-	if !force && std::env::var("GEODE_SDK").is_ok() {
-		if Config::try_sdk_path().is_ok() {
-			fail!("SDK is already installed at {}", Config::sdk_path().display());
-			info!("Use --reinstall if you want to remove the existing installation");
-			return;
-		} else {
-			let env_sdk_path = std::env::var("GEODE_SDK").unwrap();
-			info!("GEODE_SDK ({env_sdk_path}) is already set, but seems to point to an invalid sdk installation.");
-			if !crate::logging::ask_confirm("Do you wish to proceed?", true) {
-				fatal!("Aborting");
+	let shell = std::env::var("SHELL").unwrap_or_default();
+	let shell = shell.rsplit('/').next().unwrap_or_default();
+	let instructions = [
+		("bash", format!("echo 'export GEODE_SDK=\"{path}\"' >> ~/.bashrc")),
+		("zsh", format!("echo 'export GEODE_SDK=\"{path}\"' >> ~/.zshrc")),
+		("fish", format!("set -Ux GEODE_SDK \"{path}\"")),
+	];
+	match instructions.iter().find(|(name, _)| *name == shell) {
+		Some((_, command)) => info!("Set it by running `{}`, then restart your terminal", command),
+		None => {
+			info!("Set it in your shell's startup file, for example:");
+			for (name, command) in &instructions {
+				info!("  {}: {}", name, command);
 			}
 		}
-	} else if !parent.exists() {
-		fail!("Parent folder {} does not exist", parent.display());
-		return;
-	} else if path.exists() {
-		fail!("Target path already exists");
-		return;
 	}
+}
 
-	info!("Downloading SDK");
+/// Check that an SDK checkout has everything building mods needs, returning
+/// its version
+fn verify_sdk(path: &Path) -> Result<Version, String> {
+	let version = fs::read_to_string(path.join("VERSION"))
+		.map_err(|e| format!("Unable to read VERSION: {e}"))?;
+	let version = Version::parse(version.trim())
+		.map_err(|e| format!("VERSION is not a valid version: {e}"))?;
+
+	for file in ["CMakeLists.txt", "cmake/GeodeFile.cmake", "loader/include/Geode/Geode.hpp"] {
+		if !path.join(file).is_file() {
+			return Err(format!("{file} is missing"));
+		}
+	}
 
-	let mut callbacks = RemoteCallbacks::new();
-	callbacks.sideband_progress(|x| {
-		print!(
-			"{} {}",
-			"| Info |".bright_cyan(),
-			std::str::from_utf8(x).unwrap()
-		);
-		true
-	});
+	// Checkouts from somewhere other than git are fine, they just can't update
+	if let Ok(repo) = Repository::open(path) {
+		for submodule in repo.submodules().map_err(|e| format!("Unable to read submodules: {}", e.message()))? {
+			if submodule.open().is_err() {
+				return Err(format!(
+					"Submodule {} was not cloned",
+					submodule.name().unwrap_or("<Unknown>")
+				));
+			}
+		}
+	}
 
-	let mut fetch = FetchOptions::new();
-	fetch.remote_callbacks(callbacks);
+	Ok(version)
+}
 
-	let mut builder = RepoBuilder::new();
-	builder.fetch_options(fetch);
+const SDK_REPOSITORY: &str = "https://github.com/geode-sdk/geode";
 
-	let repo = builder
-		.clone("https://github.com/geode-sdk/geode", &path)
-		.expect("Could not download SDK");
+/// Where SDK versions installed alongside the main one go
+fn versioned_sdks_dir() -> PathBuf {
+	geode_root().join("sdks")
+}
 
-	// update submodules, because for some reason
-	// Repository::update_submodules is private
-	update_submodules_recurse(&repo).expect("Unable to update submodules!");
+fn versioned_sdk_path(version: &Version) -> PathBuf {
+	versioned_sdks_dir().join(format!("v{}", version))
+}
 
-	// set GEODE_SDK environment variable;
-	if set_sdk_env(&path) {
-		info!("Set GEODE_SDK environment variable automatically");
-	} else {
-		warn!("Unable to set GEODE_SDK environment variable automatically");
-		info!(
-			"Please set the GEODE_SDK enviroment variable to {}",
-			path.to_str().unwrap()
-		);
+pub fn parse_sdk_version(version: &str) -> Version {
+	Version::parse(version.trim_start_matches('v'))
+		.unwrap_or_else(|e| fatal!("'{}' is not a valid version: {}", version, e))
+}
+
+/// The SDK to build a mod targeting `geode` with: the version installed
+/// alongside the main SDK for it if there is one, otherwise GEODE_SDK
+pub fn resolve_sdk(geode: &Version) -> Option<PathBuf> {
+	let pinned = versioned_sdk_path(geode);
+	if pinned.join("VERSION").exists() {
+		return Some(pinned);
 	}
+	Config::try_sdk_path().ok()
+}
 
-	switch_to_tag(config, &repo);
+/// Version of the SDK a mod targeting `geode` is built with, or the version
+/// it targets if there's no SDK for it
+pub fn build_sdk_version(geode: &Version) -> Version {
+	resolve_sdk(geode)
+		.and_then(|path| verify_sdk(&path).ok())
+		.unwrap_or_else(|| geode.clone())
+}
 
-	done!("Successfully installed SDK");
-	info!("Please restart your command line to have the GEODE_SDK enviroment variable set.");
-	info!("Use `geode sdk install-binaries` to install pre-built binaries");
+/// Whether mods built with this SDK are 32-bit on Windows. GD was before
+/// 2.206, which SDK v3 targets
+pub fn is_win32(sdk_version: &Version) -> bool {
+	sdk_version.major < 3
 }
 
-fn update(config: &mut Config, branch: Option<Branch>) {
-	// Switch branch if necessary
-	match branch {
-		Some(Branch::Nightly) => {
-			info!("Switching to nightly");
-			config.sdk_nightly = true;
-		}
-		Some(Branch::Stable) => {
-			info!("Switching to stable");
-			config.sdk_nightly = false;
+/// Warn if a mod would be built with an SDK that doesn't fit the version it
+/// targets
+pub fn check_sdk_version(id: &str, geode: &Version) {
+	let Some(version) = resolve_sdk(geode).and_then(|path| verify_sdk(&path).ok()) else {
+		return;
+	};
+	// Newer SDKs of the same major version build mods for older loaders fine
+	if version.major != geode.major || version.pre != geode.pre || version < *geode {
+		warn!(
+			"{} targets Geode v{}, but the SDK is v{}. Install that version \
+			alongside it with `geode sdk install-version {}`",
+			id, geode, version, geode
+		);
+	}
+}
+
+/// Where a version of the SDK is installed, whether alongside the main SDK or
+/// as the main SDK itself
+pub fn find_installed_sdk(version: &Version) -> Option<PathBuf> {
+	let alongside = versioned_sdk_path(version);
+	if alongside.join("VERSION").exists() {
+		return Some(alongside);
+	}
+	Config::try_sdk_path()
+		.ok()
+		.filter(|path| verify_sdk(path).is_ok_and(|v| v == *version))
+}
+
+/// The SDK a profile uses and its version: the one set for it with `geode
+/// profile set-sdk` if that's still installed, otherwise the main SDK
+pub fn profile_sdk(profile: &Profile) -> Option<(PathBuf, Version)> {
+	if let Some(version) = &profile.sdk_version {
+		let path = Version::parse(version).ok().and_then(|v| find_installed_sdk(&v));
+		match path {
+			Some(path) => return verify_sdk(&path).ok().map(|v| (path, v)),
+			None => warn!(
+				"Profile '{}' uses SDK v{}, which isn't installed anymore, so the main SDK is used",
+				profile.name, version
+			),
 		}
-		None => {}
+	}
+	let path = Config::try_sdk_path().ok()?;
+	verify_sdk(&path).ok().map(|v| (path, v))
+}
+
+/// Warn if a mod being installed targets a newer or incompatible Geode than
+/// the SDK of the profile it's installed in
+pub fn check_profile_sdk(config: &Config, id: &str, geode: &Version) {
+	let profile = config.get_current_profile();
+	let Some((_, version)) = profile_sdk(&profile) else {
+		return;
+	};
+	if version.major != geode.major || version.pre != geode.pre || version < *geode {
+		warn!(
+			"{} targets Geode v{}, but profile '{}' uses SDK v{}, so it may not load",
+			id, geode, profile.name, version
+		);
+	}
+}
+
+fn install_version(config: &Config, version: &str) {
+	let version = parse_sdk_version(version);
+	let path = versioned_sdk_path(&version);
+	if path.exists() {
+		fail!("SDK v{} is already installed at {}", version, path.display());
+		return;
+	}
+	fs::create_dir_all(versioned_sdks_dir()).expect("Unable to create SDK directory");
+
+	info!("Downloading SDK v{}", version);
+
+	// Cloning the main SDK is much faster than downloading it again
+	let repo = match Config::try_sdk_path() {
+		Ok(main) => RepoBuilder::new()
+			.fetch_options(progress_fetch_options())
+			.clone(&main.to_string_lossy(), &path)
+			.unwrap_or_else(|e| fatal!("Could not download SDK: {}", e.message())),
+		Err(_) => clone_from_sources(config, &path),
 	};
+	repo.remote_set_url("origin", SDK_REPOSITORY).expect("Unable to set SDK remote");
 
-	info!("Updating SDK");
+	let tag = format!("refs/tags/v{}", version);
+	if repo.revparse_single(&tag).is_err() {
+		// The main SDK may be older than the version asked for
+		fetch_sdk(config, &repo, &[&format!("+{tag}:{tag}")]).unwrap_or(());
+	}
+	if repo.revparse_single(&tag).is_err() {
+		drop(repo);
+		fs::remove_dir_all(&path).unwrap_or(());
+		fatal!("Geode has no release v{}", version);
+	}
+	switch_to_ref(&repo, &tag);
+	update_submodules_recurse(&repo).expect("Unable to update submodules!");
 
-	// Initialize repository
-	let repo = Repository::open(Config::sdk_path())
-		.expect("Could not initialize local SDK repository");
+	match verify_sdk(&path) {
+		Ok(_) => done!("Installed SDK v{} at {}", version, path.display()),
+		Err(e) => fail!("The SDK at {} is incomplete: {}", path.display(), e),
+	}
+}
+
+fn list_versions() {
+	match Config::try_sdk_path() {
+		Ok(path) => match verify_sdk(&path) {
+			Ok(version) => println!("* v{} [ path = {} ]", version.to_string().bright_cyan(), path.display().to_string().bright_green()),
+			Err(e) => println!("* {} [ path = {} ]", e.red(), path.display().to_string().bright_green()),
+		},
+		Err(_) => info!("GEODE_SDK isn't set, install the SDK with `geode sdk install`"),
+	}
 
-	// Fetch
-	let mut remote = repo
-		.find_remote(repo.remotes().unwrap().iter().next().unwrap().unwrap())
-		.unwrap();
+	let mut versions = fs::read_dir(versioned_sdks_dir())
+		.map(|dir| dir.filter_map(|e| Some(e.ok()?.path())).collect::<Vec<_>>())
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|path| Some((verify_sdk(&path).ok()?, path)))
+		.collect::<Vec<_>>();
+	versions.sort();
+	for (version, path) in versions {
+		println!("  v{} [ path = {} ]", version.to_string().bright_cyan(), path.display().to_string().bright_green());
+	}
+}
+
+fn remove_version(config: &Config, version: &str) {
+	let version = parse_sdk_version(version);
+	let path = versioned_sdk_path(&version);
+	if !path.exists() {
+		fail!("SDK v{} isn't installed alongside the main SDK", version);
+		return;
+	}
+	if is_linked_sdk(config, &path) {
+		fail!("SDK v{} was registered with `geode sdk set-path`, so it isn't removed", version);
+		return;
+	}
+	fs::remove_dir_all(&path).unwrap_or_else(|e| fatal!("Unable to remove SDK: {}", e));
+	done!("Removed SDK v{}", version);
+
+	for profile in &config.profiles {
+		let profile = profile.borrow();
+		if profile.sdk_version == Some(version.to_string()) {
+			warn!(
+				"Profile '{}' used SDK v{}, set another with `geode profile set-sdk --profile {}`",
+				profile.name, version, profile.name
+			);
+		}
+	}
+}
+
+fn get_sdk_path() -> Option<PathBuf> {
+	if std::env::var("GEODE_SDK").is_ok() &&
+		Config::try_sdk_path().is_ok()
+	{
+		Some(Config::sdk_path())
+	}
+	else {
+		None
+	}
+}
 
+fn progress_fetch_options<'a>() -> FetchOptions<'a> {
 	let mut callbacks = RemoteCallbacks::new();
 	callbacks.sideband_progress(|x| {
 		print!(
@@ -307,50 +868,469 @@ fn update(config: &mut Config, branch: Option<Branch>) {
 		true
 	});
 
-	remote
-		.fetch(
-			&["main"],
-			Some(FetchOptions::new().remote_callbacks(callbacks)),
-			None,
-		)
-		.expect("Could not fetch latest update");
-
-	// Check if can fast-forward
-	let fetch_head = repo.find_reference("FETCH_HEAD").unwrap();
-	let fetch_commit = repo.reference_to_annotated_commit(&fetch_head).unwrap();
-
-	let merge_analysis = repo.merge_analysis(&[&fetch_commit]).unwrap().0;
-
-	if merge_analysis.is_up_to_date() {
-		switch_to_tag(config, &repo);
-
-		done!("SDK is up to date");
-	} else if !merge_analysis.is_fast_forward() {
-		fail!("Cannot update SDK, it has local changes");
-		info!(
-			"Go into the repository at {} and manually run `git pull`",
-			Config::sdk_path().to_str().unwrap()
-		);
-	} else {
-		// Change head and checkout
+	let mut fetch = FetchOptions::new();
+	fetch.remote_callbacks(callbacks);
+	fetch.proxy_options(proxy_options());
+	fetch
+}
 
-		switch_to_tag(config, &repo);
+/// Clone the SDK from the first of its mirrors, or GitHub, that works. It's
+/// cloned rather than downloaded as an archive, so git checks every object
+/// against its hash and there's no sum to verify
+fn clone_from_sources(config: &Config, path: &Path) -> Repository {
+	clone_from_urls(&config.download_sources(Download::Sdk), path, None)
+}
 
-		done!("Successfully updated SDK.");
+/// Clone the SDK from the first of `urls` that works, showing how far along
+/// it is in `progress` if given instead of git's own messages
+fn clone_from_urls(urls: &[String], path: &Path, progress: Option<&Progress>) -> Repository {
+	let task = progress.map(|p| p.add("SDK"));
+	for url in urls {
+		let fetch = match (progress, task) {
+			(Some(progress), Some(task)) => {
+				let mut callbacks = RemoteCallbacks::new();
+				callbacks.transfer_progress(move |stats| {
+					progress.update(task, stats.received_objects() as u64, Some(stats.total_objects() as u64));
+					true
+				});
+				let mut fetch = FetchOptions::new();
+				fetch.remote_callbacks(callbacks);
+				fetch.proxy_options(proxy_options());
+				fetch
+			}
+			_ => progress_fetch_options(),
+		};
+		match RepoBuilder::new().fetch_options(fetch).clone(url, path) {
+			Ok(repo) => {
+				// Local clones copy the objects without reporting transfers
+				if let (Some(progress), Some(task)) = (progress, task) {
+					progress.update(task, 1, Some(1));
+				}
+				return repo;
+			}
+			Err(e) => {
+				warn!("Unable to download the SDK from {}: {}", url, e.message());
+				fs::remove_dir_all(path).unwrap_or(());
+			}
+		}
 	}
+	fatal!("Could not download SDK");
 }
 
-fn switch_to_ref(repo: &Repository, name: &str) {
-	let (obj, refer) = repo.revparse_ext(name).unwrap();
-	repo.checkout_tree(&obj, None).expect("Unable to checkout tree");
-	match refer {
-		Some(gref) => repo.set_head(gref.name().unwrap()),
-		None => repo.set_head_detached(obj.id()),
-	}.expect("Failed to update head");
+/// Fetch into the SDK from its mirrors, falling back to its own remote,
+/// along with the tags since stable is the latest of them
+fn fetch_sdk(config: &Config, repo: &Repository, refspecs: &[&str]) -> Result<(), git2::Error> {
+	let mut mirrors = config.download_sources(Download::Sdk);
+	// GitHub, which is normally where the SDK's own remote points
+	mirrors.pop();
+
+	let own_remote = repo.remotes()?.iter().flatten().next().map(String::from);
+	let mut last_error = git2::Error::from_str("The SDK has no remote to update from");
+	for mirror in mirrors.iter().map(Some).chain([None]) {
+		let mut remote = match (mirror, &own_remote) {
+			(Some(url), _) => repo.remote_anonymous(url)?,
+			(None, Some(name)) => repo.find_remote(name)?,
+			(None, None) => break,
+		};
+		let mut fetch = progress_fetch_options();
+		fetch.download_tags(git2::AutotagOption::All);
+		match remote.fetch(refspecs, Some(&mut fetch), None) {
+			Ok(_) => return Ok(()),
+			Err(e) => {
+				warn!("Unable to fetch from {}: {}", remote.url().unwrap_or("the SDK's remote"), e.message());
+				last_error = e;
+			}
+		}
+	}
+	Err(last_error)
 }
 
-fn switch_to_tag(config: &mut Config, repo: &Repository) {
-	info!("Updating head");
+fn clone_sdk(config: &mut Config, path: &Path) {
+	info!("Downloading SDK");
+
+	let repo = clone_from_sources(config, path);
+
+	// update submodules, because for some reason
+	// Repository::update_submodules is private
+	update_submodules_recurse(&repo).expect("Unable to update submodules!");
+
+	switch_to_tag(config, &repo);
+}
+
+/// Binaries downloaded while the SDK was cloned, for the release it was
+/// expected to end up at
+struct PrefetchedBinaries {
+	tag: String,
+	zips: Vec<(PathBuf, Option<String>)>,
+}
+
+/// Clone the SDK while downloading the binaries for the release it should end
+/// up at, which is only certain once it's cloned, so the installed version
+/// has to be checked against `tag` before using them
+fn clone_with_binaries(
+	config: &mut Config,
+	path: &Path,
+	platforms: &[BinaryPlatform],
+	skip_checksum: bool,
+) -> Option<PrefetchedBinaries> {
+	let tag = if config.sdk_nightly {
+		"nightly".to_string()
+	} else {
+		match latest_release() {
+			Ok(version) => format!("v{}", version),
+			Err(e) => {
+				warn!("Unable to find the latest release ({}), so the binaries are downloaded after the SDK", e);
+				clone_sdk(config, path);
+				return None;
+			}
+		}
+	};
+
+	let assets = binaries_assets(config, &tag, platforms, skip_checksum, None);
+	let downloads = geode_root().join("downloads");
+	fs::create_dir_all(&downloads).expect("Unable to create downloads directory");
+
+	info!("Downloading SDK and binaries for {}", tag);
+	let progress = Progress::new();
+	let urls = config.download_sources(Download::Sdk);
+	let (repo, zips) = std::thread::scope(|scope| {
+		let clone = scope.spawn(|| clone_from_urls(&urls, path, Some(&progress)));
+		let zips = download_binaries(config, &tag, &assets, &downloads, &progress);
+		(clone.join().unwrap(), zips)
+	});
+	progress.finish();
+	let zips = zips.unwrap_or_else(|e| fatal!("{}", e));
+
+	update_submodules_recurse(&repo).expect("Unable to update submodules!");
+	switch_to_tag(config, &repo);
+
+	Some(PrefetchedBinaries {
+		tag,
+		zips: zips.into_iter().zip(assets).map(|(zip, (_, _, sum))| (zip, sum)).collect(),
+	})
+}
+
+/// Put the SDK from a local archive or directory at `path`. Archives of the
+/// repository like GitHub's have everything in one top-level folder, which
+/// is unwrapped. Archives are checked against their sum like the binaries
+/// from `install-binaries --from` are, which also covers the binaries in them
+fn install_from_local(from: &Path, path: &Path, skip_checksum: bool) {
+	if from.is_dir() {
+		// Nothing to check a loose directory against, sources are fine but
+		// binaries aren't installed unverified without being asked to
+		if from.join("bin").is_dir() {
+			if !skip_checksum {
+				fatal!(
+					"The binaries in {} can't be verified as it's a directory. \
+					Use an archive with a SHA-256 sum, or --skip-checksum to install it anyway",
+					from.join("bin").display()
+				);
+			}
+			warn!("Skipping checksum verification of {}", from.join("bin").display());
+		}
+		crate::file::copy_dir_recursive(&from.to_path_buf(), &path.to_path_buf())
+			.unwrap_or_else(|e| fatal!("Unable to copy SDK from {}: {}", from.display(), e));
+		return;
+	}
+
+	match local_sha256(from) {
+		Some(expected) => verify_sha256(from, &expected, false),
+		None if skip_checksum => warn!("Skipping checksum verification of {}", from.display()),
+		None => fatal!(
+			"There's no SHA-256 sum for {} in a .sha256 or SHA256SUMS file next \
+			to it, so it can't be verified. Use --skip-checksum to install it anyway",
+			from.display()
+		),
+	}
+	let file = fs::File::open(from)
+		.unwrap_or_else(|e| fatal!("Unable to read {}: {}", from.display(), e));
+	let mut zip = zip::ZipArchive::new(file)
+		.unwrap_or_else(|e| fatal!("{} isn't a valid ZIP archive: {}", from.display(), e));
+	zip.extract(path).expect("Unable to extract SDK archive");
+
+	let entries = fs::read_dir(path).expect("Unable to read extracted SDK")
+		.flatten()
+		.map(|e| e.path())
+		.collect::<Vec<_>>();
+	if let [inner] = entries.as_slice() {
+		if inner.is_dir() && !path.join("VERSION").exists() {
+			let temp = path.with_extension("unpacking");
+			fs::rename(inner, &temp).expect("Unable to unpack SDK archive");
+			fs::remove_dir(path).expect("Unable to unpack SDK archive");
+			fs::rename(&temp, path).expect("Unable to unpack SDK archive");
+		}
+	}
+}
+
+fn install(
+	config: &mut Config,
+	path: PathBuf,
+	force: bool,
+	from: Option<PathBuf>,
+	binaries: Vec<BinaryPlatform>,
+	skip_checksum: bool,
+) {
+	let parent = path.parent().unwrap();
+
+	if !force && std::env::var("GEODE_SDK").is_ok() {
+		if Config::try_sdk_path().is_ok() {
+			fail!("SDK is already installed at {}", Config::sdk_path().display());
+			info!("Use --reinstall if you want to remove the existing installation");
+			return;
+		} else {
+			let env_sdk_path = std::env::var("GEODE_SDK").unwrap();
+			info!("GEODE_SDK ({env_sdk_path}) is already set, but seems to point to an invalid sdk installation.");
+			if !crate::logging::ask_confirm("Do you wish to proceed?", true) {
+				fatal!("Aborting");
+			}
+		}
+	} else if !parent.exists() {
+		fail!("Parent folder {} does not exist", parent.display());
+		return;
+	} else if path.exists() {
+		fail!("Target path already exists");
+		return;
+	}
+
+	// A new install follows its branch again
+	config.sdk_commit = None;
+
+	let mut prefetched = None;
+
+	if let Some(from) = &from {
+		if !from.exists() {
+			fatal!("{} doesn't exist", from.display());
+		}
+		info!("Installing SDK from {}", from.display());
+		install_from_local(from, &path, skip_checksum);
+	} else if binaries.is_empty() {
+		clone_sdk(config, &path);
+	} else {
+		prefetched = clone_with_binaries(config, &path, &binaries, skip_checksum);
+	}
+
+	let version = match verify_sdk(&path) {
+		Ok(version) => {
+			info!("Verified SDK v{}", version);
+			version
+		}
+		Err(e) => {
+			fail!("The SDK at {} is incomplete: {}", path.display(), e);
+			if from.is_some() {
+				fs::remove_dir_all(&path).ok();
+				info!("Nothing was installed, check that the archive has the whole SDK");
+			} else {
+				info!("Try again with `geode sdk install --reinstall`");
+			}
+			return;
+		}
+	};
+
+	// Geode CLI finds it even if the environment variable can't be set
+	config.sdk_path = Some(path.canonicalize().unwrap_or_else(|_| path.clone()));
+	config.sdk_linked = false;
+
+	// set GEODE_SDK environment variable;
+	let env_set = set_sdk_env(&path);
+	if env_set {
+		info!("Set GEODE_SDK environment variable automatically");
+	} else {
+		warn!(
+			"Unable to set the GEODE_SDK enviroment variable to {} automatically",
+			path.to_str().unwrap()
+		);
+		print_sdk_env_instructions(&path);
+	}
+
+	done!("Successfully installed SDK");
+	if env_set {
+		info!("Please restart your command line to have the GEODE_SDK enviroment variable set.");
+	}
+	if from.is_some() && path.join("bin").is_dir() {
+		info!("Pre-built binaries from the archive were installed too");
+	} else if binaries.is_empty() {
+		info!("Use `geode sdk install-binaries` to install pre-built binaries");
+	}
+
+	// Done last and saved first, so the SDK stays set up even if these fail
+	if !binaries.is_empty() {
+		config.save();
+		let tag = if config.sdk_nightly { "nightly".to_string() } else { format!("v{}", version) };
+		let target_dir = release_binaries_dir(&path, config.sdk_nightly, &version);
+		match prefetched {
+			Some(prefetched) if prefetched.tag == tag => {
+				extract_verified_binaries(&prefetched.zips, &target_dir);
+				done!("Binaries installed to {}", target_dir.display());
+			}
+			other => {
+				if let Some(prefetched) = other {
+					info!("The SDK ended up at {} rather than {}, downloading its binaries", tag, prefetched.tag);
+					for (zip, _) in prefetched.zips {
+						fs::remove_file(zip).unwrap_or(());
+					}
+				}
+				install_release_binaries(config, &tag, &target_dir, &binaries, skip_checksum, None);
+			}
+		}
+	}
+}
+
+fn update(config: &mut Config, branch: Option<Branch>, commit: Option<String>) {
+	// Switch branch if necessary
+	match branch {
+		Some(Branch::Nightly) => {
+			info!("Switching to nightly");
+			config.sdk_nightly = true;
+		}
+		Some(Branch::Stable) => {
+			info!("Switching to stable");
+			config.sdk_nightly = false;
+		}
+		None => {}
+	};
+	if commit.is_none() {
+		if let Some(pinned) = config.sdk_commit.take() {
+			info!("Unpinning the SDK from {}", &pinned[..7.min(pinned.len())]);
+		}
+	}
+
+	info!("Updating SDK");
+
+	// Initialize repository
+	let sdk_path = Config::sdk_path();
+	let repo = Repository::open(&sdk_path)
+		.expect("Could not initialize local SDK repository");
+	let old_version = verify_sdk(&sdk_path).ok();
+	let old_head = repo.head().ok().and_then(|h| h.target());
+
+	fetch_sdk(config, &repo, &["main"])
+		.unwrap_or_else(|e| fatal!("Could not fetch latest update: {}", e.message()));
+
+	// Check if main can fast-forward. FETCH_HEAD lists the tags as well, so
+	// look for main in it
+	let mut fetched_main = None;
+	repo.fetchhead_foreach(|name, _, id, _| {
+		if name == "refs/heads/main" {
+			fetched_main = Some(*id);
+		}
+		true
+	}).expect("Unable to read fetched SDK");
+	let fetch_commit = fetched_main
+		.and_then(|id| repo.find_annotated_commit(id).ok())
+		.unwrap_or_else(|| fatal!("The SDK's remote has no main branch"));
+
+	match repo.find_reference("refs/heads/main") {
+		Ok(mut main) => {
+			let merge_analysis = repo.merge_analysis_for_ref(&main, &[&fetch_commit]).unwrap().0;
+			if merge_analysis.is_fast_forward() {
+				// Moving main while it's checked out would leave the files
+				// behind, so let switch_to_tag do the checkout
+				if let Some(head) = old_head {
+					repo.set_head_detached(head).expect("Failed to update head");
+				}
+				main.set_target(fetch_commit.id(), "geode sdk update: fast-forward")
+					.expect("Unable to fast-forward SDK");
+			} else if !merge_analysis.is_up_to_date() {
+				fail!("Cannot update SDK, it has local changes");
+				info!(
+					"Go into the repository at {} and manually run `git pull`",
+					sdk_path.to_str().unwrap()
+				);
+				return;
+			}
+		}
+		Err(_) => {
+			repo.reference("refs/heads/main", fetch_commit.id(), false, "geode sdk update: create main")
+				.expect("Unable to create main branch of SDK");
+		}
+	}
+
+	if let Some(commit) = commit {
+		let find = |repo: &Repository| repo.revparse_single(&commit).and_then(|o| o.peel_to_commit()).ok().map(|c| c.id());
+		let mut found = find(&repo);
+		// Commits only on other branches can still be fetched by their full hash
+		if found.is_none() && commit.len() == 40 {
+			fetch_sdk(config, &repo, &[commit.as_str()]).unwrap_or(());
+			found = find(&repo);
+		}
+		let Some(id) = found else {
+			fatal!("The SDK has no commit {}", commit);
+		};
+		config.sdk_commit = Some(id.to_string());
+	}
+
+	switch_to_tag(config, &repo);
+
+	let new_version = verify_sdk(&sdk_path).unwrap_or_else(|e| fatal!("SDK is incomplete after updating: {}", e));
+	let new_head = repo.head().ok().and_then(|h| h.target());
+	let short = |id: Option<git2::Oid>| id.map(|id| id.to_string()[..7].to_string()).unwrap_or_default();
+	match old_version {
+		Some(old) if old != new_version => {
+			done!("Updated SDK from v{} to v{}", old, new_version);
+			print_changelog(&sdk_path, &old, &new_version);
+		}
+		// Nightlies often don't change the version
+		_ if old_head != new_head => done!(
+			"Updated SDK from {} to {} (v{})",
+			short(old_head), short(new_head), new_version
+		),
+		_ => done!("SDK is up to date (v{})", new_version),
+	}
+}
+
+/// Print the entries of the SDK's CHANGELOG.md for versions after `old` up
+/// to `new`, so breaking changes are known before rebuilding
+fn print_changelog(sdk_path: &Path, old: &Version, new: &Version) {
+	let Ok(changelog) = fs::read_to_string(sdk_path.join("CHANGELOG.md")) else {
+		info!("See https://github.com/geode-sdk/geode/releases for what changed");
+		return;
+	};
+
+	// Sections start with headings like `## v2.0.0-beta.1`
+	let mut in_range = false;
+	let mut printed = false;
+	for line in changelog.lines() {
+		if let Some(heading) = line.strip_prefix("## ") {
+			in_range = heading.split_whitespace().next()
+				.and_then(|v| Version::parse(v.trim_matches(['[', ']']).trim_start_matches('v')).ok())
+				.is_some_and(|v| v > *old && v <= *new);
+			if in_range {
+				if !printed {
+					info!("Changes since v{}:", old);
+				}
+				println!("\n{}", heading.bright_cyan());
+				printed = true;
+			}
+		} else if in_range && !line.trim().is_empty() {
+			if line.to_lowercase().contains("breaking") {
+				println!("{}", line.bright_yellow());
+			} else {
+				println!("{}", line);
+			}
+		}
+	}
+	if printed {
+		println!();
+	}
+}
+
+fn switch_to_ref(repo: &Repository, name: &str) {
+	let (obj, refer) = repo.revparse_ext(name).unwrap();
+	repo.checkout_tree(&obj, None).expect("Unable to checkout tree");
+	match refer {
+		Some(gref) => repo.set_head(gref.name().unwrap()),
+		None => repo.set_head_detached(obj.id()),
+	}.expect("Failed to update head");
+}
+
+fn switch_to_tag(config: &mut Config, repo: &Repository) {
+	info!("Updating head");
+
+	if let Some(commit) = &config.sdk_commit {
+		switch_to_ref(repo, commit);
+		done!("Pinned SDK to commit {}", &commit[..7.min(commit.len())]);
+		return;
+	}
 
 	if config.sdk_nightly {
 		switch_to_ref(repo, "refs/heads/main");
@@ -382,23 +1362,261 @@ fn switch_to_tag(config: &mut Config, repo: &Repository) {
 	done!("Updated head to v{}", latest_version.unwrap());
 }
 
-fn install_binaries(config: &mut Config) {
-	update(config, None);
-	let release_tag: String;
-	let target_dir: PathBuf;
-	if config.sdk_nightly {
-		info!("Installing nightly binaries");
-		release_tag = "nightly".into();
-		target_dir = Config::sdk_path().join("bin/nightly");
+/// Platforms Geode releases prebuilt loader binaries for
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum BinaryPlatform {
+	Win,
+	Mac,
+	Android32,
+	Android64,
+}
+
+impl BinaryPlatform {
+	fn current() -> Option<BinaryPlatform> {
+		if cfg!(windows) {
+			Some(BinaryPlatform::Win)
+		} else if cfg!(target_os = "macos") {
+			Some(BinaryPlatform::Mac)
+		} else {
+			None
+		}
+	}
+
+	/// How the release assets for the platform end
+	fn asset_suffix(self) -> &'static str {
+		match self {
+			BinaryPlatform::Win => "win.zip",
+			BinaryPlatform::Mac => "mac.zip",
+			BinaryPlatform::Android32 => "android32.zip",
+			BinaryPlatform::Android64 => "android64.zip",
+		}
+	}
+
+	/// What the SDK's codegen calls the platform
+	fn codegen_name(self, sdk_version: &Version) -> &'static str {
+		match self {
+			BinaryPlatform::Win if is_win32(sdk_version) => "Win32",
+			BinaryPlatform::Win => "Win64",
+			BinaryPlatform::Mac => "MacOS",
+			BinaryPlatform::Android32 => "Android32",
+			BinaryPlatform::Android64 => "Android64",
+		}
+	}
+
+	/// Key for the platform in mod.json's `gd` object
+	fn mod_json_key(self) -> &'static str {
+		match self {
+			BinaryPlatform::Win => "win",
+			BinaryPlatform::Mac => "mac",
+			BinaryPlatform::Android32 | BinaryPlatform::Android64 => "android",
+		}
+	}
+
+	fn name(self) -> &'static str {
+		match self {
+			BinaryPlatform::Win => "win",
+			BinaryPlatform::Mac => "mac",
+			BinaryPlatform::Android32 => "android32",
+			BinaryPlatform::Android64 => "android64",
+		}
+	}
+}
+
+/// The SDK the current directory builds with and its version: the one its
+/// mod.json targets if that's installed alongside, otherwise the current
+/// profile's, otherwise the main SDK
+fn current_sdk(config: &Config) -> (PathBuf, Version) {
+	let path = std::env::current_dir()
+		.ok()
+		.and_then(|dir| try_parse_mod_info(&dir).ok())
+		.map(|info| versioned_sdk_path(&info.geode))
+		.filter(|path| path.join("VERSION").exists())
+		.or_else(|| {
+			let profile = config.get_profile(&config.current_profile)?;
+			profile_sdk(&profile.borrow()).map(|(path, _)| path)
+		})
+		.unwrap_or_else(Config::sdk_path);
+	let version = verify_sdk(&path).unwrap_or_else(|e| fatal!("The SDK at {} is incomplete: {}", path.display(), e));
+	(path, version)
+}
+
+/// Whether binaries for an SDK come from the nightly release, which is only
+/// the case for the main SDK on the nightly branch
+fn is_nightly(config: &Config, sdk_path: &Path) -> bool {
+	config.sdk_nightly && Config::try_sdk_path().is_ok_and(|main| main == sdk_path)
+}
+
+/// Where the prebuilt binaries for an SDK go
+fn binaries_dir(config: &Config, sdk_path: &Path, version: &Version) -> PathBuf {
+	release_binaries_dir(sdk_path, is_nightly(config, sdk_path), version)
+}
+
+fn release_binaries_dir(sdk_path: &Path, nightly: bool, version: &Version) -> PathBuf {
+	if nightly {
+		sdk_path.join("bin/nightly")
 	} else {
-		let ver = get_version();
-		info!("Installing binaries for {}", ver);
-		release_tag = format!("v{}", ver);
 		// remove any -beta or -alpha suffixes as geode cmake doesn't care about those
-		let mut stripped_ver = ver.clone();
+		let mut stripped_ver = version.clone();
 		stripped_ver.pre = Prerelease::EMPTY;
-		target_dir = Config::sdk_path().join(format!("bin/{}", stripped_ver));
+		sdk_path.join(format!("bin/{}", stripped_ver))
+	}
+}
+
+/// Find the SHA-256 sum published for an asset, either the digest GitHub
+/// lists for it or a line in a `<asset>.sha256` or SHA256SUMS file in the
+/// same release
+fn published_sha256(assets: &[GithubReleaseAsset], asset: &GithubReleaseAsset) -> Option<String> {
+	if let Some(hash) = asset.digest.as_ref().and_then(|d| d.strip_prefix("sha256:")) {
+		return Some(hash.to_lowercase());
+	}
+
+	let sums = assets.iter().find(|a| {
+		a.name == format!("{}.sha256", asset.name)
+			|| a.name.eq_ignore_ascii_case("SHA256SUMS")
+			|| a.name.eq_ignore_ascii_case("SHA256SUMS.txt")
+	})?;
+	let text = reqwest::blocking::get(&sums.browser_download_url)
+		.and_then(|res| res.error_for_status())
+		.and_then(|res| res.text())
+		.unwrap_or_else(|e| fatal!("Unable to download {}: {}", sums.name, e));
+
+	parse_sha256_sums(&text, &asset.name)
+}
+
+/// Find the SHA-256 sum of a file someone else downloaded, from a
+/// `<file>.sha256` or SHA256SUMS file next to it
+fn local_sha256(file: &Path) -> Option<String> {
+	let name = file.file_name()?.to_str()?;
+	let mut sums_file = file.to_path_buf().into_os_string();
+	sums_file.push(".sha256");
+	[PathBuf::from(sums_file), file.with_file_name("SHA256SUMS"), file.with_file_name("SHA256SUMS.txt")]
+		.iter()
+		.filter_map(|p| fs::read_to_string(p).ok())
+		.find_map(|text| parse_sha256_sums(&text, name))
+}
+
+fn parse_sha256_sums(text: &str, name: &str) -> Option<String> {
+	// Lines are `<hash>  <file>`, or just the hash in a file for one asset
+	text.lines().find_map(|line| {
+		let mut parts = line.split_whitespace();
+		let hash = parts.next()?;
+		match parts.next().map(|f| f.trim_start_matches('*')) {
+			Some(file) if file != name => None,
+			_ => Some(hash.to_lowercase()),
+		}
+	})
+}
+
+/// Fail if a file doesn't match its published sum. Temporary downloads are
+/// removed so nothing unverified is left around
+fn verify_sha256(file: &Path, expected: &str, temporary: bool) {
+	let name = file.file_name().unwrap_or_default().to_string_lossy();
+	let hash = sha256::digest_file(file)
+		.unwrap_or_else(|e| fatal!("Unable to read {}: {}", file.display(), e));
+	if hash != expected {
+		if temporary {
+			fs::remove_file(file).ok();
+		}
+		fatal!(
+			"Checksum mismatch for {}: expected {} but got {}. The download \
+			is corrupted or was tampered with, nothing was installed",
+			name, expected, hash
+		);
 	}
+	info!("Verified SHA-256 sum of {}", name);
+}
+
+fn extract_binaries(zip_path: &Path, target_dir: &Path) {
+	fs::create_dir_all(target_dir).expect("Unable to create directory for binaries");
+	let file = fs::File::open(zip_path).expect("Unable to read binaries ZIP");
+	let mut zip = zip::ZipArchive::new(file).expect("Binaries ZIP appears to be corrupted");
+	zip.extract(target_dir)
+		.expect("Unable to unzip binaries");
+}
+
+fn install_binaries(
+	config: &mut Config,
+	platforms: Vec<BinaryPlatform>,
+	skip_checksum: bool,
+	from: Option<PathBuf>,
+	checksums: Option<PathBuf>,
+) {
+	let checksums = checksums.map(|path| fs::read_to_string(&path)
+		.unwrap_or_else(|e| fatal!("Unable to read {}: {}", path.display(), e)));
+
+	let platforms = if platforms.is_empty() {
+		vec![BinaryPlatform::current().unwrap_or_else(|| {
+			fatal!("There are no loader binaries for this platform, pick one with --platform")
+		})]
+	} else {
+		platforms
+	};
+
+	// The binaries have to match the SDK's headers, so it's not updated here
+	let (sdk_path, version) = current_sdk(config);
+	let release_tag: String = if is_nightly(config, &sdk_path) {
+		info!("Installing nightly binaries");
+		"nightly".into()
+	} else {
+		info!("Installing binaries for {}", version);
+		format!("v{}", version)
+	};
+	let target_dir = binaries_dir(config, &sdk_path, &version);
+
+	if let Some(from) = from {
+		if !from.is_file() {
+			fatal!("{} isn't a file", from.display());
+		}
+		match local_sha256(&from) {
+			Some(expected) => verify_sha256(&from, &expected, false),
+			None if skip_checksum => warn!("Skipping checksum verification of {}", from.display()),
+			None => fatal!(
+				"There's no SHA-256 sum for {} in a .sha256 or SHA256SUMS file next \
+				to it, so it can't be verified. Use --skip-checksum to install it anyway",
+				from.display()
+			),
+		}
+		extract_binaries(&from, &target_dir);
+		done!("Binaries installed to {}", target_dir.display());
+		return;
+	}
+
+	install_release_binaries(config, &release_tag, &target_dir, &platforms, skip_checksum, checksums.as_deref());
+}
+
+/// Download, check and extract the binaries of a release for several
+/// platforms, fetching them all at the same time
+fn install_release_binaries(
+	config: &Config,
+	release_tag: &str,
+	target_dir: &Path,
+	platforms: &[BinaryPlatform],
+	skip_checksum: bool,
+	checksums: Option<&str>,
+) {
+	let assets = binaries_assets(config, release_tag, platforms, skip_checksum, checksums);
+	fs::create_dir_all(target_dir).expect("Unable to create directory for binaries");
+
+	let progress = Progress::new();
+	let zips = download_binaries(config, release_tag, &assets, target_dir, &progress);
+	progress.finish();
+	let zips = zips.unwrap_or_else(|e| fatal!("{}", e));
+
+	let zips = zips.into_iter().zip(assets).map(|(zip, (_, _, sum))| (zip, sum)).collect::<Vec<_>>();
+	extract_verified_binaries(&zips, target_dir);
+	done!("Binaries installed to {}", target_dir.display());
+}
+
+/// Find the file name and SHA-256 sum of the binaries of a release for each
+/// platform. Sums come from `checksums` if given, otherwise from GitHub, so
+/// a mirror can't vouch for its own files
+fn binaries_assets(
+	config: &Config,
+	release_tag: &str,
+	platforms: &[BinaryPlatform],
+	skip_checksum: bool,
+	checksums: Option<&str>,
+) -> Vec<(BinaryPlatform, String, Option<String>)> {
 	let url = format!(
 		"https://api.github.com/repos/geode-sdk/geode/releases/tags/{}",
 		release_tag
@@ -407,54 +1625,140 @@ fn install_binaries(config: &mut Config) {
 	let mut headers = HeaderMap::new();
 	headers.insert(USER_AGENT, HeaderValue::from_static("github_api/1.0"));
 
-	let res = reqwest::blocking::Client::new()
+	let sources = config.download_sources(Download::Binaries);
+	let release = reqwest::blocking::Client::new()
 		.get(&url)
 		.headers(headers)
 		.send()
-		.expect("Unable to get download info from GitHub")
-		.json::<GithubReleaseResponse>()
-		.unwrap_or_else(|_| fatal!(
-			"It appears that \"{}\" is not a valid Geode release",
-			release_tag
-		));
-
-	let mut target_url: Option<String> = None;
-	for asset in res.assets {
-		#[cfg(target_os = "windows")]
-		if asset.name.to_lowercase().contains("win") {
-			target_url = Some(asset.browser_download_url);
-			info!("Found binaries for platform Windows");
-			break;
+		.and_then(|res| res.error_for_status());
+
+	// Mirrors only have the files, so without GitHub's API the names are
+	// guessed from how releases name them
+	let assets = match release {
+		Ok(res) => {
+			let res = res.json::<GithubReleaseResponse>()
+				.unwrap_or_else(|_| fatal!(
+					"It appears that \"{}\" is not a valid Geode release",
+					release_tag
+				));
+			platforms.iter().map(|&platform| {
+				let Some(asset) = res.assets
+					.iter()
+					.find(|asset| asset.name.to_lowercase().ends_with(platform.asset_suffix()))
+				else {
+					fatal!("Release {} has no binaries for {:?}", release_tag, platform);
+				};
+				let sum = match checksums {
+					Some(text) => parse_sha256_sums(text, &asset.name),
+					None => published_sha256(&res.assets, asset),
+				};
+				(platform, asset.name.clone(), sum)
+			})
+			.collect::<Vec<_>>()
+		}
+		Err(e) if e.status().is_some_and(|s| s == reqwest::StatusCode::NOT_FOUND) => {
+			fatal!("It appears that \"{}\" is not a valid Geode release", release_tag)
 		}
+		Err(e) if sources.len() > 1 => {
+			warn!("Unable to get download info from GitHub ({}), trying the mirrors", e);
+			platforms.iter().map(|&platform| {
+				let name = format!("geode-{}-{}", release_tag, platform.asset_suffix());
+				let sum = checksums.and_then(|text| parse_sha256_sums(text, &name));
+				if sum.is_none() && !skip_checksum {
+					fatal!(
+						"GitHub can't be reached for the SHA-256 sum of {}, and one from \
+						the mirror would prove nothing. Pass a SHA256SUMS file with \
+						--checksums, or use --skip-checksum to install it anyway",
+						name
+					);
+				}
+				(platform, name, sum)
+			})
+			.collect()
+		}
+		Err(e) => fatal!("Unable to get download info from GitHub: {}", e),
+	};
 
-		#[cfg(target_os = "macos")]
-		if asset.name.to_lowercase().contains("mac") {
-			target_url = Some(asset.browser_download_url);
-			info!("Found binaries for platform MacOS");
-			break;
+	for (_, name, sum) in &assets {
+		info!("Found binaries {}", name);
+		if sum.is_none() && !skip_checksum {
+			fatal!(
+				"There's no SHA-256 sum for {} in {}, so the download can't be \
+				verified. Use --skip-checksum to install it anyway",
+				name,
+				if checksums.is_some() { "the --checksums file".to_string() } else { format!("release {}", release_tag) }
+			);
 		}
 	}
+	assets
+}
 
-	assert!(target_url.is_some(), "No binaries found for current platform!");
-
-	fs::create_dir_all(&target_dir).expect("Unable to create directory for binaries");
-
-	info!("Downloading");
-
-	let temp_zip = target_dir.join("temp.zip");
-	download_url(target_url.unwrap(), &temp_zip).expect("Downloading binaries failed");
-
-	let file = fs::File::open(&temp_zip).expect("Unable to read downloaded ZIP");
-	let mut zip = zip::ZipArchive::new(file).expect("Downloaded ZIP appears to be corrupted");
-	zip.extract(target_dir)
-		.expect("Unable to unzip downloaded binaries");
-
-	fs::remove_file(temp_zip).expect("Unable to clean up downloaded ZIP");
+/// Download the binaries into `dir` at the same time, each from the first of
+/// the mirrors, or GitHub, that works. Returns the ZIPs in the same order
+fn download_binaries(
+	config: &Config,
+	release_tag: &str,
+	assets: &[(BinaryPlatform, String, Option<String>)],
+	dir: &Path,
+	progress: &Progress,
+) -> Result<Vec<PathBuf>, String> {
+	let sources = config.download_sources(Download::Binaries);
+	let results = std::thread::scope(|scope| {
+		let downloads = assets
+			.iter()
+			.map(|(platform, name, _)| {
+				let task = progress.add(platform.name());
+				let sources = &sources;
+				scope.spawn(move || {
+					let zip = dir.join(name);
+					let mut errors = Vec::new();
+					for base in sources {
+						let url = format!("{}/{}/{}", base.trim_end_matches('/'), release_tag, name);
+						match download_url_with_progress(url, &zip, &|done, total| progress.update(task, done, total)) {
+							Ok(_) => return (zip, errors, true),
+							Err(e) => errors.push(format!("Unable to download {} from {}: {}", name, base, e)),
+						}
+					}
+					(zip, errors, false)
+				})
+			})
+			.collect::<Vec<_>>();
+		downloads.into_iter().map(|d| d.join().unwrap()).collect::<Vec<_>>()
+	});
+	progress.finish();
+
+	// Failures are only shown now so they don't get mixed into the progress
+	let mut zips = Vec::new();
+	let mut failed = false;
+	for (zip, errors, downloaded) in results {
+		for error in errors {
+			warn!("{}", error);
+		}
+		failed |= !downloaded;
+		zips.push(zip);
+	}
+	if failed {
+		return Err("Downloading binaries failed. Run the command again to continue the download".into());
+	}
+	Ok(zips)
+}
 
-	done!("Binaries installed");
+/// Check downloaded binaries against their sums and extract them, removing
+/// the downloads
+fn extract_verified_binaries(zips: &[(PathBuf, Option<String>)], target_dir: &Path) {
+	for (zip, sum) in zips {
+		match sum {
+			Some(expected) => verify_sha256(zip, expected, true),
+			None => warn!("Skipping checksum verification of {}", zip.file_name().unwrap().to_string_lossy()),
+		}
+	}
+	for (zip, _) in zips {
+		extract_binaries(zip, target_dir);
+		fs::remove_file(zip).expect("Unable to clean up downloaded ZIP");
+	}
 }
 
-fn set_sdk_path(path: PathBuf, do_move: bool) {
+fn set_sdk_path(config: &mut Config, path: PathBuf, do_move: bool) {
 	if do_move {
 		let old = std::env::var("GEODE_SDK").map(PathBuf::from)
 			.expect("Cannot locate SDK.");
@@ -469,19 +1773,644 @@ fn set_sdk_path(path: PathBuf, do_move: bool) {
 		assert!(!path.exists(), "Cannot move SDK to existing path {}", path.to_str().unwrap());
 
 		fs::rename(old, &path).expect("Unable to move SDK");
-	} else {
-		assert!(path.exists(), "Cannot set SDK path to nonexistent directory {}", path.to_str().unwrap());
-		assert!(path.is_dir(), "Cannot set SDK path to non-directory {}", path.to_str().unwrap());
-		assert!(path.join("VERSION").exists(), "{} is either malformed or not a Geode SDK installation", path.to_str().unwrap());
+	} else if !path.is_dir() {
+		fatal!("Cannot set SDK path to {}, it isn't a directory", path.display());
 	}
 
+	// Checkouts of the SDK being worked on can be anywhere, as long as they
+	// have what building mods needs
+	let path = path.canonicalize().expect("Unable to resolve SDK path");
+	let version = verify_sdk(&path)
+		.unwrap_or_else(|e| fatal!("{} doesn't look like a Geode SDK: {}", path.display(), e));
+	config.sdk_path = Some(path.clone());
+	config.sdk_linked = true;
+
 	if set_sdk_env(&path) {
-		done!("Successfully set SDK path to {}", path.to_str().unwrap());
+		done!("Successfully set SDK path to {} (v{})", path.to_str().unwrap(), version);
 	} else {
-		fatal!("Unable to change SDK path");
+		done!(
+			"Geode CLI will use the SDK at {} (v{}) when GEODE_SDK isn't set",
+			path.to_str().unwrap(), version
+		);
+		warn!("Unable to set the GEODE_SDK enviroment variable automatically for other tools");
+		print_sdk_env_instructions(&path);
+	}
+}
+
+/// Latest release of Geode on GitHub
+fn latest_release() -> Result<Version, String> {
+	#[derive(Deserialize)]
+	struct Release {
+		tag_name: String,
+	}
+
+	let mut headers = HeaderMap::new();
+	headers.insert(USER_AGENT, HeaderValue::from_static("github_api/1.0"));
+	let release = reqwest::blocking::Client::new()
+		.get("https://api.github.com/repos/geode-sdk/geode/releases/latest")
+		.headers(headers)
+		.send()
+		.and_then(|r| r.error_for_status())
+		.and_then(|r| r.json::<Release>())
+		.map_err(|e| e.to_string())?;
+	Version::parse(release.tag_name.trim_start_matches('v'))
+		.map_err(|e| format!("'{}' is not a version: {}", release.tag_name, e))
+}
+
+/// Whether the loader in the current profile is the one from the prebuilt
+/// binaries of the profile's SDK
+fn check_profile_loader(config: &Config) {
+	let Some(profile) = config.get_profile(&config.current_profile) else {
+		info!("No profile set up, so the loader can't be checked");
+		return;
+	};
+	let profile = profile.borrow();
+
+	let Some((sdk_path, version)) = profile_sdk(&profile) else {
+		return;
+	};
+	if profile.sdk_version == Some(version.to_string()) {
+		info!("Profile '{}' uses SDK v{}", profile.name, version);
+	}
+	let bin_dir = binaries_dir(config, &sdk_path, &version);
+	let Ok(entries) = fs::read_dir(&bin_dir) else {
+		info!("No prebuilt binaries installed for SDK v{}, install them with `geode sdk install-binaries`", version);
+		return;
+	};
+	let loaders = entries
+		.filter_map(|e| Some(e.ok()?.path()))
+		.filter(|p| p.file_stem().is_some_and(|s| s == "Geode")
+			&& p.extension().is_some_and(|e| e == "dll" || e == "dylib"))
+		.collect::<Vec<_>>();
+
+	let hash = |path: &Path| fs::read(path).ok().map(|data| hex::encode(Sha3_256::digest(data)));
+	for loader in loaders {
+		let name = loader.file_name().unwrap();
+		// macOS keeps the loader with GD's other libraries
+		let Some(installed) = [profile.gd_path.join(name), profile.gd_path.join("Frameworks").join(name)]
+			.into_iter()
+			.find(|p| p.exists())
+		else {
+			warn!("{} isn't installed in profile '{}'", name.to_string_lossy(), profile.name);
+			continue;
+		};
+		if hash(&installed) == hash(&loader) {
+			info!("{} in profile '{}' matches the SDK", name.to_string_lossy(), profile.name);
+		} else {
+			warn!(
+				"{} in profile '{}' is different from the SDK's, so mods built with it may not load",
+				name.to_string_lossy(), profile.name
+			);
+		}
 	}
 }
 
+/// The installed SDK's version and commit next to the latest release
+fn version_report(config: &Config) {
+	let version = get_version();
+	let head = Repository::open(Config::sdk_path())
+		.ok()
+		.and_then(|repo| repo.head().ok()?.target())
+		.map(|id| id.to_string());
+	let branch = match &config.sdk_commit {
+		Some(pinned) => format!("pinned to {}", &pinned[..7.min(pinned.len())]),
+		None if config.sdk_nightly => "nightly".into(),
+		None => "stable".into(),
+	};
+	// The pinned commit is what's checked out, unless someone moved it
+	let commit = head
+		.filter(|head| config.sdk_commit.as_ref() != Some(head))
+		.map(|head| format!(", {}", &head[..7]))
+		.unwrap_or_default();
+	info!("Geode SDK version: {} ({}{})", version, branch, commit);
+
+	match latest_release() {
+		Ok(latest) if latest > version => {
+			warn!("Latest release is v{}, update with `geode sdk update`", latest)
+		}
+		Ok(latest) => info!("Latest release is v{}, you're up to date", latest),
+		Err(e) => warn!("Unable to check the latest release: {}", e),
+	}
+
+	check_profile_loader(config);
+}
+
+/// Platforms `install-toolchain` can set up cross-compiling for
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum Toolchain {
+	Android,
+}
+
+/// Oldest Android version mods are built for. GD itself doesn't run on
+/// anything older
+const ANDROID_PLATFORM: &str = "android-23";
+
+/// Google's list of SDK packages, which has the NDK's downloads and their
+/// sums
+const ANDROID_REPOSITORY: &str = "https://dl.google.com/android/repository/repository2-3.xml";
+
+fn toolchains_dir() -> PathBuf {
+	geode_root().join("toolchains")
+}
+
+/// Check a folder is an NDK that can build mods, returning its version
+fn verify_ndk(path: &Path) -> Result<String, String> {
+	let properties = fs::read_to_string(path.join("source.properties"))
+		.map_err(|_| "source.properties is missing".to_string())?;
+	let revision = properties.lines()
+		.find_map(|l| l.strip_prefix("Pkg.Revision"))
+		.map(|r| r.trim_start_matches([' ', '=']).trim().to_string())
+		.ok_or("source.properties has no Pkg.Revision")?;
+	if !path.join("build/cmake/android.toolchain.cmake").exists() {
+		return Err("build/cmake/android.toolchain.cmake is missing".into());
+	}
+	let prebuilt = fs::read_dir(path.join("toolchains/llvm/prebuilt"))
+		.map_err(|_| "it has no prebuilt LLVM toolchain".to_string())?;
+	if !prebuilt.flatten().any(|host| host.path().join("bin").is_dir()) {
+		return Err("it has no prebuilt LLVM toolchain for any host".into());
+	}
+	Ok(revision)
+}
+
+/// Find the latest stable NDK for this host in Google's package list, as its
+/// version, download URL and SHA-1 sum
+fn find_ndk_download() -> (String, String, String) {
+	let host = if cfg!(windows) {
+		"windows"
+	} else if cfg!(target_os = "macos") {
+		"macosx"
+	} else {
+		"linux"
+	};
+	let xml = reqwest::blocking::get(ANDROID_REPOSITORY)
+		.and_then(|res| res.error_for_status())
+		.and_then(|res| res.text())
+		.unwrap_or_else(|e| fatal!("Unable to get the list of Android NDKs: {}", e));
+
+	let package = regex::Regex::new(r#"(?s)<remotePackage path="ndk;([^"]+)">(.*?)</remotePackage>"#).unwrap();
+	let archive = regex::Regex::new(r"(?s)<archive>(.*?)</archive>").unwrap();
+	let checksum = regex::Regex::new(r"<checksum[^>]*>([0-9a-fA-F]+)</checksum>").unwrap();
+	let url = regex::Regex::new(r"<url>([^<]+)</url>").unwrap();
+
+	package.captures_iter(&xml)
+		// channel-0 is stable, the others are betas and canaries
+		.filter(|pkg| pkg[2].contains(r#"<channelRef ref="channel-0"/>"#))
+		.filter_map(|pkg| {
+			let version = Version::parse(&pkg[1]).ok()?;
+			let archive = archive.captures_iter(&pkg[2])
+				.find(|a| a[1].contains(&format!("<host-os>{host}</host-os>")))?;
+			let sum = checksum.captures(&archive[1])?[1].to_lowercase();
+			let file = url.captures(&archive[1])?[1].to_string();
+			Some((version, file, sum))
+		})
+		.max_by(|a, b| a.0.cmp(&b.0))
+		.map(|(version, file, sum)| (
+			version.to_string(),
+			// URLs are relative to the list
+			reqwest::Url::parse(ANDROID_REPOSITORY).and_then(|base| base.join(&file))
+				.map(|url| url.to_string())
+				.unwrap_or(file),
+			sum
+		))
+		.unwrap_or_else(|| fatal!("Google doesn't list an Android NDK for this platform"))
+}
+
+fn download_ndk() -> PathBuf {
+	let (version, url, expected) = find_ndk_download();
+	let ndk_dir = toolchains_dir().join(format!("android-ndk-{}", version));
+	if verify_ndk(&ndk_dir).is_ok() {
+		info!("Android NDK {} is already installed", version);
+		return ndk_dir;
+	}
+
+	fs::create_dir_all(toolchains_dir()).expect("Unable to create toolchain directory");
+	let archive = toolchains_dir().join(format!("android-ndk-{}.zip", version));
+	info!("Downloading Android NDK {}", version);
+	download_url(url, &archive).unwrap_or_else(|e| fatal!(
+		"Downloading the NDK failed: {}. Run the command again to continue \
+		the download",
+		e
+	));
+
+	// Google only publishes SHA-1 sums for its packages
+	let hash = fs::read(&archive).map(|data| hex::encode(sha1::Sha1::digest(data)))
+		.expect("Unable to read downloaded NDK");
+	if hash != expected {
+		fs::remove_file(&archive).ok();
+		fatal!(
+			"Checksum mismatch for the NDK: expected {} but got {}. The download \
+			is corrupted or was tampered with, nothing was installed",
+			expected, hash
+		);
+	}
+	info!("Verified SHA-1 sum of the NDK");
+
+	info!("Extracting");
+	let unpack_dir = toolchains_dir().join("unpacking");
+	fs::remove_dir_all(&unpack_dir).unwrap_or(());
+	let file = fs::File::open(&archive).expect("Unable to read downloaded NDK");
+	zip::ZipArchive::new(file)
+		.and_then(|mut zip| zip.extract(&unpack_dir))
+		.unwrap_or_else(|e| fatal!("Unable to extract the NDK: {}", e));
+	// The archive has everything in a folder named after the release
+	let inner = fs::read_dir(&unpack_dir).expect("Unable to read extracted NDK")
+		.flatten()
+		.map(|e| e.path())
+		.find(|p| p.is_dir())
+		.unwrap_or_else(|| fatal!("The NDK archive is empty"));
+	fs::remove_dir_all(&ndk_dir).unwrap_or(());
+	fs::rename(inner, &ndk_dir).expect("Unable to move NDK into place");
+	fs::remove_dir_all(&unpack_dir).unwrap_or(());
+	fs::remove_file(&archive).unwrap_or(());
+
+	ndk_dir
+}
+
+/// Set up an Android NDK and write toolchain files that build for it, for
+/// `cmake -DCMAKE_TOOLCHAIN_FILE=...`
+fn install_android_toolchain(config: &mut Config, ndk: Option<PathBuf>) {
+	let ndk = match ndk {
+		Some(path) => path,
+		None => match std::env::var_os("ANDROID_NDK_ROOT").map(PathBuf::from) {
+			Some(path) if verify_ndk(&path).is_ok() => {
+				info!("Using the NDK from ANDROID_NDK_ROOT, pass --ndk to use another");
+				path
+			}
+			_ => download_ndk(),
+		},
+	};
+	let ndk = ndk.canonicalize()
+		.unwrap_or_else(|e| fatal!("Unable to find the NDK at {}: {}", ndk.display(), e));
+	let version = verify_ndk(&ndk)
+		.unwrap_or_else(|e| fatal!("{} isn't a usable Android NDK: {}", ndk.display(), e));
+	done!("Android NDK {} at {}", version, ndk.display());
+	config.android_ndk = Some(ndk.clone());
+
+	fs::create_dir_all(toolchains_dir()).expect("Unable to create toolchain directory");
+	let ndk_toolchain = ndk.join("build/cmake/android.toolchain.cmake");
+	for (name, abi) in [("android32", "armeabi-v7a"), ("android64", "arm64-v8a")] {
+		let path = toolchains_dir().join(format!("{}.cmake", name));
+		fs::write(&path, format!(
+			"# Written by `geode sdk install-toolchain android`\n\
+			set(ANDROID_ABI {abi})\n\
+			set(ANDROID_PLATFORM {ANDROID_PLATFORM})\n\
+			include(\"{}\")\n",
+			ndk_toolchain.to_string_lossy().replace('\\', "/")
+		)).unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+		info!("Toolchain for {} written to {}", abi, path.display());
+	}
+
+	done!("Android toolchain installed");
+	info!(
+		"Build for Android with `cmake -B build -DCMAKE_TOOLCHAIN_FILE={}`",
+		toolchains_dir().join("android64.cmake").display()
+	);
+	info!("Get the Android binaries with `geode sdk install-binaries --platform android64`");
+}
+
+/// Oldest CMake the SDK's build scripts work with
+const MIN_CMAKE_VERSION: Version = Version::new(3, 21, 0);
+
+/// First line of what a tool prints for `--version`
+fn tool_version(tool: &Path) -> Option<String> {
+	let output = std::process::Command::new(tool).arg("--version").output().ok()?;
+	let text = String::from_utf8_lossy(&output.stdout);
+	text.lines().next().map(|l| l.trim().to_string())
+}
+
+/// Version of a tool on PATH, or where it is if it doesn't say
+fn find_tool(name: &str) -> Option<String> {
+	which::which(name).ok()
+		.map(|path| tool_version(&path).unwrap_or_else(|| path.display().to_string()))
+}
+
+/// Look for a compiler that can build mods, returning what was found or how
+/// to get one
+#[cfg(windows)]
+fn find_compiler() -> Result<String, (String, &'static str)> {
+	if let Some(version) = find_tool("cl").or_else(|| find_tool("clang-cl")) {
+		return Ok(version);
+	}
+	// MSVC usually isn't on PATH outside a developer prompt, but CMake finds
+	// it through the Visual Studio installer
+	let vswhere = PathBuf::from(std::env::var("ProgramFiles(x86)").unwrap_or_default())
+		.join("Microsoft Visual Studio/Installer/vswhere.exe");
+	let msvc = std::process::Command::new(vswhere)
+		.args(["-latest", "-products", "*", "-requires",
+			"Microsoft.VisualStudio.Component.VC.Tools.x86.x64", "-property", "installationPath"])
+		.output()
+		.ok()
+		.map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+		.filter(|o| !o.is_empty());
+	match msvc {
+		Some(path) => Ok(format!("MSVC in {}", path)),
+		None => Err((
+			"No MSVC or clang-cl found".into(),
+			"Install Visual Studio or its Build Tools with the \"Desktop development with C++\" workload"
+		)),
+	}
+}
+
+#[cfg(target_os = "macos")]
+fn find_compiler() -> Result<String, (String, &'static str)> {
+	let tools = std::process::Command::new("xcode-select").arg("-p").output()
+		.is_ok_and(|o| o.status.success());
+	match find_tool("clang").filter(|_| tools) {
+		Some(version) => Ok(version),
+		None => Err((
+			"The Xcode command line tools aren't installed".into(),
+			"Install them with `xcode-select --install`"
+		)),
+	}
+}
+
+/// Mods are cross-compiled for Windows with clang-cl, or for Android with
+/// the NDK
+#[cfg(not(any(windows, target_os = "macos")))]
+fn find_compiler() -> Result<String, (String, &'static str)> {
+	let ndk = std::env::var_os("ANDROID_NDK_ROOT").map(PathBuf::from).filter(|p| p.is_dir());
+	match (find_tool("clang-cl").filter(|_| which::which("lld-link").is_ok()), ndk) {
+		(Some(version), _) => Ok(version),
+		(None, Some(ndk)) => Ok(format!("Android NDK at {}", ndk.display())),
+		(None, None) => Err((
+			"No compiler found that can build mods".into(),
+			"Install clang and lld to cross-compile for Windows, or set \
+			ANDROID_NDK_ROOT to an Android NDK to build for Android"
+		)),
+	}
+}
+
+fn doctor(config: &Config) {
+	let mut problems = 0;
+	let mut problem = |msg: String, fix: &str| {
+		fail!("{}", msg);
+		info!("{}", fix);
+		problems += 1;
+	};
+
+	match std::env::var_os("GEODE_SDK").map(PathBuf::from) {
+		None => problem(
+			"GEODE_SDK isn't set".into(),
+			"Install the SDK with `geode sdk install`, or use an existing \
+			checkout with `geode sdk set-path`"
+		),
+		Some(path) => match verify_sdk(&path) {
+			Ok(version) => {
+				done!("Geode SDK v{} at {}", version, path.display());
+				let bin_dir = binaries_dir(config, &path, &version);
+				if fs::read_dir(&bin_dir).is_ok_and(|mut d| d.next().is_some()) {
+					done!("Loader binaries installed at {}", bin_dir.display());
+				} else {
+					problem(
+						format!("No loader binaries installed for SDK v{}", version),
+						"Install them with `geode sdk install-binaries`"
+					);
+				}
+			}
+			Err(e) => problem(
+				format!("GEODE_SDK points to {}, which isn't a working SDK: {}", path.display(), e),
+				"Reinstall it with `geode sdk install --reinstall`"
+			),
+		},
+	}
+
+	match which::which("cmake") {
+		Err(_) => problem(
+			"CMake isn't installed".into(),
+			"Install CMake 3.21 or later from https://cmake.org/download and make sure it's on PATH"
+		),
+		Ok(cmake) => {
+			let line = tool_version(&cmake).unwrap_or_default();
+			let version = line.split_whitespace().last()
+				.and_then(|v| Version::parse(v).ok());
+			match version {
+				Some(v) if v < MIN_CMAKE_VERSION => problem(
+					format!("CMake {} is too old, the SDK needs {} or later", v, MIN_CMAKE_VERSION),
+					"Update CMake from https://cmake.org/download"
+				),
+				Some(v) => done!("CMake {}", v),
+				None => warn!("Found CMake at {}, but couldn't tell its version", cmake.display()),
+			}
+		}
+	}
+
+	match find_compiler() {
+		Ok(compiler) => done!("Compiler: {}", compiler),
+		Err((msg, fix)) => problem(msg, fix),
+	}
+
+	if config.profiles.is_empty() {
+		problem(
+			"No profiles set up".into(),
+			"Add your Geometry Dash installation with `geode profile add <PATH> --name <NAME>`"
+		);
+	}
+	for profile in &config.profiles {
+		let profile = profile.borrow();
+		if !profile.gd_path.is_dir() {
+			problem(
+				format!("Profile '{}' points to {}, which doesn't exist", profile.name, profile.gd_path.display()),
+				&format!(
+					"Remove it with `geode profile remove {}` and add it again at the right path",
+					profile.name
+				)
+			);
+			continue;
+		}
+		// macOS keeps the loader with GD's other libraries
+		let loader = ["Geode.dll", "Geode.dylib", "Frameworks/Geode.dylib"]
+			.iter()
+			.any(|name| profile.gd_path.join(name).exists());
+		if loader {
+			done!("Profile '{}' at {}", profile.name, profile.gd_path.display());
+		} else {
+			problem(
+				format!("Geode isn't installed in profile '{}' at {}", profile.name, profile.gd_path.display()),
+				"Install it from https://geode-sdk.org/install"
+			);
+		}
+	}
+
+	if problems == 0 {
+		done!("Everything is set up to build mods");
+	} else {
+		fail!("Found {} problem{}", problems, if problems == 1 { "" } else { "s" });
+		std::process::exit(1);
+	}
+}
+
+/// Print the last lines a failed command wrote, errors in red
+fn print_output_tail(output: &std::process::Output) {
+	let text = format!(
+		"{}{}",
+		String::from_utf8_lossy(&output.stdout),
+		String::from_utf8_lossy(&output.stderr)
+	);
+	let lines = text.lines().filter(|l| !l.trim().is_empty()).collect::<Vec<_>>();
+	for line in &lines[lines.len().saturating_sub(20)..] {
+		if line.to_lowercase().contains("error") {
+			println!("    {}", line.red());
+		} else {
+			println!("    {}", line);
+		}
+	}
+}
+
+/// Run a step of building or running codegen, showing what it printed only
+/// if it fails
+fn run_quietly(cmd: &mut std::process::Command, what: &str) -> Result<(), ()> {
+	match cmd.output() {
+		Ok(output) if output.status.success() => Ok(()),
+		Ok(output) => {
+			let status = output.status.code()
+				.map(|c| format!("exit code {}", c))
+				.unwrap_or_else(|| "a signal".into());
+			fail!("{} failed with {}:", what, status);
+			print_output_tail(&output);
+			Err(())
+		}
+		Err(e) => {
+			fail!("Unable to run {}: {}", what, e);
+			Err(())
+		}
+	}
+}
+
+/// Build the SDK's codegen tool, returning where the executable is. The build
+/// is kept in the SDK so later runs only rebuild what changed
+fn build_codegen(sdk_path: &Path) -> PathBuf {
+	let source = sdk_path.join("codegen");
+	if !source.join("CMakeLists.txt").exists() {
+		fatal!("The SDK at {} has no codegen to build", sdk_path.display());
+	}
+	let cmake = which::which("cmake")
+		.unwrap_or_else(|_| fatal!("CMake is needed to build codegen, see `geode sdk doctor`"));
+	let build = sdk_path.join("build-codegen");
+
+	info!("Building codegen");
+	let configured = run_quietly(
+		std::process::Command::new(&cmake)
+			.arg("-S").arg(&source)
+			.arg("-B").arg(&build)
+			.arg("-DCMAKE_BUILD_TYPE=Release"),
+		"Configuring codegen"
+	);
+	if configured.is_err() {
+		fatal!("Check that your compiler works with `geode sdk doctor`");
+	}
+	let built = run_quietly(
+		std::process::Command::new(&cmake)
+			.arg("--build").arg(&build)
+			.args(["--config", "Release"]),
+		"Building codegen"
+	);
+	if built.is_err() {
+		fatal!("Codegen didn't build, try updating the SDK with `geode sdk update`");
+	}
+
+	// Multi-config generators like Visual Studio put it in a folder per config
+	let exe = format!("Codegen{}", std::env::consts::EXE_SUFFIX);
+	[build.join(&exe), build.join("Release").join(&exe)]
+		.into_iter()
+		.find(|p| p.exists())
+		.unwrap_or_else(|| fatal!("Codegen built, but {} isn't in {}", exe, build.display()))
+}
+
+/// GD version the mod in the current directory targets on a platform
+fn mod_gd_version(platform: BinaryPlatform) -> Option<String> {
+	let dir = std::env::current_dir().ok()?;
+	let json = crate::util::mod_file::read_mod_json(&dir).ok()?;
+	match json.get("gd")? {
+		serde_json::Value::String(version) => Some(version.clone()),
+		serde_json::Value::Object(versions) => versions
+			.get(platform.mod_json_key())
+			.and_then(|v| v.as_str())
+			.map(|v| v.to_string()),
+		_ => None,
+	}
+}
+
+fn codegen(config: &Config, platforms: Vec<BinaryPlatform>, bindings: Option<PathBuf>, gd: Option<String>, build_dir: PathBuf) {
+	let platforms = if platforms.is_empty() {
+		vec![BinaryPlatform::current().unwrap_or_else(|| {
+			fatal!("No default platform on this OS, pick one with `--platform`")
+		})]
+	} else {
+		platforms
+	};
+
+	let bindings = bindings
+		.or_else(|| std::env::var_os("GEODE_BINDINGS_REPO_PATH").map(PathBuf::from))
+		.unwrap_or_else(|| {
+			fatal!("Pass the bindings checkout with `--bindings` or set GEODE_BINDINGS_REPO_PATH")
+		});
+	if !bindings.join("bindings").is_dir() {
+		fatal!("{} isn't a bindings checkout, it has no bindings folder", bindings.display());
+	}
+
+	let (sdk_path, sdk_version) = current_sdk(config);
+	let codegen = build_codegen(&sdk_path);
+
+	let mut failed = 0;
+	for &platform in &platforms {
+		let Some(gd) = gd.clone().or_else(|| mod_gd_version(platform)) else {
+			fail!(
+				"No GD version for {} in mod.json, pass one with `--gd`",
+				platform.name()
+			);
+			failed += 1;
+			continue;
+		};
+		let input = bindings.join("bindings").join(&gd);
+		if !input.is_dir() {
+			let available = fs::read_dir(bindings.join("bindings"))
+				.map(|dir| {
+					let mut versions = dir
+						.filter_map(|e| e.ok())
+						.filter(|e| e.path().is_dir())
+						.map(|e| e.file_name().to_string_lossy().to_string())
+						.collect::<Vec<_>>();
+					versions.sort();
+					versions.join(", ")
+				})
+				.unwrap_or_default();
+			fail!("The bindings have nothing for GD {} (they have {})", gd, available);
+			failed += 1;
+			continue;
+		}
+
+		let output = if platforms.len() == 1 {
+			build_dir.clone()
+		} else {
+			PathBuf::from(format!("{}-{}", build_dir.display(), platform.name()))
+		}
+		.join("geode/codegen");
+		let _ = fs::create_dir_all(&output);
+
+		info!("Generating bindings for {} (GD {})", platform.name(), gd);
+		let ran = run_quietly(
+			std::process::Command::new(&codegen)
+				.arg(platform.codegen_name(&sdk_version))
+				.arg(&input)
+				.arg(&output),
+			&format!("Codegen for {}", platform.name())
+		);
+		match ran {
+			Ok(()) => done!("Generated bindings for {} in {}", platform.name(), output.display()),
+			Err(()) => failed += 1,
+		}
+	}
+
+	if failed != 0 {
+		fatal!("Codegen failed for {} of {} platforms", failed, platforms.len());
+	}
+	info!(
+		"Build with GEODE_BINDINGS_REPO_PATH set to {} so CMake doesn't regenerate \
+		them from the SDK's bindings",
+		bindings.display()
+	);
+}
+
 pub fn get_version() -> Version {
 	Version::parse(
 		fs::read_to_string(Config::sdk_path().join("VERSION"))
@@ -493,8 +2422,8 @@ pub fn get_version() -> Version {
 
 pub fn subcommand(config: &mut Config, cmd: Sdk) {
 	match cmd {
-		Sdk::Install { reinstall, force, path } => {
-			if reinstall && !uninstall() && !force {
+		Sdk::Install { reinstall, force, path, from, binaries, skip_checksum } => {
+			if reinstall && !uninstall(config) && !force {
 				return;
 			}
 
@@ -513,17 +2442,8 @@ pub fn subcommand(config: &mut Config, cmd: Sdk) {
 			let actual_path = match path {
 				Some(p) => p,
 				None => {
-					let default_path = if cfg!(target_os = "macos") {
-						PathBuf::from("/Users/Shared/Geode/sdk")
-					} else {
-						dirs::document_dir()
-							.expect(
-								"No default path available! \
-								Please provide the path manually as an\
-								argument to `geode sdk install`",
-							)
-							.join("Geode")
-					};
+					// Kept with the rest of Geode's data so the CLI can manage it
+					let default_path = geode_root().join("sdk");
 					if !confirm!(
 						"Installing at default path {}. Is this okay?",
 						&default_path.to_str().unwrap()
@@ -533,18 +2453,29 @@ pub fn subcommand(config: &mut Config, cmd: Sdk) {
 							to `geode sdk install`"
 						);
 					}
+					fs::create_dir_all(geode_root()).expect("Unable to create Geode directory");
 					default_path
 				}
 			};
 
-			install(config, actual_path, force);
+			install(config, actual_path, force, from, binaries, skip_checksum);
+		}
+		Sdk::Uninstall { version: Some(version), .. } => remove_version(config, &version),
+		Sdk::Uninstall { all: true, .. } => uninstall_all(config),
+		Sdk::Uninstall { .. } => {
+			uninstall(config);
 		}
-		Sdk::Uninstall => {
-			uninstall();
+		Sdk::SetPath { path, r#move } => set_sdk_path(config, path, r#move),
+		Sdk::Update { branch, legacy_branch, commit } => update(config, branch.or(legacy_branch), commit),
+		Sdk::Version => version_report(config),
+		Sdk::Doctor => doctor(config),
+		Sdk::Codegen { platform, bindings, gd, build_dir } => codegen(config, platform, bindings, gd, build_dir),
+		Sdk::InstallVersion { version } => install_version(config, &version),
+		Sdk::List => list_versions(),
+		Sdk::RemoveVersion { version } => remove_version(config, &version),
+		Sdk::InstallToolchain { toolchain: Toolchain::Android, ndk } => install_android_toolchain(config, ndk),
+		Sdk::InstallBinaries { platform, skip_checksum, from, checksums } => {
+			install_binaries(config, platform, skip_checksum, from, checksums)
 		}
-		Sdk::SetPath { path, r#move } => set_sdk_path(path, r#move),
-		Sdk::Update { branch } => update(config, branch),
-		Sdk::Version => info!("Geode SDK version: {}", get_version()),
-		Sdk::InstallBinaries => install_binaries(config),
 	}
 }
@@ -1,25 +1,526 @@
 
-use crate::config::Config;
+use crate::config::{proxy_options, Config};
+use crate::index::indexed_mod_name;
 use crate::sdk::get_version;
 use crate::util::logging::{ask_confirm, ask_value};
-use crate::{done, info, warn};
+use crate::util::mod_file::{id_problem, parse_mod_info, read_mod_json, suggest_id};
+use crate::{done, fail, fatal, info, warn};
+use clap::Subcommand;
+use colored::Colorize;
 use git2::Repository;
 use path_absolutize::Absolutize;
 use regex::Regex;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-fn create_template(
-	project_location: PathBuf,
+const DEFAULT_TEMPLATE: &str = "https://github.com/geode-sdk/example-mod";
+
+/// Name of the example mod for `--template`, since it's not built in
+const DEFAULT_TEMPLATE_NAME: &str = "example";
+
+#[derive(Subcommand, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum Template {
+	/// List the templates `geode new --template` can use by name
+	List {
+		/// Print the list as JSON
+		#[clap(long)]
+		json: bool,
+	},
+}
+
+/// Path and contents of a file in a built-in template
+type TemplateFile = (&'static str, &'static [u8]);
+
+macro_rules! template_file {
+	($flavor:literal, $path:literal) => {
+		($path, include_bytes!(concat!("../templates/", $flavor, "/", $path)) as &[u8])
+	};
+}
+
+struct BuiltinTemplate {
+	name: &'static str,
+	/// Shown by `geode template list`
+	description: &'static str,
+	files: &'static [TemplateFile],
+}
+
+/// Templates built into the binary, usable without network access. The
+/// minimal one is used when the example mod can't be cloned
+const BUILTIN_TEMPLATES: &[BuiltinTemplate] = &[
+	BuiltinTemplate {
+		name: "minimal",
+		description: "One source file hooking the main menu, with nothing else set up",
+		files: &[
+			template_file!("minimal", "README.md"),
+			template_file!("minimal", ".gitignore"),
+			template_file!("minimal", "geode-template.json"),
+			template_file!("minimal", "CMakeLists.txt"),
+			template_file!("minimal", "src/main.cpp"),
+		],
+	},
+	BuiltinTemplate {
+		name: "full",
+		description: "Settings, resources and events, for bigger mods",
+		files: &[
+			template_file!("minimal", "README.md"),
+			template_file!("minimal", ".gitignore"),
+			template_file!("minimal", "geode-template.json"),
+			template_file!("full", "CMakeLists.txt"),
+			template_file!("full", "mod.json"),
+			template_file!("full", "src/main.cpp"),
+			template_file!("full", "resources/icon.png"),
+		],
+	},
+	BuiltinTemplate {
+		name: "library",
+		description: "An API for other mods to use, with exported headers",
+		files: &[
+			template_file!("minimal", "README.md"),
+			template_file!("minimal", ".gitignore"),
+			template_file!("minimal", "geode-template.json"),
+			template_file!("library", "CMakeLists.txt"),
+			template_file!("library", "mod.json"),
+			template_file!("library", "src/main.cpp"),
+			template_file!("library", "include/{{CLASS_NAME}}.hpp"),
+		],
+	},
+	BuiltinTemplate {
+		name: "ui",
+		description: "A popup opened from a button on the main menu",
+		files: &[
+			template_file!("minimal", "README.md"),
+			template_file!("minimal", ".gitignore"),
+			template_file!("minimal", "geode-template.json"),
+			template_file!("full", "CMakeLists.txt"),
+			template_file!("ui", "src/main.cpp"),
+			template_file!("ui", "src/{{CLASS_NAME}}Popup.hpp"),
+			template_file!("ui", "src/{{CLASS_NAME}}Popup.cpp"),
+		],
+	},
+];
+
+/// Optional file in a template describing how to fill it in
+const TEMPLATE_MANIFEST: &str = "geode-template.json";
+
+/// Where a project keeps the boilerplate as it was last generated, to tell
+/// its own changes apart from the template's in `geode project update`
+const TEMPLATE_BASE: &str = ".geode/template";
+
+#[derive(Deserialize)]
+struct TemplateManifest {
+	/// Globs of the paths whose contents and names have `{{VARIABLE}}`
+	/// placeholders filled in
+	#[serde(default = "TemplateManifest::default_substitute")]
+	substitute: Vec<String>,
+	/// Globs of the boilerplate files that `geode project update` keeps in
+	/// sync with the template
+	#[serde(default = "TemplateManifest::default_update")]
+	update: Vec<String>,
+	/// Shell commands to run in the project once it's generated, like
+	/// fetching a dependency or running codegen. Placeholders are filled
+	/// in, and they only run with `--allow-scripts`
+	#[serde(default, rename = "post-generate")]
+	post_generate: Vec<String>,
+}
+
+impl TemplateManifest {
+	fn default_substitute() -> Vec<String> {
+		vec!["README.md".into(), "CMakeLists.txt".into(), "src/**/*".into()]
+	}
+
+	fn default_update() -> Vec<String> {
+		vec!["CMakeLists.txt".into(), ".gitignore".into(), ".github/**/*".into(), "cmake/**/*".into()]
+	}
+
+	/// None if the template doesn't have a manifest
+	fn load(project_location: &Path) -> Option<TemplateManifest> {
+		let path = project_location.join(TEMPLATE_MANIFEST);
+		let text = fs::read_to_string(&path).ok()?;
+		fs::remove_file(&path).unwrap_or(());
+		Some(serde_json::from_str(&text).unwrap_or_else(|e| fatal!("Unable to parse {}: {}", TEMPLATE_MANIFEST, e)))
+	}
+}
+
+/// Fill in `{{VARIABLE}}` placeholders, returning the names of any unknown
+/// variables alongside the result
+fn substitute_variables(text: &str, variables: &HashMap<&str, String>) -> (String, Vec<String>) {
+	let regex = Regex::new(r"\{\{\s*([A-Z][A-Z0-9_]*)\s*\}\}").unwrap();
+	let mut unknown = Vec::new();
+	let result = regex.replace_all(text, |caps: &regex::Captures| {
+		match variables.get(&caps[1]) {
+			Some(value) => value.clone(),
+			None => {
+				unknown.push(caps[1].to_string());
+				caps[0].to_string()
+			}
+		}
+	});
+	(result.into_owned(), unknown)
+}
+
+/// Write a built-in template, returning false if there's none by that name
+fn write_builtin_template(name: &str, project_location: &Path) -> bool {
+	let Some(builtin) = BUILTIN_TEMPLATES.iter().find(|t| t.name == name) else {
+		return false;
+	};
+	for (name, contents) in builtin.files {
+		let dest = project_location.join(name);
+		fs::create_dir_all(dest.parent().unwrap()).expect("Unable to create project directory");
+		fs::write(dest, contents).expect("Unable to write to project");
+	}
+	true
+}
+
+fn clone_repo(url: &str, path: &Path) -> Result<Repository, git2::Error> {
+	let mut fetch = git2::FetchOptions::new();
+	fetch.proxy_options(proxy_options());
+	git2::build::RepoBuilder::new()
+		.fetch_options(fetch)
+		.clone(url, path)
+}
+
+/// Get a built-in template by name, a template from a git repository, or
+/// copy it from a plain folder. Without a template the example mod is used,
+/// falling back to the minimal built-in one if it can't be cloned or when
+/// offline
+fn fetch_template(template: Option<&str>, project_location: &Path, offline: bool) {
+	let Some(template) = template.filter(|t| *t != DEFAULT_TEMPLATE_NAME) else {
+		if offline {
+			write_builtin_template("minimal", project_location);
+			return;
+		}
+		// Cloned elsewhere first so a failed clone can't leave half of the
+		// example mod mixed into the built-in template
+		let clone_dir = std::env::temp_dir().join(format!("geode-example-mod-{}", std::process::id()));
+		fs::remove_dir_all(&clone_dir).unwrap_or(());
+		match clone_repo(DEFAULT_TEMPLATE, &clone_dir) {
+			Ok(_) => {
+				fs::remove_dir_all(clone_dir.join(".git")).unwrap();
+				crate::file::copy_dir_recursive(&clone_dir, &project_location.to_path_buf())
+					.expect("Unable to copy the example mod");
+			}
+			Err(e) => {
+				warn!("Unable to clone the example mod ({}), using the built-in template", e.message());
+				write_builtin_template("minimal", project_location);
+			}
+		}
+		fs::remove_dir_all(&clone_dir).unwrap_or(());
+		return;
+	};
+
+	if write_builtin_template(template, project_location) {
+		return;
+	}
+
+	let path = Path::new(template);
+	if path.is_dir() && !path.join(".git").exists() {
+		for entry in walkdir::WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+			let dest = project_location.join(entry.path().strip_prefix(path).unwrap());
+			if entry.file_type().is_dir() {
+				fs::create_dir_all(&dest).expect("Unable to create project directory");
+			} else {
+				fs::copy(entry.path(), &dest).expect("Unable to copy template");
+			}
+		}
+		return;
+	}
+
+	// Local repositories can still be cloned
+	if offline && !path.exists() {
+		fatal!(
+			"Template {} has to be downloaded, which --offline doesn't allow. \
+			Use a built-in template or a local folder",
+			template
+		);
+	}
+
+	clone_repo(template, project_location)
+		.unwrap_or_else(|e| fatal!("Unable to clone template {}: {}", template, e));
+	fs::remove_dir_all(project_location.join(".git")).unwrap();
+}
+
+/// Written to projects whose template has no .gitignore of its own
+const DEFAULT_GITIGNORE: &str = include_str!("../templates/minimal/.gitignore");
+
+/// Put a new project under version control with everything in a first
+/// commit, unless it's already inside a repository
+fn init_git(config: &Config, project_location: &Path) {
+	if Repository::discover(project_location).is_ok() {
+		info!("Project is already inside a git repository, not creating a new one");
+		return;
+	}
+
+	let gitignore = project_location.join(".gitignore");
+	if !gitignore.exists() {
+		fs::write(&gitignore, DEFAULT_GITIGNORE).expect("Unable to write .gitignore");
+	}
+
+	let repo = Repository::init(project_location)
+		.unwrap_or_else(|e| fatal!("Unable to create git repository: {}", e.message()));
+	let mut index = repo.index().expect("Unable to get git index");
+	index
+		.add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+		.and_then(|_| index.write())
+		.unwrap_or_else(|e| fatal!("Unable to add project files to git: {}", e.message()));
+
+	// Same identity as commits to the Indexer, but never GeodeBot's
+	let git_config = repo.config().ok();
+	let from_git = |key: &str| git_config.as_ref().and_then(|c| c.get_string(key).ok());
+	let name = config.git_name.clone().or_else(|| from_git("user.name"));
+	let email = config.git_email.clone().or_else(|| from_git("user.email"));
+	let (Some(name), Some(email)) = (name, email) else {
+		warn!(
+			"Git repository created, but the first commit was skipped since \
+			no git name and email are set. Set them with `git config --global \
+			user.name <name>` and `git config --global user.email <email>`"
+		);
+		return;
+	};
+	let signature = git2::Signature::now(&name, &email).expect("Unable to create commit signature");
+	let tree = index
+		.write_tree()
+		.and_then(|id| repo.find_tree(id))
+		.expect("Unable to write git tree");
+	repo.commit(Some("HEAD"), &signature, &signature, "Initial commit", &tree, &[])
+		.unwrap_or_else(|e| fatal!("Unable to make the first commit: {}", e.message()));
+	info!("Created git repository with the first commit");
+}
+
+/// Run a template's post-generation commands, or list them if the user
+/// hasn't allowed it
+fn run_post_generate(project_location: &Path, commands: &[String], variables: &HashMap<&str, String>, allow: bool) {
+	if commands.is_empty() {
+		return;
+	}
+	let commands = commands.iter().map(|c| substitute_variables(c, variables).0).collect::<Vec<_>>();
+	if !allow {
+		warn!("The template wants to run these commands after generating the project:");
+		for command in &commands {
+			info!("  {}", command);
+		}
+		info!("They were skipped. Rerun with `--allow-scripts` if you trust the template, or run them yourself");
+		return;
+	}
+
+	for command in &commands {
+		info!("Running {}", command);
+		let status = if cfg!(windows) {
+			std::process::Command::new("cmd").arg("/C").arg(command).current_dir(project_location).status()
+		} else {
+			std::process::Command::new("sh").arg("-c").arg(command).current_dir(project_location).status()
+		};
+		match status {
+			Ok(status) if status.success() => {}
+			Ok(status) => {
+				fail!("`{}` failed ({}), skipping the rest of the template's commands", command, status);
+				return;
+			}
+			Err(e) => {
+				fail!("Unable to run `{}`: {}", command, e);
+				return;
+			}
+		}
+	}
+}
+
+/// Choices for `geode new` that aren't part of mod.json
+struct NewOptions {
+	strip: bool,
+	offline: bool,
+	git: bool,
+	allow_scripts: bool,
+}
+
+/// What the user filled in about the new mod
+struct ProjectInfo {
 	name: String,
 	version: String,
 	id: String,
 	developer: String,
 	description: String,
-	strip: bool
+	geode: String,
+	license: Option<String>,
+}
+
+impl ProjectInfo {
+	/// Values for the `{{VARIABLE}}` placeholders in templates
+	fn variables(&self) -> HashMap<&'static str, String> {
+		// Project name without spaces, for CMake and such
+		let filtered_name: String = self.name.chars().filter(|c| !c.is_whitespace()).collect();
+		// And as a C++ identifier
+		let mut class_name: String = self.name.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+		if !class_name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+			class_name.insert(0, '_');
+		}
+
+		HashMap::from([
+			("MOD_ID", self.id.clone()),
+			("MOD_NAME", self.name.clone()),
+			("PROJECT_NAME", filtered_name),
+			("MACRO_NAME", class_name.to_uppercase()),
+			("CLASS_NAME", class_name),
+			("DEVELOPER", self.developer.clone()),
+			("DESCRIPTION", self.description.clone()),
+			("VERSION", self.version.clone()),
+			("GEODE_VERSION", self.geode.clone()),
+			("LICENSE", self.license.clone().unwrap_or_default()),
+			("YEAR", current_year().to_string()),
+		])
+	}
+}
+
+fn current_year() -> i64 {
+	let secs = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map(|d| d.as_secs() as i64)
+		.unwrap_or_default();
+	// Days since 1970 to a civil date, see
+	// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+	let z = secs.div_euclid(86400) + 719468;
+	let era = z.div_euclid(146097);
+	let doe = z - era * 146097;
+	let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	yoe + era * 400 + if month <= 2 { 1 } else { 0 }
+}
+
+/// Licenses whose text can be written for a new project, by SPDX identifier
+const LICENSES: &[(&str, &str)] = &[
+	("MIT", include_str!("../templates/licenses/MIT.txt")),
+	("Apache-2.0", include_str!("../templates/licenses/Apache-2.0.txt")),
+	("GPL-3.0", include_str!("../templates/licenses/GPL-3.0.txt")),
+	("LGPL-3.0", include_str!("../templates/licenses/LGPL-3.0.txt")),
+	("MPL-2.0", include_str!("../templates/licenses/MPL-2.0.txt")),
+	("BSD-3-Clause", include_str!("../templates/licenses/BSD-3-Clause.txt")),
+	("BSL-1.0", include_str!("../templates/licenses/BSL-1.0.txt")),
+	("CC0-1.0", include_str!("../templates/licenses/CC0-1.0.txt")),
+	("Unlicense", include_str!("../templates/licenses/Unlicense.txt")),
+];
+
+/// A license's SPDX identifier with the usual capitalization, and its text
+/// if it's one of the known ones
+fn find_license(license: &str) -> (String, Option<&'static str>) {
+	match LICENSES.iter().find(|(id, _)| id.eq_ignore_ascii_case(license)) {
+		Some((id, text)) => (id.to_string(), Some(text)),
+		None => (license.to_string(), None),
+	}
+}
+
+fn compile_patterns(patterns: &[String]) -> Vec<glob::Pattern> {
+	patterns
+		.iter()
+		.map(|p| glob::Pattern::new(p).unwrap_or_else(|e| fatal!("Invalid pattern {} in {}: {}", p, TEMPLATE_MANIFEST, e)))
+		.collect()
+}
+
+/// Paths of the files in a project matching any of the patterns, relative to
+/// the project
+fn matching_files(project_location: &Path, patterns: &[String]) -> Vec<PathBuf> {
+	let patterns = compile_patterns(patterns);
+	walkdir::WalkDir::new(project_location)
+		.into_iter()
+		.filter_map(|e| e.ok())
+		.filter(|e| e.file_type().is_file())
+		.map(|e| e.path().strip_prefix(project_location).unwrap().to_path_buf())
+		.filter(|relative| patterns.iter().any(|p| p.matches_path(relative)))
+		.collect()
+}
+
+/// Fetch a template into a folder and fill in the project's values
+fn render_template(
+	project_location: &Path,
+	template: Option<&str>,
+	offline: bool,
+	info: &ProjectInfo
+) -> TemplateManifest {
+	fetch_template(template, project_location, offline);
+
+	// Templates without a manifest get the example mod's names replaced instead
+	let (manifest, legacy) = match TemplateManifest::load(project_location) {
+		Some(manifest) => (manifest, false),
+		None => (
+			TemplateManifest {
+				substitute: TemplateManifest::default_substitute(),
+				update: TemplateManifest::default_update(),
+				post_generate: Vec::new(),
+			},
+			true
+		),
+	};
+
+	// The example's own ID, so code referring to it can be pointed at ours
+	let template_id = read_mod_json(project_location)
+		.ok()
+		.and_then(|json| json.get("id")?.as_str().map(String::from));
+
+	let variables = info.variables();
+	let filtered_name = &variables["PROJECT_NAME"];
+
+	let patterns = compile_patterns(&manifest.substitute);
+
+	// Children come before their folders so renaming a folder doesn't
+	// invalidate the paths still to be visited
+	let entries = walkdir::WalkDir::new(project_location)
+		.min_depth(1)
+		.contents_first(true)
+		.into_iter()
+		.filter_map(|e| e.ok())
+		.filter(|e| {
+			let relative = e.path().strip_prefix(project_location).unwrap();
+			patterns.iter().any(|p| p.matches_path(relative))
+		})
+		.collect::<Vec<_>>();
+	for entry in entries {
+		let path = entry.path();
+		let relative = path.strip_prefix(project_location).unwrap().display();
+
+		// Skip anything that isn't text
+		if entry.file_type().is_file() {
+			if let Ok(contents) = fs::read_to_string(path) {
+				let contents = if legacy {
+					let mut contents = contents.replace("Template", filtered_name);
+					if let Some(template_id) = &template_id {
+						contents = contents.replace(template_id, &info.id);
+					}
+					contents
+				} else {
+					let (contents, unknown) = substitute_variables(&contents, &variables);
+					for var in unknown {
+						warn!("Unknown template variable {{{{{}}}}} in {}", var, relative);
+					}
+					contents
+				};
+				fs::write(path, contents).unwrap();
+			}
+		}
+
+		let file_name = entry.file_name().to_string_lossy();
+		if !legacy && file_name.contains("{{") {
+			let (new_name, unknown) = substitute_variables(&file_name, &variables);
+			for var in unknown {
+				warn!("Unknown template variable {{{{{}}}}} in the name of {}", var, relative);
+			}
+			fs::rename(path, path.with_file_name(&new_name))
+				.unwrap_or_else(|e| fatal!("Unable to rename {}: {}", relative, e));
+		}
+	}
+
+	manifest
+}
+
+fn create_template(
+	config: &Config,
+	project_location: PathBuf,
+	template: Option<&str>,
+	info: ProjectInfo,
+	options: NewOptions,
 ) {
 	if project_location.exists() {
 		warn!("The provided location already exists.");
@@ -31,28 +532,16 @@ fn create_template(
 		fs::create_dir_all(&project_location).expect("Unable to create project directory");
 	}
 
-	// Clone repository
-	Repository::clone(
-		"https://github.com/geode-sdk/example-mod",
-		&project_location,
-	).expect("Unable to clone repository");
-
-	fs::remove_dir_all(project_location.join(".git")).unwrap();
-
-	// Replace "Template" with project name (no spaces)
-	let filtered_name: String = name.chars().filter(|c| !c.is_whitespace()).collect();
-
-	for file in &["README.md", "CMakeLists.txt"] {
-		let file = project_location.join(file);
+	let manifest = render_template(&project_location, template, options.offline, &info);
+	let variables = info.variables();
+	let ProjectInfo { name, version, id, developer, description, geode, license } = info;
 
-		let contents = fs::read_to_string(&file)
-			.unwrap()
-			.replace("Template", &filtered_name);
-		fs::write(file, contents).unwrap();
-	}
+	// The base is what the template renders to, so `geode project update`
+	// sees stripped comments as a change made in the project
+	save_template_base(&project_location, &project_location, &matching_files(&project_location, &manifest.update));
 
 	// Strip comments from template
-	if strip {
+	if options.strip && project_location.join("src/main.cpp").exists() {
 		let cmake_path = project_location.join("CMakeLists.txt");
 		let cpp_path = project_location.join("src/main.cpp");
 
@@ -67,8 +556,8 @@ fn create_template(
 	}
 
 	// Default mod.json
-	let mod_json = json!({
-		"geode":        get_version().to_string(),
+	let mut mod_json = json!({
+		"geode":        geode,
 		"version":      version,
 		"id":           id,
 		"name":         name,
@@ -76,6 +565,22 @@ fn create_template(
 		"description":  description
 	});
 
+	if let Some(license) = license {
+		mod_json["license"] = json!(license);
+		if let Some(text) = find_license(&license).1 {
+			fs::write(project_location.join("LICENSE"), substitute_variables(text, &variables).0)
+				.expect("Unable to write LICENSE");
+		}
+	}
+
+	// Keep anything else the template sets up, like settings or resources
+	if let Ok(serde_json::Value::Object(template_json)) = read_mod_json(&project_location) {
+		let object = mod_json.as_object_mut().unwrap();
+		for (key, value) in template_json {
+			object.entry(key).or_insert(value);
+		}
+	}
+
 	// Format neatly
 	let buf = Vec::new();
 	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
@@ -106,9 +611,146 @@ fn create_template(
 		}
 	}
 
+	// After mod.json so commands like `geode project codegen-settings` work,
+	// and before git so what they make is in the first commit
+	run_post_generate(&project_location, &manifest.post_generate, &variables, options.allow_scripts);
+
+	if options.git {
+		init_git(config, &project_location);
+	}
+
 	done!("Succesfully initialized project! Happy modding :)");
 }
 
+/// Remember how the template's boilerplate looked for the next update
+fn save_template_base(project_location: &Path, rendered: &Path, files: &[PathBuf]) {
+	let base = project_location.join(TEMPLATE_BASE);
+	for file in files {
+		let dest = base.join(file);
+		fs::create_dir_all(dest.parent().unwrap()).expect("Unable to create template base directory");
+		fs::copy(rendered.join(file), dest).expect("Unable to save template base");
+	}
+}
+
+fn print_diff(relative: &Path, current: &[u8], new: &[u8]) {
+	use colored::Colorize;
+	let (Ok(current), Ok(new)) = (std::str::from_utf8(current), std::str::from_utf8(new)) else {
+		info!("{} is a binary file, compare it with the template by hand", relative.display());
+		return;
+	};
+	let name = relative.to_string_lossy().replace('\\', "/");
+	let diff = similar::TextDiff::from_lines(current, new)
+		.unified_diff()
+		.context_radius(3)
+		.header(&format!("a/{}", name), &format!("b/{}", name))
+		.to_string();
+	for line in diff.lines() {
+		if line.starts_with("+++") || line.starts_with("---") {
+			println!("{}", line.bold());
+		} else if line.starts_with('+') {
+			println!("{}", line.green());
+		} else if line.starts_with('-') {
+			println!("{}", line.red());
+		} else if line.starts_with("@@") {
+			println!("{}", line.cyan());
+		} else {
+			println!("{}", line);
+		}
+	}
+}
+
+/// Bring a project's boilerplate up to date with the template. Files the
+/// project hasn't changed since they were generated are replaced, and
+/// anything changed on both sides is shown as a diff instead
+pub fn update_template(project_location: &Path, template: Option<String>, offline: bool) {
+	let mod_info = parse_mod_info(project_location);
+	let info = ProjectInfo {
+		name: mod_info.name,
+		version: format!("v{}", mod_info.version),
+		id: mod_info.id,
+		developer: mod_info.developer,
+		description: mod_info.description,
+		geode: mod_info.geode.to_string(),
+		license: read_mod_json(project_location)
+			.ok()
+			.and_then(|json| json.get("license")?.as_str().map(String::from)),
+	};
+
+	// Named after the process so updates running at the same time don't
+	// share it
+	let rendered = std::env::temp_dir().join(format!("geode-template-update-{}", std::process::id()));
+	if rendered.exists() {
+		fs::remove_dir_all(&rendered).expect("Unable to clear old template");
+	}
+	fs::create_dir_all(&rendered).expect("Unable to create template directory");
+
+	info!("Fetching template");
+	let manifest = render_template(&rendered, template.as_deref(), offline, &info);
+
+	let base = project_location.join(TEMPLATE_BASE);
+	if !base.exists() {
+		warn!(
+			"This project doesn't know which template version it was made from, \
+			so every file that differs from the template will be shown as a diff"
+		);
+	}
+
+	let mut in_sync = Vec::new();
+	let mut changed = 0;
+	let mut conflicts = 0;
+	for relative in matching_files(&rendered, &manifest.update) {
+		let new = fs::read(rendered.join(&relative)).unwrap();
+		let current = fs::read(project_location.join(&relative)).ok();
+		let old = fs::read(base.join(&relative)).ok();
+
+		match current {
+			None => {
+				info!("Added {}", relative.display());
+			}
+			Some(current) if current == new => {
+				in_sync.push(relative);
+				continue;
+			}
+			Some(current) if old.as_ref() == Some(&current) => {
+				info!("Updated {}", relative.display());
+			}
+			Some(_) if old.as_ref() == Some(&new) => {
+				// Only the project has changed this
+				in_sync.push(relative);
+				continue;
+			}
+			Some(current) => {
+				warn!("{} was changed both in the project and the template", relative.display());
+				print_diff(&relative, &current, &new);
+				conflicts += 1;
+				continue;
+			}
+		}
+
+		let dest = project_location.join(&relative);
+		fs::create_dir_all(dest.parent().unwrap()).expect("Unable to create project directory");
+		fs::copy(rendered.join(&relative), dest).expect("Unable to update project");
+		in_sync.push(relative);
+		changed += 1;
+	}
+
+	// Conflicting files keep their old base so they're reported until merged
+	save_template_base(project_location, &rendered, &in_sync);
+	drop(fs::remove_dir_all(&rendered));
+
+	if conflicts > 0 {
+		warn!(
+			"{} file(s) couldn't be updated automatically, apply the changes \
+			shown above by hand and run this again",
+			conflicts
+		);
+	} else if changed > 0 {
+		done!("Updated {} file(s) from the template", changed);
+	} else {
+		done!("Project is up to date with the template");
+	}
+}
+
 fn possible_name(path: &Option<PathBuf>) -> Option<String> {
 	let dir_name;
 	let Some(path) = path else { return None; };
@@ -121,7 +763,15 @@ fn possible_name(path: &Option<PathBuf>) -> Option<String> {
 	Some(dir_name)
 }
 
-pub fn build_template(config: &mut Config, location: Option<PathBuf>) {
+pub fn build_template(
+	config: &mut Config,
+	location: Option<PathBuf>,
+	template: Option<String>,
+	offline: bool,
+	license: Option<String>,
+	git: bool,
+	allow_scripts: bool,
+) {
 	info!("This utility will walk you through setting up a new mod.");
 	info!("You can change any of the properties you set here later on by editing the generated mod.json file.");
 
@@ -151,11 +801,57 @@ pub fn build_template(config: &mut Config, location: Option<PathBuf>) {
 		true,
 	));
 
-	let mod_id = format!(
-		"{}.{}",
-		final_developer.to_lowercase().replace(' ', "_"),
-		final_name.to_lowercase().replace(' ', "_")
-	);
+	let id_part = |s: &str| s
+		.to_lowercase()
+		.replace(' ', "_")
+		.chars()
+		.filter(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_".contains(*c))
+		.collect::<String>();
+	let mut suggested_id = format!("{}.{}", id_part(&final_developer), id_part(&final_name));
+	let mod_id = loop {
+		let id = ask_value("ID", Some(&suggested_id), true);
+		if let Some(problem) = id_problem(&id) {
+			fail!("'{}' is not a valid ID: {}", id, problem);
+			// IDs typed without a developer get the one given above
+			let full = if id.contains('.') { id } else { format!("{}.{}", final_developer, id) };
+			if let Some(suggestion) = suggest_id(&full) {
+				if ask_confirm(&format!("Use '{}' instead?", suggestion), true) {
+					suggested_id = suggestion;
+				} else {
+					continue;
+				}
+			} else {
+				continue;
+			}
+		} else {
+			suggested_id = id;
+		}
+
+		// Only the cached index is checked, so this works offline
+		if let Some(name) = indexed_mod_name(config, &suggested_id) {
+			warn!("'{}' is already used by {} on the index", suggested_id, name);
+			if !ask_confirm("Do you want to use it anyway?", false) {
+				continue;
+			}
+		}
+		break suggested_id;
+	};
+
+	let license = license
+		.or_else(|| {
+			info!(
+				"Licenses with a built-in text: {}",
+				LICENSES.iter().map(|(id, _)| *id).collect::<Vec<_>>().join(", ")
+			);
+			Some(ask_value("License", None, false)).filter(|l| !l.is_empty())
+		})
+		.map(|license| {
+			let (license, text) = find_license(&license);
+			if text.is_none() {
+				warn!("No built-in text for the license '{}', add a LICENSE file yourself", license);
+			}
+			license
+		});
 
 	let strip = ask_confirm(
 		"Do you want to remove comments from the default template?", false
@@ -164,12 +860,58 @@ pub fn build_template(config: &mut Config, location: Option<PathBuf>) {
 	info!("Creating project {}", mod_id);
 
 	create_template(
+		config,
 		final_location,
-		final_name,
-		final_version,
-		mod_id,
-		final_developer,
-		final_description,
-		strip
+		template.as_deref(),
+		ProjectInfo {
+			name: final_name,
+			version: final_version,
+			id: mod_id,
+			developer: final_developer,
+			description: final_description,
+			geode: get_version().to_string(),
+			license,
+		},
+		NewOptions {
+			strip,
+			offline,
+			git,
+			allow_scripts,
+		},
 	);
 }
+
+fn list_templates(json: bool) {
+	let templates = std::iter::once((
+		DEFAULT_TEMPLATE_NAME,
+		format!("The Geode example mod, cloned from {}", DEFAULT_TEMPLATE),
+	))
+	.chain(BUILTIN_TEMPLATES.iter().map(|t| (t.name, t.description.to_string())))
+	.collect::<Vec<_>>();
+
+	if json {
+		let list = templates
+			.iter()
+			.map(|(name, description)| json!({
+				"name": name,
+				"description": description,
+				"builtin": *name != DEFAULT_TEMPLATE_NAME,
+			}))
+			.collect::<Vec<_>>();
+		println!("{}", serde_json::to_string_pretty(&list).unwrap());
+		return;
+	}
+
+	println!("Templates:");
+	for (name, description) in &templates {
+		let default = if *name == DEFAULT_TEMPLATE_NAME { " (default)" } else { "" };
+		println!("    - {}{}: {}", name.bright_green(), default.bright_cyan(), description);
+	}
+	info!("Use one with `geode new --template <name>`, or give a git URL or path instead");
+}
+
+pub fn subcommand(cmd: Template) {
+	match cmd {
+		Template::List { json } => list_templates(json),
+	}
+}
@@ -3,6 +3,7 @@ use crate::mod_file::BitmapFont;
 use std::fs;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use texture_packer::exporter::ImageExporter;
 use texture_packer::texture::Texture;
 use texture_packer::TexturePacker;
@@ -331,7 +332,7 @@ fn extract_from_cache(
 pub fn get_font_bundles(
 	font: &BitmapFont,
 	working_dir: &Path,
-	cache: &mut Option<CacheBundle>,
+	cache: &Mutex<Option<CacheBundle>>,
 	mod_info: &ModFileInfo,
 	shut_up: bool,
 ) -> FontBundles {
@@ -341,7 +342,7 @@ pub fn get_font_bundles(
 		info!("Fetching font {}", font.name.bright_yellow());
 	}
 
-	if let Some(cache_bundle) = cache {
+	if let Some(cache_bundle) = cache.lock().unwrap().as_mut() {
 		// Cache found
 		if let Some(p) = cache_bundle.cache.fetch_font_bundles(font) {
 			if !shut_up {
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs;
 use std::fs::File;
 use std::io::Read;
@@ -9,10 +9,13 @@ use crate::mod_file::BitmapFont;
 use crate::spritesheet::SpriteSheet;
 use crate::warn;
 
+// BTreeMap so the cache file (and with it the package) comes out the same every time
 #[derive(Serialize, Deserialize)]
 pub struct ResourceCache {
-	pub spritesheets: HashMap<String, PathBuf>,
-	pub fonts: HashMap<String, PathBuf>,
+	pub spritesheets: BTreeMap<String, PathBuf>,
+	pub fonts: BTreeMap<String, PathBuf>,
+	#[serde(default)]
+	pub sprites: BTreeMap<String, PathBuf>,
 }
 
 pub struct CacheBundle {
@@ -55,16 +58,30 @@ fn hash_sheet(sheet: &SpriteSheet) -> String {
 		.map(|x| sha256::digest_file(x).unwrap())
 		.collect();
 	hashes.sort();
+	// the name decides what the output files are called
+	hashes.push(sheet.name.clone());
 	sha256::digest(hashes.into_iter().collect::<String>())
 }
 
+fn hash_sprite(path: &Path) -> String {
+	sha256::digest(format!(
+		"{}|{}",
+		path.file_stem().and_then(|x| x.to_str()).unwrap_or_default(),
+		sha256::digest_file(path).unwrap()
+	))
+}
+
 fn hash_font(font: &BitmapFont) -> String {
 	sha256::digest(format!(
-		"{}|{}|{}|{}",
+		"{}|{}|{}|{}|{}|{},{},{}",
+		font.name,
 		font.size,
 		font.outline,
 		font.charset.clone().unwrap_or_default(),
-		sha256::digest_file(font.path.clone()).unwrap()
+		sha256::digest_file(font.path.clone()).unwrap(),
+		font.color.red,
+		font.color.green,
+		font.color.blue
 	))
 }
 
@@ -122,8 +139,9 @@ pub fn get_cache_bundle(path: &Path) -> Option<CacheBundle> {
 impl ResourceCache {
 	pub fn new() -> ResourceCache {
 		ResourceCache {
-			spritesheets: HashMap::new(),
-			fonts: HashMap::new(),
+			spritesheets: BTreeMap::new(),
+			fonts: BTreeMap::new(),
+			sprites: BTreeMap::new(),
 		}
 	}
 
@@ -154,6 +172,17 @@ impl ResourceCache {
 		self.fonts.insert(hash_font(font), path);
 	}
 
+	pub fn add_sprite(&mut self, sprite: &Path, path: PathBuf) {
+		if !path.is_relative() {
+			unreachable!("Contact geode developers: {}", path.display());
+		}
+		self.sprites.insert(hash_sprite(sprite), path);
+	}
+
+	pub fn fetch_sprite(&self, sprite: &Path) -> Option<&Path> {
+		self.sprites.get(&hash_sprite(sprite)).map(|x| &**x)
+	}
+
 	pub fn fetch_spritesheet_bundles(&self, sheet: &SpriteSheet) -> Option<&Path> {
 		self.spritesheets.get(&hash_sheet(sheet)).map(|x| &**x)
 	}
@@ -12,6 +12,10 @@ use crate::{done, fail, fatal, info, warn};
 pub struct Profile {
 	pub name: String,
 	pub gd_path: PathBuf,
+	/// SDK version the profile's loader comes from, set with `geode profile
+	/// set-sdk`. When not set, the profile goes with the main SDK
+	#[serde(default)]
+	pub sdk_version: Option<String>,
 
 	#[serde(flatten)]
 	other: HashMap<String, Value>,
@@ -24,6 +28,48 @@ pub struct Config {
 	pub profiles: Vec<RefCell<Profile>>,
 	pub default_developer: Option<String>,
 	pub sdk_nightly: bool,
+	pub github_token: Option<String>,
+	pub git_name: Option<String>,
+	pub git_email: Option<String>,
+	pub indexer_path: Option<PathBuf>,
+	#[serde(default)]
+	pub indexer_keep_history: bool,
+	#[serde(default)]
+	pub indexer_shallow: bool,
+	#[serde(default)]
+	pub compress_pngs: bool,
+	pub package_compression: Option<String>,
+	/// Proxy for all downloads, like `http://proxy.example.com:8080`.
+	/// HTTP_PROXY and HTTPS_PROXY take priority over it
+	pub proxy: Option<String>,
+	/// Git URLs of copies of the SDK repository, tried before GitHub
+	#[serde(default)]
+	pub sdk_mirrors: Vec<String>,
+	/// Base URLs with loader binaries laid out like GitHub's release
+	/// downloads, as `<base>/<tag>/<file>`, tried before GitHub
+	#[serde(default)]
+	pub binaries_mirrors: Vec<String>,
+	/// URLs of ZIPs of the index's main branch, tried before GitHub
+	#[serde(default)]
+	pub index_mirrors: Vec<String>,
+	pub index_url: Option<String>,
+	pub index_token: Option<String>,
+	/// SDK installed by Geode CLI or registered with `geode sdk set-path`,
+	/// used when GEODE_SDK isn't set
+	pub sdk_path: Option<PathBuf>,
+	/// Whether `sdk_path` was registered with `geode sdk set-path` rather
+	/// than installed, so uninstalling leaves it alone
+	#[serde(default)]
+	pub sdk_linked: bool,
+	/// Commit `geode sdk update --commit` pinned the SDK to, instead of
+	/// following its branch
+	pub sdk_commit: Option<String>,
+	/// Android NDK installed with `geode sdk install-toolchain android`, used
+	/// when ANDROID_NDK_ROOT isn't set
+	pub android_ndk: Option<PathBuf>,
+	/// Indexer location given with `--path`, only used for the current run
+	#[serde(skip)]
+	pub indexer_path_override: Option<PathBuf>,
 	#[serde(flatten)]
 	other: HashMap<String, Value>,
 }
@@ -62,6 +108,7 @@ impl OldConfig {
 								.unwrap_or(&inst.executable)
 								.into(),
 							gd_path: inst.path.clone(),
+							sdk_version: None,
 							other: HashMap::new(),
 						})
 					})
@@ -78,11 +125,64 @@ impl OldConfig {
 			profiles,
 			default_developer: self.default_developer.to_owned(),
 			sdk_nightly: false,
+			github_token: None,
+			git_name: None,
+			git_email: None,
+			indexer_path: None,
+			indexer_keep_history: false,
+			indexer_shallow: false,
+			compress_pngs: false,
+			package_compression: None,
+			proxy: None,
+			sdk_mirrors: Vec::new(),
+			binaries_mirrors: Vec::new(),
+			index_mirrors: Vec::new(),
+			index_url: None,
+			index_token: None,
+			sdk_path: None,
+			sdk_linked: false,
+			sdk_commit: None,
+			android_ndk: None,
+			indexer_path_override: None,
 			other: HashMap::new(),
 		}
 	}
 }
 
+/// Downloads that can come from mirrors instead of GitHub
+#[derive(Clone, Copy, Debug)]
+pub enum Download {
+	Sdk,
+	Binaries,
+	Index,
+}
+
+impl Download {
+	fn official(self) -> &'static str {
+		match self {
+			Download::Sdk => "https://github.com/geode-sdk/geode",
+			Download::Binaries => "https://github.com/geode-sdk/geode/releases/download",
+			Download::Index => "https://github.com/geode-sdk/mods/zipball/main",
+		}
+	}
+
+	fn env_var(self) -> &'static str {
+		match self {
+			Download::Sdk => "GEODE_SDK_MIRRORS",
+			Download::Binaries => "GEODE_BINARIES_MIRRORS",
+			Download::Index => "GEODE_INDEX_MIRRORS",
+		}
+	}
+}
+
+/// Proxy settings for git operations. libgit2 only uses the proxy from git's
+/// config or the environment when asked to
+pub fn proxy_options<'a>() -> git2::ProxyOptions<'a> {
+	let mut opts = git2::ProxyOptions::new();
+	opts.auto();
+	opts
+}
+
 pub fn geode_root() -> PathBuf {
 	// get data dir per-platform
 	let data_dir: PathBuf;
@@ -107,6 +207,7 @@ impl Profile {
 		Profile {
 			name,
 			gd_path: location,
+			sdk_version: None,
 			other: HashMap::<String, Value>::new(),
 		}
 	}
@@ -122,6 +223,27 @@ impl Profile {
 	pub fn mods_dir(&self) -> PathBuf {
 		self.geode_dir().join("mods")
 	}
+
+	pub fn unzipped_dir(&self) -> PathBuf {
+		self.geode_dir().join("unzipped")
+	}
+
+	/// Where mods keep their save data, which is next to GD's saves rather
+	/// than in the profile
+	pub fn save_dir(&self) -> Option<PathBuf> {
+		#[cfg(windows)]
+		{
+			dirs::data_local_dir().map(|dir| dir.join("GeometryDash").join("geode").join("mods"))
+		}
+		#[cfg(target_os = "macos")]
+		{
+			dirs::data_dir().map(|dir| dir.join("GeometryDash").join("geode").join("mods"))
+		}
+		#[cfg(not(any(windows, target_os = "macos")))]
+		{
+			None
+		}
+	}
 }
 
 impl Config {
@@ -169,6 +291,27 @@ impl Config {
 		Ok(path)
 	}
 
+	/// Where to get a download from, in the order to try them: the mirrors
+	/// from the environment (comma-separated) or else the config, then GitHub
+	pub fn download_sources(&self, download: Download) -> Vec<String> {
+		let configured = match download {
+			Download::Sdk => &self.sdk_mirrors,
+			Download::Binaries => &self.binaries_mirrors,
+			Download::Index => &self.index_mirrors,
+		};
+		let mut sources = match std::env::var(download.env_var()) {
+			Ok(var) => var.split(',')
+				.map(|s| s.trim().to_string())
+				.filter(|s| !s.is_empty())
+				.collect(),
+			Err(_) => configured.clone(),
+		};
+		let official = download.official().to_string();
+		sources.retain(|s| s.trim_end_matches('/') != official);
+		sources.push(official);
+		sources
+	}
+
 	pub fn sdk_path() -> PathBuf {
 		match Self::try_sdk_path() {
 			Ok(path) => path,
@@ -188,6 +331,25 @@ impl Config {
 				profiles: Vec::new(),
 				default_developer: None,
 				sdk_nightly: false,
+				github_token: None,
+				git_name: None,
+				git_email: None,
+				indexer_path: None,
+				indexer_keep_history: false,
+				indexer_shallow: false,
+				compress_pngs: false,
+				package_compression: None,
+				proxy: None,
+				sdk_mirrors: Vec::new(),
+				binaries_mirrors: Vec::new(),
+				index_mirrors: Vec::new(),
+				index_url: None,
+				index_token: None,
+				sdk_path: None,
+				sdk_linked: false,
+				sdk_commit: None,
+				android_ndk: None,
+				indexer_path_override: None,
 				other: HashMap::<String, Value>::new(),
 			};
 		}
@@ -202,6 +364,25 @@ impl Config {
 				profiles: Vec::new(),
 				default_developer: None,
 				sdk_nightly: false,
+				github_token: None,
+				git_name: None,
+				git_email: None,
+				indexer_path: None,
+				indexer_keep_history: false,
+				indexer_shallow: false,
+				compress_pngs: false,
+				package_compression: None,
+				proxy: None,
+				sdk_mirrors: Vec::new(),
+				binaries_mirrors: Vec::new(),
+				index_mirrors: Vec::new(),
+				index_url: None,
+				index_token: None,
+				sdk_path: None,
+				sdk_linked: false,
+				sdk_commit: None,
+				android_ndk: None,
+				indexer_path_override: None,
 				other: HashMap::<String, Value>::new(),
 			}
 		} else {
@@ -224,6 +405,32 @@ impl Config {
 
 		output.save();
 
+		// Set it for the whole run so CMake and other tools started by the CLI
+		// find it the same way
+		if std::env::var_os("GEODE_SDK").is_none() {
+			// An SDK at the default install path is used even if it was
+			// installed before the CLI remembered where
+			let default_sdk = geode_root().join("sdk");
+			if let Some(ref path) = output.sdk_path {
+				std::env::set_var("GEODE_SDK", path);
+			} else if default_sdk.join("VERSION").exists() {
+				std::env::set_var("GEODE_SDK", default_sdk);
+			}
+		}
+		if std::env::var_os("ANDROID_NDK_ROOT").is_none() {
+			if let Some(ref path) = output.android_ndk {
+				std::env::set_var("ANDROID_NDK_ROOT", path);
+			}
+		}
+		// Both reqwest and libgit2 pick the proxy up from the environment
+		if let Some(ref proxy) = output.proxy {
+			for var in ["HTTP_PROXY", "HTTPS_PROXY"] {
+				if std::env::var_os(var).is_none() && std::env::var_os(var.to_lowercase()).is_none() {
+					std::env::set_var(var, proxy);
+				}
+			}
+		}
+
 		if output.profiles.is_empty() {
 			warn!("No Geode profiles found! Some operations will be unavailable.");
 			warn!("Setup Geode using `geode config setup`");
@@ -1,29 +1,51 @@
 use semver::{VersionReq, Version};
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::io::Read;
 use std::path::{PathBuf, Path};
 use crate::spritesheet::SpriteSheet;
+use crate::fatal;
 
 trait Glob {
-	fn glob(self) -> Self;
+	fn glob(self) -> Result<Self, String> where Self: Sized;
 }
 
 impl Glob for Vec<PathBuf> {
-	fn glob(self) -> Self {
-		self
-			.into_iter()
-			.flat_map(|src|
-				glob::glob(
-					std::env::current_dir().unwrap()
-						.join(&src)
-						.to_str()
-						.unwrap()
-				)
-				.unwrap_or_else(|_| panic!("Invalid glob pattern {}", src.to_str().unwrap()))
-				.map(|g| g.unwrap())
-			).collect()
+	/// Expand glob patterns, leaving out files matched by patterns starting
+	/// with `!`
+	fn glob(self) -> Result<Self, String> {
+		let root = std::env::current_dir().unwrap();
+		let mut files = Vec::new();
+		let mut excludes = Vec::new();
+
+		for src in &self {
+			let src = src.to_str().unwrap();
+			if let Some(exclude) = src.strip_prefix('!') {
+				excludes.push(
+					glob::Pattern::new(root.join(exclude).to_str().unwrap())
+						.map_err(|e| format!("Invalid glob pattern {}: {}", src, e))?
+				);
+				continue;
+			}
+
+			let matches = glob::glob(root.join(src).to_str().unwrap())
+				.map_err(|e| format!("Invalid glob pattern {}: {}", src, e))?
+				.filter_map(|g| g.ok())
+				.collect::<Vec<_>>();
+			if matches.is_empty() {
+				return Err(format!("'{}' doesn't match any files", src));
+			}
+			for file in matches {
+				if !files.contains(&file) {
+					files.push(file);
+				}
+			}
+		}
+
+		files.retain(|file| !excludes.iter().any(|exclude| exclude.matches_path(file)));
+		Ok(files)
 	}
 }
 
@@ -31,23 +53,22 @@ fn parse_glob<'de, D>(deserializer: D) -> Result<Vec<PathBuf>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    Ok(Vec::<PathBuf>::deserialize(deserializer)?.glob())
+    Vec::<PathBuf>::deserialize(deserializer)?.glob().map_err(serde::de::Error::custom)
 }
 
 fn parse_spritesheets<'de, D>(deserializer: D) -> Result<HashMap<String, SpriteSheet>, D::Error>
 where
     D: Deserializer<'de>,
 {
-	Ok(HashMap::<String, Vec<PathBuf>>::deserialize(deserializer)?
+	HashMap::<String, Vec<PathBuf>>::deserialize(deserializer)?
 		.into_iter()
         .map(|(name, srcs)| {
-			(name.clone(), SpriteSheet {
+			Ok((name.clone(), SpriteSheet {
 				name,
-				files: srcs.glob()
-			})
+				files: srcs.glob().map_err(serde::de::Error::custom)?
+			}))
         })
 		.collect()
-	)
 }
 
 fn parse_version<'de, D>(deserializer: D) -> Result<Version, D::Error>
@@ -208,23 +229,289 @@ pub struct ModFileInfo {
 	pub api: Option<ModApi>,
 }
 
-pub fn try_parse_mod_info(root_path: &Path) -> Result<ModFileInfo, String> {
-	let data = if root_path.is_dir() {
+/// Read mod.json from a mod's folder, a .geode package or the file itself
+fn read_mod_json_data(root_path: &Path) -> Result<String, String> {
+	if root_path.is_dir() {
 		std::fs::read_to_string(root_path.join("mod.json"))
-		.map_err(|e| format!("Unable to read mod.json: {e}"))?
+			.map_err(|e| format!("Unable to read mod.json: {e}"))
+	} else if root_path.extension().is_some_and(|ext| ext == "json") {
+		std::fs::read_to_string(root_path)
+			.map_err(|e| format!("Unable to read mod.json: {e}"))
 	} else {
 		let mut out = String::new();
 
-		zip::ZipArchive::new(fs::File::open(root_path).unwrap())
+		zip::ZipArchive::new(fs::File::open(root_path).map_err(|e| format!("Unable to open package: {e}"))?)
 			.map_err(|e| format!("Unable to unzip: {e}"))?
 			.by_name("mod.json")
 			.map_err(|e| format!("Unable to find mod.json in package: {e}"))?
 			.read_to_string(&mut out)
 			.map_err(|e| format!("Unable to read mod.json: {e}"))?;
 
-		out
+		Ok(out)
+	}
+}
+
+pub fn read_mod_json(root_path: &Path) -> Result<Value, String> {
+	serde_json::from_str(&read_mod_json_data(root_path)?)
+		.map_err(|e| format!("Could not parse mod.json: {e}"))
+}
+
+/// Format mod.json like the ones `geode new` makes, indented with tabs
+pub fn format_mod_json(json: &Value) -> Result<Vec<u8>, String> {
+	let formatter = serde_json::ser::PrettyFormatter::with_indent(b"\t");
+	let mut ser = serde_json::Serializer::with_formatter(Vec::new(), formatter);
+	json.serialize(&mut ser).map_err(|e| format!("Unable to serialize mod.json: {e}"))?;
+	Ok(ser.into_inner())
+}
+
+/// Write a mod's folder's mod.json, formatted like the ones `geode new` makes
+pub fn write_mod_json(root_path: &Path, json: &Value) -> Result<(), String> {
+	std::fs::write(root_path.join("mod.json"), format_mod_json(json)?)
+		.map_err(|e| format!("Unable to write mod.json: {e}"))
+}
+
+fn type_name(value: &Value) -> &'static str {
+	match value {
+		Value::Null => "null",
+		Value::Bool(_) => "a bool",
+		Value::Number(_) => "a number",
+		Value::String(_) => "a string",
+		Value::Array(_) => "an array",
+		Value::Object(_) => "an object",
+	}
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+	String,
+	Bool,
+	Number,
+	Array,
+	Object,
+}
+
+impl Kind {
+	fn name(self) -> &'static str {
+		match self {
+			Kind::String => "a string",
+			Kind::Bool => "a bool",
+			Kind::Number => "a number",
+			Kind::Array => "an array",
+			Kind::Object => "an object",
+		}
+	}
+}
+
+/// Collects schema errors along with the key path they happened at
+struct SchemaCheck {
+	errors: Vec<String>,
+}
+
+impl SchemaCheck {
+	fn error(&mut self, path: &str, msg: String) {
+		self.errors.push(format!("[mod.json]{path}: {msg}"));
+	}
+
+	fn expect<'a>(&mut self, value: &'a Value, path: &str, expected: Kind) -> Option<&'a Value> {
+		let matches = match expected {
+			Kind::String => value.is_string(),
+			Kind::Bool => value.is_boolean(),
+			Kind::Number => value.is_number(),
+			Kind::Array => value.is_array(),
+			Kind::Object => value.is_object(),
+		};
+		if !matches {
+			self.error(path, format!("Expected {}, found {}", expected.name(), type_name(value)));
+			return None;
+		}
+		Some(value)
+	}
+
+	fn field<'a>(
+		&mut self,
+		obj: &'a serde_json::Map<String, Value>,
+		path: &str,
+		key: &str,
+		expected: Kind,
+		required: bool,
+	) -> Option<&'a Value> {
+		match obj.get(key) {
+			Some(value) => self.expect(value, &format!("{path}.{key}"), expected),
+			None => {
+				if required {
+					self.error(path, format!("Missing key '{key}'"));
+				}
+				None
+			}
+		}
+	}
+
+	fn string_array(&mut self, obj: &serde_json::Map<String, Value>, path: &str, key: &str) {
+		if let Some(Value::Array(items)) = self.field(obj, path, key, Kind::Array, false) {
+			for (i, item) in items.iter().enumerate() {
+				self.expect(item, &format!("{path}.{key}[{i}]"), Kind::String);
+			}
+		}
+	}
+
+	fn version(&mut self, value: Option<&Value>, path: &str) {
+		if let Some(version) = value.and_then(|v| v.as_str()) {
+			if let Err(e) = Version::parse(version.strip_prefix('v').unwrap_or(version)) {
+				self.error(path, format!("'{version}' is not a valid version: {e}"));
+			}
+		}
+	}
+}
+
+/// Longest mod ID the index accepts
+const MAX_ID_LENGTH: usize = 64;
+
+/// Characters allowed in either part of an ID
+fn is_id_char(c: char) -> bool {
+	c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'
+}
+
+/// Why an ID isn't one the index accepts, if it isn't. IDs look like
+/// `developer.mod-name`, in lowercase
+pub fn id_problem(id: &str) -> Option<String> {
+	let Some((dev, name)) = id.split_once('.') else {
+		return Some("it should look like `developer.mod-name`".into());
+	};
+	if dev.is_empty() || name.is_empty() {
+		Some("the developer and mod name parts can't be empty".into())
+	} else if name.contains('.') {
+		Some("it can only have one `.`, between the developer and mod name".into())
+	} else if id.chars().any(|c| c.is_ascii_uppercase()) {
+		Some("it should be in lowercase".into())
+	} else if let Some(c) = id.chars().find(|c| *c != '.' && !is_id_char(*c)) {
+		Some(format!("'{c}' isn't allowed, only lowercase letters, digits, `-` and `_` are"))
+	} else if id.len() > MAX_ID_LENGTH {
+		Some(format!("it can be at most {MAX_ID_LENGTH} characters long"))
+	} else {
+		None
+	}
+}
+
+/// Whether an ID is one the index accepts, see `id_problem` for why not
+pub fn is_valid_id(id: &str) -> bool {
+	id_problem(id).is_none()
+}
+
+/// The valid ID closest to an invalid `developer.mod-name` one
+pub fn suggest_id(id: &str) -> Option<String> {
+	let (dev, name) = id.split_once('.')?;
+	let part = |s: &str| s
+		.trim()
+		.to_lowercase()
+		.chars()
+		.map(|c| if c.is_whitespace() || c == '.' { '-' } else { c })
+		.filter(|c| is_id_char(*c))
+		.collect::<String>()
+		.trim_matches('-')
+		.to_string();
+	let suggestion = format!("{}.{}", part(dev), part(name))
+		.chars()
+		.take(MAX_ID_LENGTH)
+		.collect::<String>();
+	is_valid_id(&suggestion).then_some(suggestion)
+}
+
+/// Check mod.json's structure against the Geode mod.json schema, returning
+/// every problem found
+pub fn validate_mod_json(json: &Value) -> Vec<String> {
+	let mut check = SchemaCheck { errors: Vec::new() };
+	let Some(root) = json.as_object() else {
+		check.error("", format!("Expected an object, found {}", type_name(json)));
+		return check.errors;
 	};
 
+	let geode = check.field(root, "", "geode", Kind::String, true);
+	check.version(geode, ".geode");
+
+	if let Some(id) = check.field(root, "", "id", Kind::String, true).and_then(|v| v.as_str()) {
+		if let Some(problem) = id_problem(id) {
+			check.error(".id", format!("'{id}' is not a valid ID: {problem}"));
+		}
+	}
+
+	let version = check.field(root, "", "version", Kind::String, true);
+	check.version(version, ".version");
+
+	for key in ["name", "developer", "description"] {
+		check.field(root, "", key, Kind::String, true);
+	}
+	for key in ["repository", "support", "license"] {
+		check.field(root, "", key, Kind::String, false);
+	}
+	check.string_array(root, "", "tags");
+
+	match root.get("binary") {
+		None | Some(Value::String(_)) => {},
+		Some(Value::Object(binaries)) => {
+			for (platform, name) in binaries {
+				check.expect(name, &format!(".binary.{platform}"), Kind::String);
+			}
+		},
+		Some(other) => check.error(
+			".binary",
+			format!("Expected a string or an object, found {}", type_name(other)),
+		),
+	}
+
+	if let Some(Value::Array(deps)) = check.field(root, "", "dependencies", Kind::Array, false) {
+		for (i, dep) in deps.iter().enumerate() {
+			let path = format!(".dependencies[{i}]");
+			let Some(Value::Object(dep)) = check.expect(dep, &path, Kind::Object) else { continue; };
+			check.field(dep, &path, "id", Kind::String, true);
+			if let Some(version) = check.field(dep, &path, "version", Kind::String, true).and_then(|v| v.as_str()) {
+				if let Err(e) = VersionReq::parse(&version.replace('v', "")) {
+					check.error(&format!("{path}.version"), format!("'{version}' is not a valid version range: {e}"));
+				}
+			}
+			check.field(dep, &path, "required", Kind::Bool, false);
+		}
+	}
+
+	if let Some(Value::Object(res)) = check.field(root, "", "resources", Kind::Object, false) {
+		for key in ["libraries", "files", "sprites"] {
+			check.string_array(res, ".resources", key);
+		}
+		if let Some(Value::Object(sheets)) = check.field(res, ".resources", "spritesheets", Kind::Object, false) {
+			for name in sheets.keys() {
+				check.string_array(sheets, ".resources.spritesheets", name);
+			}
+		}
+		if let Some(Value::Object(fonts)) = check.field(res, ".resources", "fonts", Kind::Object, false) {
+			for (name, font) in fonts {
+				let path = format!(".resources.fonts.{name}");
+				let Some(Value::Object(font)) = check.expect(font, &path, Kind::Object) else { continue; };
+				check.field(font, &path, "path", Kind::String, true);
+				check.field(font, &path, "size", Kind::Number, true);
+				check.field(font, &path, "charset", Kind::String, false);
+				check.field(font, &path, "outline", Kind::Number, false);
+				if let Some(color) = check.field(font, &path, "color", Kind::String, false).and_then(|v| v.as_str()) {
+					if let Err(e) = Color::parse_hex(color) {
+						check.error(&format!("{path}.color"), e);
+					}
+				}
+			}
+		}
+	}
+
+	if let Some(Value::Object(api)) = check.field(root, "", "api", Kind::Object, false) {
+		if api.contains_key("include") {
+			check.string_array(api, ".api", "include");
+		} else {
+			check.error(".api", "Missing key 'include'".into());
+		}
+	}
+
+	check.errors
+}
+
+pub fn try_parse_mod_info(root_path: &Path) -> Result<ModFileInfo, String> {
+	let data = read_mod_json_data(root_path)?;
+
 	// to make globs work, relink current directory to the one mod.json is in
 	let old = std::env::current_dir().or(Err("Unable to get current directory"))?;
 
@@ -237,14 +524,14 @@ pub fn try_parse_mod_info(root_path: &Path) -> Result<ModFileInfo, String> {
 	).or(Err("Unable to relink working directory"))?;
 	
 	let res = serde_json::from_str(&data)
-		.map_err(|e| format!("Could not parse mod.json: {e}"))?;
+		.map_err(|e| format!("Could not parse mod.json: {e}"));
 	
 	// then link it back to where-ever it was
 	std::env::set_current_dir(old).or(Err("Unable to reset working directory"))?;
 
-	Ok(res)
+	res
 }
 
 pub fn parse_mod_info(root_path: &Path) -> ModFileInfo {
-	try_parse_mod_info(root_path).unwrap()
+	try_parse_mod_info(root_path).unwrap_or_else(|e| fatal!("{}", e))
 }
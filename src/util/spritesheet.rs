@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use image::{imageops, ImageFormat, RgbaImage};
 use serde_json::json;
@@ -217,7 +218,7 @@ fn extract_from_cache(
 pub fn get_spritesheet_bundles(
 	sheet: &SpriteSheet,
 	working_dir: &Path,
-	cache: &mut Option<CacheBundle>,
+	cache: &Mutex<Option<CacheBundle>>,
 	mod_info: &ModFileInfo,
 	shut_up: bool,
 ) -> SheetBundles {
@@ -225,7 +226,7 @@ pub fn get_spritesheet_bundles(
 		info!("Fetching spritesheet {}", sheet.name.bright_yellow());
 	}
 
-	if let Some(cache_bundle) = cache {
+	if let Some(cache_bundle) = cache.lock().unwrap().as_mut() {
 		// Cache found
 		if let Some(p) = cache_bundle.cache.fetch_spritesheet_bundles(sheet) {
 			if !shut_up {
@@ -0,0 +1,392 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::Subcommand;
+use serde::{Deserialize, Serialize};
+
+use crate::package::{self, PackageOptions};
+use crate::sdk;
+use crate::util::config::Config;
+use crate::util::mod_file::{try_parse_mod_info, ModFileInfo};
+use crate::{done, fatal, info, warn};
+
+/// Manifest at the root of a repository with several mods in it
+pub const WORKSPACE_FILE: &str = "geode-workspace.json";
+
+#[derive(Subcommand, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub enum Workspace {
+	/// Create a workspace manifest in the current directory listing the mods
+	/// inside it
+	Init {
+		/// Folders or globs of the member mods, like `mods/*`. If none are
+		/// given, every folder with a mod.json is added
+		#[clap(num_args(0..))]
+		members: Vec<String>,
+	},
+
+	/// List the mods in the workspace and which members they depend on
+	List,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct WorkspaceManifest {
+	/// Folders of the member mods, relative to the workspace. May be globs
+	members: Vec<String>,
+	/// Build directory of each member, relative to the member
+	#[serde(default = "default_build_dir")]
+	build_dir: String,
+}
+
+fn default_build_dir() -> String {
+	"build".into()
+}
+
+pub struct Member {
+	pub path: PathBuf,
+	pub info: ModFileInfo,
+}
+
+pub struct WorkspaceInfo {
+	pub root: PathBuf,
+	pub build_dir: String,
+	pub members: Vec<Member>,
+}
+
+impl WorkspaceInfo {
+	/// The workspace `dir` is in, if any
+	pub fn find(dir: &Path) -> Option<WorkspaceInfo> {
+		let dir = dir.canonicalize().ok()?;
+		let root = dir.ancestors().find(|d| d.join(WORKSPACE_FILE).is_file())?;
+		Some(WorkspaceInfo::load(root))
+	}
+
+	fn load(root: &Path) -> WorkspaceInfo {
+		let path = root.join(WORKSPACE_FILE);
+		let manifest: WorkspaceManifest = serde_json::from_str(
+			&fs::read_to_string(&path).unwrap_or_else(|e| fatal!("Unable to read {}: {}", path.display(), e))
+		).unwrap_or_else(|e| fatal!("Unable to parse {}: {}", path.display(), e));
+
+		let mut paths = Vec::new();
+		for pattern in &manifest.members {
+			let full = root.join(pattern).to_string_lossy().to_string();
+			let matches = glob::glob(&full)
+				.unwrap_or_else(|e| fatal!("Invalid member {} in {}: {}", pattern, WORKSPACE_FILE, e))
+				.filter_map(|p| p.ok())
+				.filter(|p| p.join("mod.json").is_file())
+				.collect::<Vec<_>>();
+			if matches.is_empty() {
+				warn!("No mods found at {} in {}", pattern, WORKSPACE_FILE);
+			}
+			paths.extend(matches);
+		}
+		paths.sort();
+		paths.dedup();
+
+		let mut members: Vec<Member> = Vec::new();
+		for path in paths {
+			let info = try_parse_mod_info(&path)
+				.unwrap_or_else(|e| fatal!("Unable to load workspace member {}: {}", path.display(), e));
+			if let Some(other) = members.iter().find(|m| m.info.id == info.id) {
+				fatal!(
+					"{} and {} both have the ID {}",
+					other.path.display(), path.display(), info.id
+				);
+			}
+			members.push(Member { path, info });
+		}
+
+		WorkspaceInfo {
+			root: root.to_path_buf(),
+			build_dir: manifest.build_dir,
+			members,
+		}
+	}
+
+	fn get(&self, id: &str) -> Option<&Member> {
+		self.members.iter().find(|m| m.info.id == id)
+	}
+
+	/// Members of the workspace that `member` depends on
+	fn dependencies<'a>(&'a self, member: &'a Member) -> impl Iterator<Item = &'a Member> {
+		member.info.dependencies.iter().filter_map(|dep| self.get(&dep.id))
+	}
+
+	/// Add a member after the members it depends on
+	fn visit<'a>(&'a self, member: &'a Member, order: &mut Vec<&'a Member>, visiting: &mut Vec<&'a str>) {
+		if order.iter().any(|m| m.info.id == member.info.id) {
+			return;
+		}
+		if visiting.contains(&member.info.id.as_str()) {
+			fatal!(
+				"Workspace members depend on each other: {} -> {}",
+				visiting.join(" -> "), member.info.id
+			);
+		}
+		visiting.push(&member.info.id);
+		for dep in self.dependencies(member) {
+			self.visit(dep, order, visiting);
+		}
+		visiting.pop();
+		order.push(member);
+	}
+
+	/// The member with this ID or folder name
+	fn find_member(&self, name: &str) -> Option<&Member> {
+		self.members.iter().find(|m| {
+			m.info.id == name || m.path.file_name().is_some_and(|f| f.to_string_lossy() == name)
+		})
+	}
+
+	/// The chosen members, along with the members they depend on in the
+	/// order they should be built if `with_dependencies` is set
+	fn select(&self, member: Option<String>, all: bool, with_dependencies: bool) -> Vec<&Member> {
+		let roots = if all {
+			self.members.iter().collect()
+		} else if let Some(name) = member {
+			let Some(found) = self.find_member(&name) else {
+				fatal!(
+					"No member called {} in the workspace. Members: {}",
+					name,
+					self.members.iter().map(|m| m.info.id.as_str()).collect::<Vec<_>>().join(", ")
+				);
+			};
+			vec![found]
+		} else {
+			let current = std::env::current_dir().unwrap().canonicalize().unwrap();
+			let Some(found) = self.members.iter().find(|m| current.starts_with(&m.path)) else {
+				fatal!("Not inside a workspace member, give the name of one or use --all");
+			};
+			vec![found]
+		};
+
+		if !with_dependencies {
+			return roots;
+		}
+		let mut order = Vec::new();
+		for root in roots {
+			self.visit(root, &mut order, &mut Vec::new());
+		}
+		order
+	}
+
+	fn member_build_dir(&self, member: &Member) -> PathBuf {
+		member.path.join(&self.build_dir)
+	}
+
+	fn built_package(&self, member: &Member) -> PathBuf {
+		self.member_build_dir(member).join(format!("{}.geode", member.info.id))
+	}
+}
+
+/// Versions of the other mods in the workspace `dir` is in, which are built
+/// alongside it instead of coming from the index
+pub fn sibling_versions(dir: &Path) -> Vec<(String, semver::Version)> {
+	let Some(workspace) = WorkspaceInfo::find(dir) else {
+		return Vec::new();
+	};
+	let dir = dir.canonicalize().unwrap();
+	workspace.members
+		.into_iter()
+		.filter(|m| m.path != dir)
+		.map(|m| (m.info.id, m.info.version))
+		.collect()
+}
+
+/// Whether `name` isn't a path but the ID or folder name of a member of the
+/// workspace the current directory is in, for commands that take either
+pub fn is_member(name: &Path) -> bool {
+	!name.exists() && WorkspaceInfo::find(&std::env::current_dir().unwrap())
+		.is_some_and(|workspace| workspace.find_member(&name.to_string_lossy()).is_some())
+}
+
+fn current_workspace() -> WorkspaceInfo {
+	WorkspaceInfo::find(&std::env::current_dir().unwrap()).unwrap_or_else(|| fatal!(
+		"No {} found in this directory or any above it. Create one with `geode workspace init`",
+		WORKSPACE_FILE
+	))
+}
+
+fn init(members: Vec<String>) {
+	let root = std::env::current_dir().unwrap();
+	let path = root.join(WORKSPACE_FILE);
+	if path.exists() {
+		fatal!("{} already exists", path.display());
+	}
+
+	let members = if members.is_empty() {
+		let mut found = walkdir::WalkDir::new(&root)
+			.min_depth(1)
+			.max_depth(3)
+			.into_iter()
+			.filter_entry(|e| {
+				let name = e.file_name().to_string_lossy();
+				!name.starts_with('.') && name != "build" && name != "node_modules"
+			})
+			.filter_map(|e| e.ok())
+			.filter(|e| e.file_type().is_dir() && e.path().join("mod.json").is_file())
+			.map(|e| e.path().strip_prefix(&root).unwrap().to_string_lossy().replace('\\', "/"))
+			.collect::<Vec<_>>();
+		found.sort();
+		if found.is_empty() {
+			fatal!("No mods found in this directory, give the folders of the members");
+		}
+		found
+	} else {
+		members
+	};
+
+	let manifest = WorkspaceManifest {
+		members,
+		build_dir: default_build_dir(),
+	};
+	fs::write(&path, serde_json::to_string_pretty(&manifest).unwrap())
+		.unwrap_or_else(|e| fatal!("Unable to write {}: {}", path.display(), e));
+
+	let workspace = WorkspaceInfo::load(&root);
+	for member in &workspace.members {
+		info!("Added {} ({})", member.info.id, member.path.strip_prefix(&root).unwrap().display());
+	}
+	done!("Workspace created with {} members", workspace.members.len());
+}
+
+fn list(workspace: &WorkspaceInfo) {
+	if workspace.members.is_empty() {
+		info!("The workspace has no members");
+		return;
+	}
+	for member in &workspace.members {
+		let deps = workspace.dependencies(member).map(|d| d.info.id.as_str()).collect::<Vec<_>>();
+		println!(
+			"{} v{} ({}){}",
+			member.info.id,
+			member.info.version,
+			member.path.strip_prefix(&workspace.root).unwrap_or(&member.path).display(),
+			if deps.is_empty() { String::new() } else { format!(", uses {}", deps.join(", ")) }
+		);
+	}
+}
+
+/// Build a member of the workspace the current directory is in, or every
+/// member with `all`. Outside of a workspace, the mod in the current directory
+/// is built
+pub fn build(member: Option<String>, all: bool, config: &str) {
+	let current = std::env::current_dir().unwrap();
+	if member.is_none() && !all && WorkspaceInfo::find(&current).is_none() {
+		let info = try_parse_mod_info(&current).unwrap_or_else(|e| fatal!("{}", e));
+		build_members(&default_build_dir(), &[&Member { path: current, info }], config);
+		return;
+	}
+	let workspace = current_workspace();
+	build_members(&workspace.build_dir, &workspace.select(member, all, true), config);
+}
+
+fn build_members(build_dir: &str, members: &[&Member], config: &str) {
+	let Ok(cmake) = which::which("cmake") else {
+		fatal!("CMake not found. CMake is required to build Geode projects.");
+	};
+
+	for member in members {
+		info!("Building {}", member.info.id);
+
+		// Build against the SDK version the mod targets if it's installed
+		sdk::check_sdk_version(&member.info.id, &member.info.geode);
+		let sdk_path = sdk::resolve_sdk(&member.info.geode);
+		let win32 = sdk::is_win32(&sdk::build_sdk_version(&member.info.geode));
+
+		if !member.path.join(build_dir).join("CMakeCache.txt").exists() {
+			let mut configure = std::process::Command::new(&cmake);
+			if let Some(ref sdk_path) = sdk_path {
+				configure.env("GEODE_SDK", sdk_path);
+			}
+			configure
+				.current_dir(&member.path)
+				.arg("-B")
+				.arg(build_dir)
+				.arg(format!("-DCMAKE_BUILD_TYPE={}", config));
+			// GD on macOS only runs on Intel
+			if cfg!(windows) {
+				configure.arg("-A").arg(if win32 { "Win32" } else { "x64" });
+			} else if cfg!(target_os = "macos") {
+				configure.arg("-DCMAKE_OSX_ARCHITECTURES=x86_64");
+			}
+			let status = configure.status().expect("Unable to run CMake");
+			if !status.success() {
+				fatal!("Unable to configure {}", member.info.id);
+			}
+		}
+
+		let mut build = std::process::Command::new(&cmake);
+		if let Some(ref sdk_path) = sdk_path {
+			build.env("GEODE_SDK", sdk_path);
+		}
+		let status = build
+			.current_dir(&member.path)
+			.arg("--build")
+			.arg(build_dir)
+			.arg("--config")
+			.arg(config)
+			.status()
+			.expect("Unable to run CMake");
+		if !status.success() {
+			fatal!("Unable to build {}", member.info.id);
+		}
+	}
+
+	done!("Built {} mods", members.len());
+}
+
+/// Package what's been built for a member of the workspace, or for every
+/// member with `all`, into their build directories
+pub fn package(
+	config: &mut Config,
+	member: Option<String>,
+	all: bool,
+	binaries: Vec<PathBuf>,
+	options: PackageOptions,
+) {
+	let workspace = current_workspace();
+	for member in workspace.select(member, all, false) {
+		info!("Packaging {}", member.info.id);
+		let binaries = if binaries.is_empty() {
+			package::find_binaries(&workspace.member_build_dir(member))
+		} else {
+			binaries.clone()
+		};
+		if binaries.is_empty() && member.info.api.is_none() {
+			fatal!(
+				"No binaries found in {}, build it with `geode build` first",
+				workspace.member_build_dir(member).display()
+			);
+		}
+		package::create_package(config, &member.path, binaries.clone(), PackageOptions {
+			output: Some(options.output.clone().unwrap_or_else(|| workspace.built_package(member))),
+			api: options.api || binaries.is_empty(),
+			..options.clone()
+		});
+	}
+}
+
+/// Install the packages built for a member of the workspace, or for every
+/// member with `all`
+pub fn install(config: &mut Config, member: Option<String>, all: bool) {
+	let workspace = current_workspace();
+	for member in workspace.select(member, all, false) {
+		let path = workspace.built_package(member);
+		if !path.exists() {
+			fatal!(
+				"{} hasn't been packaged yet, package it with `geode package new {}`",
+				member.info.id, member.info.id
+			);
+		}
+		package::install(config, &path);
+	}
+}
+
+pub fn subcommand(cmd: Workspace) {
+	match cmd {
+		Workspace::Init { members } => init(members),
+		Workspace::List => list(&current_workspace()),
+	}
+}
@@ -0,0 +1,49 @@
+/**
+ * Include the Geode headers.
+ */
+#include <Geode/Geode.hpp>
+#include <Geode/loader/SettingEvent.hpp>
+#include <Geode/modify/MenuLayer.hpp>
+
+/**
+ * Brings cocos2d and all Geode namespaces to the current scope.
+ */
+using namespace geode::prelude;
+
+/**
+ * Code in `$on_mod(Loaded)` runs once the mod has been loaded, which is the
+ * place to set up event listeners.
+ */
+$on_mod(Loaded) {
+	listenForSettingChanges("greeting", +[](std::string value) {
+		log::info("The greeting is now '{}'", value);
+	});
+}
+
+/**
+ * `$modify` lets you extend and modify GD's classes.
+ * To hook a function, $modify the class and write a new
+ * definition with the signature of the function you want to hook.
+ */
+class $modify(MenuLayer) {
+	bool init() {
+		if (!MenuLayer::init()) {
+			return false;
+		}
+
+		if (Mod::get()->getSettingValue<bool>("show-greeting")) {
+			auto greeting = Mod::get()->getSettingValue<std::string>("greeting");
+			Notification::create(greeting + " from {{MOD_NAME}}!", NotificationIcon::Success)->show();
+		}
+
+		/**
+		 * Sprites listed under "resources" in mod.json are prefixed with
+		 * the mod's ID.
+		 */
+		auto icon = CCSprite::create("{{MOD_ID}}/icon.png");
+		icon->setPosition({ 30.f, 30.f });
+		this->addChild(icon);
+
+		return true;
+	}
+};
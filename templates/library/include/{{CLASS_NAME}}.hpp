@@ -0,0 +1,24 @@
+#pragma once
+
+#include <Geode/Geode.hpp>
+
+/**
+ * On Windows functions have to be exported from the mod's binary to be
+ * usable from other mods, which this does when building the mod itself.
+ */
+#ifdef GEODE_IS_WINDOWS
+	#ifdef {{MACRO_NAME}}_EXPORTING
+		#define {{MACRO_NAME}}_DLL __declspec(dllexport)
+	#else
+		#define {{MACRO_NAME}}_DLL __declspec(dllimport)
+	#endif
+#else
+	#define {{MACRO_NAME}}_DLL __attribute__((visibility("default")))
+#endif
+
+namespace {{CLASS_NAME}} {
+	/**
+	 * Everything other mods can use goes here.
+	 */
+	{{MACRO_NAME}}_DLL int add(int a, int b);
+}
@@ -0,0 +1,10 @@
+/**
+ * Include the API this mod exports, so the definitions get exported.
+ */
+#include <{{CLASS_NAME}}.hpp>
+
+using namespace geode::prelude;
+
+int {{CLASS_NAME}}::add(int a, int b) {
+	return a + b;
+}
@@ -0,0 +1,27 @@
+/**
+ * Include the Geode headers.
+ */
+#include <Geode/Geode.hpp>
+
+/**
+ * Brings cocos2d and all Geode namespaces to the current scope.
+ */
+using namespace geode::prelude;
+
+/**
+ * `$modify` lets you extend and modify GD's classes.
+ * To hook a function, $modify the class and write a new
+ * definition with the signature of the function you want to hook.
+ */
+#include <Geode/modify/MenuLayer.hpp>
+class $modify(MenuLayer) {
+	bool init() {
+		if (!MenuLayer::init()) {
+			return false;
+		}
+
+		log::info("Hello from {{MOD_NAME}}!");
+
+		return true;
+	}
+};
@@ -0,0 +1,42 @@
+/**
+ * Include the Geode headers.
+ */
+#include <Geode/Geode.hpp>
+#include <Geode/modify/MenuLayer.hpp>
+#include "{{CLASS_NAME}}Popup.hpp"
+
+/**
+ * Brings cocos2d and all Geode namespaces to the current scope.
+ */
+using namespace geode::prelude;
+
+/**
+ * Adds a button to the main menu that opens a popup.
+ */
+class $modify(My{{CLASS_NAME}}MenuLayer, MenuLayer) {
+	bool init() {
+		if (!MenuLayer::init()) {
+			return false;
+		}
+
+		auto button = CCMenuItemSpriteExtra::create(
+			CircleButtonSprite::createWithSpriteFrameName("geode.loader/geode-logo-outline-gold.png"),
+			this,
+			menu_selector(My{{CLASS_NAME}}MenuLayer::onOpenPopup)
+		);
+
+		/**
+		 * Menus on the main menu have IDs that other mods can find
+		 * them with, which is safer than relying on their positions.
+		 */
+		auto menu = this->getChildByID("bottom-menu");
+		menu->addChild(button);
+		menu->updateLayout();
+
+		return true;
+	}
+
+	void onOpenPopup(CCObject*) {
+		{{CLASS_NAME}}Popup::create("Hello from {{MOD_NAME}}!")->show();
+	}
+};
@@ -0,0 +1,21 @@
+#include "{{CLASS_NAME}}Popup.hpp"
+
+bool {{CLASS_NAME}}Popup::setup(std::string const& text) {
+	this->setTitle("{{MOD_NAME}}");
+
+	auto label = CCLabelBMFont::create(text.c_str(), "bigFont.fnt");
+	label->setScale(.5f);
+	m_mainLayer->addChildAtPosition(label, Anchor::Center);
+
+	return true;
+}
+
+{{CLASS_NAME}}Popup* {{CLASS_NAME}}Popup::create(std::string const& text) {
+	auto ret = new {{CLASS_NAME}}Popup();
+	if (ret->initAnchored(240.f, 160.f, text)) {
+		ret->autorelease();
+		return ret;
+	}
+	delete ret;
+	return nullptr;
+}
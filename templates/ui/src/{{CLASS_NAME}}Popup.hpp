@@ -0,0 +1,17 @@
+#pragma once
+
+#include <Geode/Geode.hpp>
+
+using namespace geode::prelude;
+
+/**
+ * `Popup` handles the background, the close button and opening animations,
+ * so only the contents need to be set up in `setup`.
+ */
+class {{CLASS_NAME}}Popup : public Popup<std::string const&> {
+protected:
+	bool setup(std::string const& text) override;
+
+public:
+	static {{CLASS_NAME}}Popup* create(std::string const& text);
+};